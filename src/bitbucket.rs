@@ -0,0 +1,234 @@
+use crate::git::{PullRequest, RemoteCommit};
+use crate::http::{fetch_all_pages, http_get, parse_json, unexpected_response, HttpResponse};
+use regex::Regex;
+
+lazy_static! {
+    // Pull request reference, like `tombruijn/lintje#123`.
+    static ref BITBUCKET_PULL_REQUEST: Regex =
+        Regex::new(r"^([\w.-]+/[\w.-]+)#(\d+)$").unwrap();
+}
+
+pub const DEFAULT_API_URL: &str = "https://api.bitbucket.org/2.0";
+// The maximum number of commits the Bitbucket API returns per page.
+const COMMITS_PER_PAGE: usize = 100;
+
+/// A pull request in a Bitbucket Cloud repository, like `tombruijn/lintje#123`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestReference {
+    /// The workspace and repository slug, like `tombruijn/lintje`.
+    pub repository: String,
+    pub id: u32,
+}
+
+impl PullRequestReference {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid pull request: {}. Use the `workspace/repo#number` format.",
+                value
+            )
+        };
+        let captures = BITBUCKET_PULL_REQUEST
+            .captures(value.trim())
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            repository: captures[1].to_string(),
+            id: captures[2].parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Fetches pull requests using the Bitbucket Cloud REST API.
+#[derive(Debug)]
+pub struct Bitbucket {
+    api_url: String,
+    user: Option<String>,
+    token: Option<String>,
+}
+
+impl Bitbucket {
+    /// With a user, the token is used as an app password. Without a user, the token is used as an
+    /// access token.
+    pub fn new(api_url: &str, user: Option<String>, token: Option<String>) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            user,
+            token,
+        }
+    }
+
+    /// Fetch the branch name and commits of the pull request with the Bitbucket API, so the
+    /// commits don't need to be available in the local repository.
+    ///
+    /// The files changed in the commits are not fetched, to avoid a request per commit.
+    pub fn fetch_pull_request(
+        &self,
+        reference: &PullRequestReference,
+    ) -> Result<PullRequest, String> {
+        let url = format!(
+            "{}/repositories/{}/pullrequests/{}",
+            self.api_url, reference.repository, reference.id
+        );
+        let response = self.get(&url)?;
+        let branch = match response.status {
+            200 => parse_pull_request_branch(&response.body)?,
+            404 => {
+                return Err(format!(
+                    "Pull request not found on Bitbucket: {}#{}",
+                    reference.repository, reference.id
+                ))
+            }
+            _ => {
+                return Err(unexpected_response(
+                    "Bitbucket",
+                    "user and token",
+                    &response,
+                ))
+            }
+        };
+        let commits = fetch_all_pages(|page| {
+            let response = self.get(&format!(
+                "{}/commits?pagelen={}&page={}",
+                url, COMMITS_PER_PAGE, page
+            ))?;
+            if response.status != 200 {
+                return Err(unexpected_response(
+                    "Bitbucket",
+                    "user and token",
+                    &response,
+                ));
+            }
+            parse_pull_request_commits(&response.body)
+        })?;
+        // Bitbucket lists the newest commit first, like Git
+        Ok(PullRequest { branch, commits })
+    }
+
+    fn get(&self, url: &str) -> Result<HttpResponse, String> {
+        let (credentials, authorization) = match (&self.user, &self.token) {
+            (Some(user), Some(token)) => (Some(format!("{}:{}", user, token)), None),
+            (None, Some(token)) => (None, Some(format!("Authorization: Bearer {}", token))),
+            _ => (None, None),
+        };
+        let mut curl_options = vec![];
+        if let Some(credentials) = &credentials {
+            curl_options.push(("user", credentials.as_str()));
+        }
+        if let Some(authorization) = &authorization {
+            curl_options.push(("header", authorization.as_str()));
+        }
+        http_get(url, &curl_options)
+    }
+}
+
+fn parse_pull_request_branch(body: &str) -> Result<String, String> {
+    match parse_json("Bitbucket", body)?["source"]["branch"]["name"].as_str() {
+        Some(branch) => Ok(branch.to_string()),
+        None => Err(format!(
+            "Unable to find pull request branch in Bitbucket response: {}",
+            body
+        )),
+    }
+}
+
+// Returns the commits on the page and if there is a next page. Every page links to the next page,
+// until the last page.
+fn parse_pull_request_commits(body: &str) -> Result<(Vec<RemoteCommit>, bool), String> {
+    let json = parse_json("Bitbucket", body)?;
+    let commits = match json["values"].as_array() {
+        Some(commits) => commits,
+        None => {
+            return Err(format!(
+                "Unable to find commits in Bitbucket response: {}",
+                body
+            ))
+        }
+    };
+    let commits = commits
+        .iter()
+        .map(|commit| {
+            let message = commit["message"].as_str().ok_or_else(|| {
+                format!(
+                    "Unable to find commit message in Bitbucket response: {}",
+                    body
+                )
+            })?;
+            // The author is formatted like Git formats it: `Jane Doe <jane@example.com>`
+            let author = commit["author"]["raw"].as_str().unwrap_or_default();
+            let (author_name, email) = match author.split_once(" <") {
+                Some((name, email)) => (
+                    Some(name.trim().to_string()),
+                    Some(email.trim_end_matches('>').to_string()),
+                ),
+                None => (None, None),
+            };
+            Ok(RemoteCommit::from_api(
+                commit["hash"].as_str().map(String::from),
+                email,
+                author_name,
+                message.to_string(),
+            ))
+        })
+        .collect::<Result<Vec<RemoteCommit>, String>>()?;
+    Ok((commits, json["next"].is_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_pull_request_branch, parse_pull_request_commits, PullRequestReference};
+    use crate::git::RemoteCommit;
+
+    #[test]
+    fn test_pull_request_reference() {
+        assert_eq!(
+            PullRequestReference::parse("tombruijn/lintje#123"),
+            Ok(PullRequestReference {
+                repository: "tombruijn/lintje".to_string(),
+                id: 123
+            })
+        );
+        assert!(PullRequestReference::parse("tombruijn/lintje").is_err());
+        assert!(PullRequestReference::parse("#123").is_err());
+        assert!(PullRequestReference::parse("tombruijn/lintje!123").is_err());
+    }
+
+    #[test]
+    fn test_parse_pull_request_branch() {
+        assert_eq!(
+            parse_pull_request_branch(r#"{"id":1,"source":{"branch":{"name":"login-form"}}}"#),
+            Ok("login-form".to_string())
+        );
+        assert!(parse_pull_request_branch(r#"{"id":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_pull_request_commits() {
+        let body = r#"{
+            "values": [{
+                "hash": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                "message": "Add the login form\n\nUsers can now log in.\n",
+                "author": {"raw": "Tom <tom@example.com>"}
+            }],
+            "next": "https://api.bitbucket.org/2.0/page=2"
+        }"#;
+        assert_eq!(
+            parse_pull_request_commits(body),
+            Ok((
+                vec![RemoteCommit {
+                    sha: Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                    email: Some("tom@example.com".to_string()),
+                    author_name: Some("Tom".to_string()),
+                    message: "Add the login form\n\nUsers can now log in.\n".to_string(),
+                    has_changes: true,
+                }],
+                true
+            ))
+        );
+        assert_eq!(
+            parse_pull_request_commits(r#"{"values":[]}"#),
+            Ok((vec![], false))
+        );
+        assert!(parse_pull_request_commits(r#"{"type":"error"}"#).is_err());
+        assert!(parse_pull_request_commits(r#"{"values":[{"hash":"a"}]}"#).is_err());
+    }
+}
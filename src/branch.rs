@@ -1,6 +1,12 @@
+use crate::characters::{
+    escape_invisible_characters, escaped_invisible_character_range, find_confusables,
+    find_invisible_characters,
+};
 use crate::issue::{Context, Issue, Position};
+use crate::options::RuleOptions;
 use crate::rule::Rule;
-use crate::utils::{character_count_for_bytes_index, display_width, is_punctuation};
+use crate::stats;
+use crate::utils::{character_count_for_bytes_index, display_width, is_punctuation, pluralize};
 use core::ops::Range;
 use regex::{Regex, RegexBuilder};
 
@@ -19,8 +25,38 @@ lazy_static! {
         tempregex.multi_line(false);
         tempregex.build().unwrap()
     };
+    // Jira ticket numbers, like "JIRA-123" and "jira-123"
+    static ref BRANCH_TICKET: Regex = {
+        let mut tempregex = RegexBuilder::new(r"\b[a-z]{2,}-\d+");
+        tempregex.case_insensitive(true);
+        tempregex.build().unwrap()
+    };
+    // Branch names generated by GitHub when editing files or reverting pull requests in the web
+    // interface.
+    // patch-1
+    // username-patch-2
+    // revert-123-branch-name
+    static ref GENERATED_BRANCH: Regex =
+        Regex::new(r"^(([\w-]+-)?patch-\d+|revert-\d+-.+)$").unwrap();
 }
 
+// The order in which the branch rules are validated.
+const BRANCH_RULES: [Rule; 13] = [
+    Rule::BranchNameLength,
+    Rule::BranchNameTicketNumber,
+    Rule::BranchNameTicketPresence,
+    Rule::BranchNamePunctuation,
+    Rule::BranchNameCase,
+    Rule::BranchNameCharacters,
+    Rule::BranchNameCliche,
+    Rule::BranchNameForbiddenWord,
+    Rule::BranchNameDepth,
+    Rule::BranchNameGenerated,
+    Rule::BranchNameProtected,
+    Rule::BranchNameConfusable,
+    Rule::BranchNameInvisibleCharacter,
+];
+
 #[derive(Debug)]
 pub struct Branch {
     pub name: String,
@@ -39,11 +75,30 @@ impl Branch {
         self.issues.is_empty()
     }
 
-    pub fn validate(&mut self) {
-        self.validate_length();
-        self.validate_ticket_number();
-        self.validate_punctuation();
-        self.validate_cliche();
+    pub fn validate(&mut self, options: &RuleOptions) {
+        stats::measure("Branch rules", || {
+            for rule in BRANCH_RULES {
+                if options.rule_disabled(rule) {
+                    continue;
+                }
+                match rule {
+                    Rule::BranchNameLength => self.validate_length(),
+                    Rule::BranchNameTicketNumber => self.validate_ticket_number(options),
+                    Rule::BranchNameTicketPresence => self.validate_ticket_presence(options),
+                    Rule::BranchNamePunctuation => self.validate_punctuation(),
+                    Rule::BranchNameCase => self.validate_case(options),
+                    Rule::BranchNameCharacters => self.validate_characters(options),
+                    Rule::BranchNameCliche => self.validate_cliche(options),
+                    Rule::BranchNameForbiddenWord => self.validate_forbidden_words(options),
+                    Rule::BranchNameDepth => self.validate_depth(options),
+                    Rule::BranchNameGenerated => self.validate_generated(options),
+                    Rule::BranchNameProtected => self.validate_protected(options),
+                    Rule::BranchNameConfusable => self.validate_confusables(),
+                    Rule::BranchNameInvisibleCharacter => self.validate_invisible_characters(),
+                    _ => error!("Commit rule {} can't be validated for branches", rule),
+                }
+            }
+        });
     }
 
     fn validate_length(&mut self) {
@@ -67,8 +122,13 @@ impl Branch {
         }
     }
 
-    fn validate_ticket_number(&mut self) {
-        let name = &self.name;
+    fn validate_ticket_number(&mut self, options: &RuleOptions) {
+        // Replaced by the BranchNameTicketPresence rule
+        if options.require_branch_ticket {
+            return;
+        }
+
+        let (start, name) = self.name_without_prefix(options);
         if let Some(captures) = BRANCH_WITH_TICKET_NUMBER.captures(name) {
             let valid = match (captures.get(1), captures.get(2), captures.get(3)) {
                 (None, None, _) => false,
@@ -79,23 +139,56 @@ impl Branch {
             };
             if !valid {
                 let context = vec![Context::branch_error(
-                    name.to_string(),
+                    self.name.to_string(),
                     Range {
-                        start: 0,
-                        end: name.len(),
+                        start,
+                        end: self.name.len(),
                     },
                     "Remove the ticket number from the branch name or expand the branch name with more details".to_string(),
                 )];
                 self.add_error(
                     Rule::BranchNameTicketNumber,
                     "A ticket number was detected in the branch name".to_string(),
-                    1,
+                    character_count_for_bytes_index(&self.name, start),
                     context,
                 );
             }
         }
     }
 
+    // The configured patterns replace the built-in Jira ticket pattern.
+    fn validate_ticket_presence(&mut self, options: &RuleOptions) {
+        if !options.require_branch_ticket {
+            return;
+        }
+
+        let name = &self.name;
+        let has_ticket = if options.branch_ticket_patterns.is_empty() {
+            BRANCH_TICKET.is_match(name)
+        } else {
+            options
+                .branch_ticket_patterns
+                .iter()
+                .any(|pattern| pattern.is_match(name))
+        };
+        if !has_ticket {
+            let context = vec![Context::branch_error(
+                name.to_string(),
+                Range {
+                    start: 0,
+                    end: name.len(),
+                },
+                "Add a ticket number to the branch name".to_string(),
+            )];
+            self.add_error(
+                Rule::BranchNameTicketPresence,
+                "The branch name does not contain a ticket number".to_string(),
+                1,
+                context,
+            );
+        }
+    }
+
     fn validate_punctuation(&mut self) {
         match &self.name.chars().next() {
             Some(character) => {
@@ -156,26 +249,281 @@ impl Branch {
         }
     }
 
-    fn validate_cliche(&mut self) {
-        let branch = &self.name.to_lowercase();
-        if BRANCH_WITH_CLICHE.is_match(branch) {
+    // Report every run of uppercase characters as one issue, like the "JIRA" in "JIRA-123-fix".
+    fn validate_case(&mut self, options: &RuleOptions) {
+        if !options.check_branch_case {
+            return;
+        }
+        for (range, message) in character_runs(&self.name, "uppercase", char::is_uppercase) {
+            let characters = &self.name[range.clone()];
+            let context = vec![Context::branch_error(
+                self.name.to_string(),
+                range.clone(),
+                format!("Replace with `{}`", characters.to_lowercase()),
+            )];
+            self.add_error(
+                Rule::BranchNameCase,
+                message,
+                character_count_for_bytes_index(&self.name, range.start),
+                context,
+            );
+        }
+    }
+
+    // Report every run of characters that are not allowed as one issue, so that characters like
+    // emoji made up of multiple code points are reported once.
+    fn validate_characters(&mut self, options: &RuleOptions) {
+        if !options.check_branch_characters {
+            return;
+        }
+        let allowed = |character: char| {
+            character.is_ascii_alphanumeric()
+                || "-/_".contains(character)
+                || options.branch_characters.contains(character)
+        };
+        for (range, message) in character_runs(&self.name, "disallowed", |c| !allowed(c)) {
+            let context = vec![Context::branch_error(
+                self.name.to_string(),
+                range.clone(),
+                "Use letters, numbers, `-`, `/` or `_` instead".to_string(),
+            )];
+            self.add_error(
+                Rule::BranchNameCharacters,
+                message,
+                character_count_for_bytes_index(&self.name, range.start),
+                context,
+            );
+        }
+    }
+
+    fn validate_cliche(&mut self, options: &RuleOptions) {
+        let (start, name) = self.name_without_prefix(options);
+        if BRANCH_WITH_CLICHE.is_match(&name.to_lowercase()) {
             let context = vec![Context::branch_error(
-                branch.to_string(),
+                self.name.to_string(),
                 Range {
-                    start: 0,
-                    end: branch.len(),
+                    start,
+                    end: self.name.len(),
                 },
                 "Describe the change in more detail".to_string(),
             )];
             self.add_error(
                 Rule::BranchNameCliche,
                 "The branch name does not explain the change in much detail".to_string(),
+                character_count_for_bytes_index(&self.name, start),
+                context,
+            );
+        }
+    }
+
+    fn validate_forbidden_words(&mut self, options: &RuleOptions) {
+        let mut issues = vec![];
+        for word in &options.forbidden_branch_words {
+            let word = word.trim();
+            if word.is_empty() {
+                continue;
+            }
+            // Only match whole words, separated by any other character than letters and numbers.
+            let mut regex = RegexBuilder::new(&format!(
+                r"(?:^|[^\p{{L}}\p{{N}}])({})(?:$|[^\p{{L}}\p{{N}}])",
+                regex::escape(word)
+            ));
+            regex.case_insensitive(true);
+            let regex = match regex.build() {
+                Ok(regex) => regex,
+                Err(e) => {
+                    error!("BranchNameForbiddenWord: Invalid forbidden word: {}", e);
+                    continue;
+                }
+            };
+            for captures in regex.captures_iter(&self.name) {
+                if let Some(capture) = captures.get(1) {
+                    let context = vec![Context::branch_error(
+                        self.name.to_string(),
+                        capture.range(),
+                        "Remove the forbidden word from the branch name".to_string(),
+                    )];
+                    issues.push((
+                        format!(
+                            "The branch name contains the forbidden word `{}`",
+                            capture.as_str()
+                        ),
+                        character_count_for_bytes_index(&self.name, capture.start()),
+                        context,
+                    ));
+                }
+            }
+        }
+
+        for (message, column, context) in issues {
+            self.add_error(Rule::BranchNameForbiddenWord, message, column, context);
+        }
+    }
+
+    fn validate_depth(&mut self, options: &RuleOptions) {
+        let name = &self.name;
+        let depth = name.split('/').count();
+        if let Some(max_depth) = options.branch_max_depth {
+            if depth > max_depth {
+                // Mark the segments after the maximum depth, including the separator
+                let start = name
+                    .match_indices('/')
+                    .nth(max_depth.saturating_sub(1))
+                    .map_or(0, |(index, _)| index);
+                let context = vec![Context::branch_error(
+                    name.to_string(),
+                    Range {
+                        start,
+                        end: name.len(),
+                    },
+                    format!(
+                        "Use at most {} `/` separated {} in the branch name",
+                        max_depth,
+                        pluralize("segment", max_depth)
+                    ),
+                )];
+                self.add_error(
+                    Rule::BranchNameDepth,
+                    format!(
+                        "The branch name has {} segments, more than the maximum of {}",
+                        depth, max_depth
+                    ),
+                    character_count_for_bytes_index(name, start),
+                    context,
+                );
+                return;
+            }
+        }
+        if let Some(min_depth) = options.branch_min_depth {
+            if depth < min_depth {
+                let context = vec![Context::branch_error(
+                    name.to_string(),
+                    Range {
+                        start: 0,
+                        end: name.len(),
+                    },
+                    format!(
+                        "Use at least {} `/` separated {} in the branch name",
+                        min_depth,
+                        pluralize("segment", min_depth)
+                    ),
+                )];
+                self.add_error(
+                    Rule::BranchNameDepth,
+                    format!(
+                        "The branch name has {} {}, fewer than the minimum of {}",
+                        depth,
+                        pluralize("segment", depth),
+                        min_depth
+                    ),
+                    1,
+                    context,
+                );
+            }
+        }
+    }
+
+    fn validate_generated(&mut self, options: &RuleOptions) {
+        if options.allow_generated_branch_names {
+            return;
+        }
+
+        let name = &self.name;
+        if GENERATED_BRANCH.is_match(name) {
+            let context = vec![Context::branch_hint(
+                name.to_string(),
+                Range {
+                    start: 0,
+                    end: name.len(),
+                },
+                "Rename the branch to describe the change before opening a pull request"
+                    .to_string(),
+            )];
+            self.add_hint(
+                Rule::BranchNameGenerated,
+                "The branch name looks automatically generated".to_string(),
                 1,
                 context,
             );
         }
     }
 
+    fn validate_protected(&mut self, options: &RuleOptions) {
+        let name = &self.name;
+        if options.protected_branches.contains(name) {
+            let context = vec![Context::branch_error(
+                name.to_string(),
+                Range {
+                    start: 0,
+                    end: name.len(),
+                },
+                "Move the commits to a new branch and open a pull request".to_string(),
+            )];
+            self.add_error(
+                Rule::BranchNameProtected,
+                format!("The branch `{}` is a protected branch", name),
+                1,
+                context,
+            );
+        }
+    }
+
+    fn validate_confusables(&mut self) {
+        for (index, confusable) in find_confusables(&self.name) {
+            let context = vec![Context::branch_error(
+                self.name.to_string(),
+                Range {
+                    start: index,
+                    end: index + confusable.character.len_utf8(),
+                },
+                format!("Replace with `{}`", confusable.replacement),
+            )];
+            self.add_error(
+                Rule::BranchNameConfusable,
+                format!(
+                    "The branch name contains the confusable character `{}` ({})",
+                    confusable.character,
+                    confusable.code_point()
+                ),
+                character_count_for_bytes_index(&self.name, index),
+                context,
+            );
+        }
+    }
+
+    fn validate_invisible_characters(&mut self) {
+        for (index, invisible) in find_invisible_characters(&self.name) {
+            let context = vec![Context::branch_error(
+                escape_invisible_characters(&self.name),
+                escaped_invisible_character_range(&self.name, index),
+                "Remove the invisible character".to_string(),
+            )];
+            self.add_error(
+                Rule::BranchNameInvisibleCharacter,
+                format!(
+                    "The branch name contains the invisible character {}",
+                    invisible.code_point()
+                ),
+                character_count_for_bytes_index(&self.name, index),
+                context,
+            );
+        }
+    }
+
+    // The part of the branch name after the first matching allowed prefix, like `fix-signup` of
+    // `tom/fix-signup`, with the byte index it starts at.
+    fn name_without_prefix(&self, options: &RuleOptions) -> (usize, &str) {
+        for prefix in &options.branch_prefixes {
+            if prefix.is_empty() {
+                continue;
+            }
+            if let Some(name) = self.name.strip_prefix(prefix.as_str()) {
+                return (prefix.len(), name);
+            }
+        }
+        (0, &self.name)
+    }
+
     fn add_error(&mut self, rule: Rule, message: String, column: usize, context: Vec<Context>) {
         self.issues.push(Issue::error(
             rule,
@@ -184,18 +532,70 @@ impl Branch {
             context,
         ));
     }
+
+    fn add_hint(&mut self, rule: Rule, message: String, column: usize, context: Vec<Context>) {
+        self.issues.push(Issue::hint(
+            rule,
+            message,
+            Position::Branch { column },
+            context,
+        ));
+    }
 }
 
-#[cfg(test)]
+// The byte ranges of the runs of characters in the branch name that match the predicate, with
+// the message of the issue about them. Every run is reported as one issue.
+fn character_runs<F: Fn(char) -> bool>(
+    name: &str,
+    kind: &str,
+    predicate: F,
+) -> Vec<(Range<usize>, String)> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for (index, character) in name.char_indices() {
+        if !predicate(character) {
+            continue;
+        }
+        let end = index + character.len_utf8();
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end = end,
+            _ => ranges.push(index..end),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|range| {
+            let characters = &name[range.clone()];
+            let label = if characters.chars().count() == 1 {
+                "character"
+            } else {
+                "characters"
+            };
+            let message = format!(
+                "The branch name contains the {} {} `{}`",
+                kind, label, characters
+            );
+            (range, message)
+        })
+        .collect()
+}
+
+// The tests compare the formatted context of the issues, which requires the `color` feature
+#[cfg(all(test, feature = "color"))]
 mod tests {
     use crate::branch::Branch;
-    use crate::issue::{Issue, Position};
+    use crate::issue::{Issue, IssueType, Position};
+    use crate::options::RuleOptions;
     use crate::rule::Rule;
     use crate::utils::test::formatted_context;
+    use regex::Regex;
 
     fn validated_branch(name: String) -> Branch {
+        validated_branch_with_options(name, &RuleOptions::default())
+    }
+
+    fn validated_branch_with_options(name: String, options: &RuleOptions) -> Branch {
         let mut branch = Branch::new(name);
-        branch.validate();
+        branch.validate(options);
         branch
     }
 
@@ -333,6 +733,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_branch_ticket_presence() {
+        // Not checked unless enabled
+        assert_branch_name_as_valid("fix-user-signup", &Rule::BranchNameTicketPresence);
+
+        let options = RuleOptions {
+            require_branch_ticket: true,
+            ..RuleOptions::default()
+        };
+        for name in ["JIRA-123-fix-user-signup", "feature/jira-123", "fix-PROJ-1"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameTicketPresence);
+        }
+        for name in ["fix-user-signup", "123-fix-user-signup", "fix-signup-v2"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameTicketPresence);
+        }
+        // Replaces the BranchNameTicketNumber rule
+        let branch = validated_branch_with_options("JIRA-123".to_string(), &options);
+        assert_branch_valid_for(branch, &Rule::BranchNameTicketNumber);
+
+        let branch = validated_branch_with_options("fix-user-signup".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameTicketPresence);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The branch name does not contain a ticket number"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | fix-user-signup\n\
+             | ^^^^^^^^^^^^^^^ Add a ticket number to the branch name\n"
+        );
+
+        // The configured ticket patterns
+        let options = RuleOptions {
+            require_branch_ticket: true,
+            branch_ticket_patterns: vec![Regex::new(r"^\d+-").unwrap()],
+            ..RuleOptions::default()
+        };
+        let branch = validated_branch_with_options("123-fix-signup".to_string(), &options);
+        assert_branch_valid_for(branch, &Rule::BranchNameTicketPresence);
+        let branch = validated_branch_with_options("JIRA-123-fix".to_string(), &options);
+        assert_branch_invalid_for(branch, &Rule::BranchNameTicketPresence);
+    }
+
     #[test]
     fn test_validate_punctuation() {
         let subjects = vec!["fix-test", "fix-あ-test"];
@@ -401,6 +849,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_case() {
+        // Not validated unless enabled
+        assert_branch_name_as_valid("Fix-User-Signup", &Rule::BranchNameCase);
+
+        let options = RuleOptions {
+            check_branch_case: true,
+            ..RuleOptions::default()
+        };
+        for name in ["fix-user-signup", "feature/123-fix", "исправить-ошибку"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameCase);
+        }
+        for name in ["Fix-user-signup", "fix-JIRA-123", "feature/Ñu"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameCase);
+        }
+
+        let branch = validated_branch_with_options("JIRA-123-fix".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameCase);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the uppercase characters `JIRA`"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | JIRA-123-fix\n\
+             | ^^^^ Replace with `jira`\n"
+        );
+
+        let branch = validated_branch_with_options("Fix-User-signup".to_string(), &options);
+        let issues = branch
+            .issues
+            .iter()
+            .filter(|issue| issue.rule == Rule::BranchNameCase)
+            .collect::<Vec<&Issue>>();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[1].message,
+            "The branch name contains the uppercase character `U`"
+        );
+        assert_eq!(issues[1].position, Position::Branch { column: 5 });
+    }
+
+    #[test]
+    fn test_validate_characters() {
+        // Not validated unless enabled
+        assert_branch_name_as_valid("fix user signup", &Rule::BranchNameCharacters);
+
+        let options = RuleOptions {
+            check_branch_characters: true,
+            ..RuleOptions::default()
+        };
+        for name in ["fix-user-signup", "feature/fix_user_signup", "JIRA-123"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameCharacters);
+        }
+        for name in [
+            "fix user signup",
+            "fix-café-menu",
+            "fix&deploy",
+            "fix-$HOME",
+            "release-1.2",
+            "fix-✨",
+        ] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameCharacters);
+        }
+
+        let branch = validated_branch_with_options("fix-café-menu".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameCharacters);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the disallowed character `é`"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 8 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | fix-café-menu\n\
+             |        ^ Use letters, numbers, `-`, `/` or `_` instead\n"
+        );
+
+        let branch = validated_branch_with_options("fix-&&-deploy".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameCharacters);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the disallowed characters `&&`"
+        );
+
+        let options = RuleOptions {
+            check_branch_characters: true,
+            branch_characters: ".é".to_string(),
+            ..RuleOptions::default()
+        };
+        for name in ["release-1.2", "fix-café-menu"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameCharacters);
+        }
+    }
+
     #[test]
     fn test_validate_cliche() {
         let subjects = vec!["add-email-validation", "fix-brittle-test"];
@@ -449,4 +1002,277 @@ mod tests {
              | ^^^^^^^ Describe the change in more detail\n"
         );
     }
+
+    #[test]
+    fn test_branch_prefixes() {
+        let options = RuleOptions {
+            branch_prefixes: vec!["tom/".to_string(), "jane/".to_string()],
+            ..RuleOptions::default()
+        };
+        for name in [
+            "tom/fix-user-signup",
+            "jane/123-fix-user-signup",
+            "bob-fix-bug",
+        ] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameTicketNumber);
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameCliche);
+        }
+        for name in ["tom/123", "jane/JIRA-123", "tom/fix-123"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameTicketNumber);
+        }
+        for name in ["tom/fix-bug", "jane/wip", "tom/update-readme"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameCliche);
+        }
+
+        let branch = validated_branch_with_options("jane/JIRA-123".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameTicketNumber);
+        assert_eq!(issue.position, Position::Branch { column: 6 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | jane/JIRA-123\n\
+             |      ^^^^^^^^ Remove the ticket number from the branch name or expand the branch name with more details\n"
+        );
+
+        let branch = validated_branch_with_options("tom/Fix-bug".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameCliche);
+        assert_eq!(issue.position, Position::Branch { column: 5 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | tom/Fix-bug\n\
+             |     ^^^^^^^ Describe the change in more detail\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_forbidden_words() {
+        assert_branch_name_as_valid("master-fix-bug", &Rule::BranchNameForbiddenWord);
+
+        let options = RuleOptions {
+            forbidden_branch_words: vec!["master".to_string(), "acme corp".to_string()],
+            ..RuleOptions::default()
+        };
+        let valid_names = vec!["fix-bug", "fix-remastered-video", "masterful-fix"];
+        for name in valid_names {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameForbiddenWord);
+        }
+        let invalid_names = vec![
+            "master",
+            "master-fix",
+            "fix/master",
+            "fix_master_bug",
+            "Master-fix",
+            "fix-for-acme corp",
+        ];
+        for name in invalid_names {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_invalid_for(branch, &Rule::BranchNameForbiddenWord);
+        }
+
+        let branch = validated_branch_with_options("fix/MASTER-build".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameForbiddenWord);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the forbidden word `MASTER`"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 5 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | fix/MASTER-build\n\
+             |     ^^^^^^ Remove the forbidden word from the branch name\n"
+        );
+
+        let branch = validated_branch_with_options("master/fix/master".to_string(), &options);
+        let count = branch
+            .issues
+            .iter()
+            .filter(|issue| issue.rule == Rule::BranchNameForbiddenWord)
+            .count();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_validate_depth() {
+        assert_branch_name_as_valid("feature/login/form/fix", &Rule::BranchNameDepth);
+
+        let options = RuleOptions {
+            branch_max_depth: Some(2),
+            ..RuleOptions::default()
+        };
+        for name in ["login-form", "feature/login-form"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameDepth);
+        }
+        let branch = validated_branch_with_options("tom/feature/login-form".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameDepth);
+        assert_eq!(
+            issue.message,
+            "The branch name has 3 segments, more than the maximum of 2"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 12 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | tom/feature/login-form\n\
+             |            ^^^^^^^^^^^ Use at most 2 `/` separated segments in the branch name\n"
+        );
+
+        // Require exactly `type/description`
+        let options = RuleOptions {
+            branch_min_depth: Some(2),
+            branch_max_depth: Some(2),
+            ..RuleOptions::default()
+        };
+        let branch = validated_branch_with_options("feature/login-form".to_string(), &options);
+        assert_branch_valid_for(branch, &Rule::BranchNameDepth);
+        let branch = validated_branch_with_options("login-form".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameDepth);
+        assert_eq!(
+            issue.message,
+            "The branch name has 1 segment, fewer than the minimum of 2"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | login-form\n\
+             | ^^^^^^^^^^ Use at least 2 `/` separated segments in the branch name\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_generated() {
+        let valid_names = vec![
+            "patch-login-form",
+            "fix-patch-1-regression",
+            "revert-login-form",
+            "patch-notes",
+        ];
+        assert_branch_names_as_valid(valid_names, &Rule::BranchNameGenerated);
+
+        let invalid_names = vec![
+            "patch-1",
+            "patch-12",
+            "tombruijn-patch-2",
+            "tom-bruijn-patch-3",
+            "revert-123-login-form",
+        ];
+        assert_branch_names_as_invalid(invalid_names, &Rule::BranchNameGenerated);
+
+        let branch = validated_branch("tombruijn-patch-2".to_string());
+        let issue = find_issue(branch.issues, &Rule::BranchNameGenerated);
+        assert_eq!(issue.r#type, IssueType::Hint);
+        assert_eq!(
+            issue.message,
+            "The branch name looks automatically generated"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | tombruijn-patch-2\n\
+             | ----------------- Rename the branch to describe the change before opening a pull request\n"
+        );
+
+        let options = RuleOptions {
+            allow_generated_branch_names: true,
+            ..RuleOptions::default()
+        };
+        let branch = validated_branch_with_options("patch-1".to_string(), &options);
+        assert_branch_valid_for(branch, &Rule::BranchNameGenerated);
+    }
+
+    #[test]
+    fn test_validate_confusables() {
+        let valid_names = vec!["fix-user-signup", "fix-café-menu", "исправить-ошибку"];
+        assert_branch_names_as_valid(valid_names, &Rule::BranchNameConfusable);
+
+        let invalid_names = vec!["fix\u{2013}user-signup", "fix-l\u{043E}gin-form"];
+        assert_branch_names_as_invalid(invalid_names, &Rule::BranchNameConfusable);
+
+        let branch = validated_branch("fix\u{2013}signup".to_string());
+        let issue = find_issue(branch.issues, &Rule::BranchNameConfusable);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the confusable character `\u{2013}` (U+2013 EN DASH)"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 4 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | fix\u{2013}signup\n\
+             |    ^ Replace with `-`\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_invisible_characters() {
+        assert_branch_valid_for(
+            validated_branch("fix-user-signup".to_string()),
+            &Rule::BranchNameInvisibleCharacter,
+        );
+
+        let invalid_names = vec!["fix-user\u{200B}-signup", "fix-\u{202E}user-signup"];
+        assert_branch_names_as_invalid(invalid_names, &Rule::BranchNameInvisibleCharacter);
+
+        let branch = validated_branch("fix\u{200B}-signup".to_string());
+        let issue = find_issue(branch.issues, &Rule::BranchNameInvisibleCharacter);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(
+            issue.message,
+            "The branch name contains the invisible character U+200B ZERO WIDTH SPACE"
+        );
+        assert_eq!(issue.position, Position::Branch { column: 4 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | fix<U+200B>-signup\n\
+             |    ^^^^^^^^ Remove the invisible character\n"
+        );
+    }
+
+    #[test]
+    fn test_disabled_rules() {
+        let options = RuleOptions {
+            disabled_rules: vec![Rule::BranchNameLength],
+            ..Default::default()
+        };
+        let branch = validated_branch_with_options("fix".to_string(), &options);
+        assert_branch_valid_for(branch, &Rule::BranchNameLength);
+        assert_branch_name_as_invalid("fix", &Rule::BranchNameLength);
+    }
+
+    #[test]
+    fn test_validate_protected() {
+        let options = RuleOptions {
+            protected_branches: vec!["main".to_string(), "release".to_string()],
+            ..RuleOptions::default()
+        };
+        for name in ["fix-user-signup", "main-menu", "master"] {
+            let branch = validated_branch_with_options(name.to_string(), &options);
+            assert_branch_valid_for(branch, &Rule::BranchNameProtected);
+        }
+        // Not validated without protected branches
+        assert_branch_names_as_valid(vec!["main"], &Rule::BranchNameProtected);
+
+        let branch = validated_branch_with_options("release".to_string(), &options);
+        let issue = find_issue(branch.issues, &Rule::BranchNameProtected);
+        assert_eq!(issue.r#type, IssueType::Error);
+        assert_eq!(issue.message, "The branch `release` is a protected branch");
+        assert_eq!(issue.position, Position::Branch { column: 1 });
+        assert_eq!(
+            formatted_context(&issue),
+            "|\n\
+             | release\n\
+             | ^^^^^^^ Move the commits to a new branch and open a pull request\n"
+        );
+    }
 }
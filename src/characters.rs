@@ -0,0 +1,356 @@
+use core::ops::Range;
+use regex::Regex;
+
+lazy_static! {
+    static ref EMOJI: Regex =
+        Regex::new(r"^[\p{Extended_Pictographic}\p{Emoji_Modifier}]$").unwrap();
+    static ref LATIN: Regex = Regex::new(r"^\p{Script=Latin}$").unwrap();
+}
+
+/// A character that looks like, or is easily mistaken for, an ASCII character.
+#[derive(Debug, PartialEq)]
+pub struct ConfusableCharacter {
+    pub character: char,
+    pub name: &'static str,
+    pub replacement: &'static str,
+}
+
+impl ConfusableCharacter {
+    /// Returns the code point and name of the character, like `U+043E CYRILLIC SMALL LETTER O`.
+    pub fn code_point(&self) -> String {
+        format!("U+{:04X} {}", self.character as u32, self.name)
+    }
+}
+
+macro_rules! confusable {
+    ($character:expr, $name:expr, $replacement:expr) => {
+        ConfusableCharacter {
+            character: $character,
+            name: $name,
+            replacement: $replacement,
+        }
+    };
+}
+
+// Characters from other scripts that look like Latin letters, and typographic punctuation inserted
+// by word processors. The list is not exhaustive, but covers the characters most commonly found.
+const CONFUSABLES: &[ConfusableCharacter] = &[
+    // Cyrillic
+    confusable!('\u{0430}', "CYRILLIC SMALL LETTER A", "a"),
+    confusable!('\u{0435}', "CYRILLIC SMALL LETTER IE", "e"),
+    confusable!('\u{043E}', "CYRILLIC SMALL LETTER O", "o"),
+    confusable!('\u{0440}', "CYRILLIC SMALL LETTER ER", "p"),
+    confusable!('\u{0441}', "CYRILLIC SMALL LETTER ES", "c"),
+    confusable!('\u{0443}', "CYRILLIC SMALL LETTER U", "y"),
+    confusable!('\u{0445}', "CYRILLIC SMALL LETTER HA", "x"),
+    confusable!('\u{0455}', "CYRILLIC SMALL LETTER DZE", "s"),
+    confusable!(
+        '\u{0456}',
+        "CYRILLIC SMALL LETTER BYELORUSSIAN-UKRAINIAN I",
+        "i"
+    ),
+    confusable!('\u{0458}', "CYRILLIC SMALL LETTER JE", "j"),
+    confusable!('\u{0410}', "CYRILLIC CAPITAL LETTER A", "A"),
+    confusable!('\u{0412}', "CYRILLIC CAPITAL LETTER VE", "B"),
+    confusable!('\u{0415}', "CYRILLIC CAPITAL LETTER IE", "E"),
+    confusable!('\u{041A}', "CYRILLIC CAPITAL LETTER KA", "K"),
+    confusable!('\u{041C}', "CYRILLIC CAPITAL LETTER EM", "M"),
+    confusable!('\u{041D}', "CYRILLIC CAPITAL LETTER EN", "H"),
+    confusable!('\u{041E}', "CYRILLIC CAPITAL LETTER O", "O"),
+    confusable!('\u{0420}', "CYRILLIC CAPITAL LETTER ER", "P"),
+    confusable!('\u{0421}', "CYRILLIC CAPITAL LETTER ES", "C"),
+    confusable!('\u{0422}', "CYRILLIC CAPITAL LETTER TE", "T"),
+    confusable!('\u{0425}', "CYRILLIC CAPITAL LETTER HA", "X"),
+    // Greek
+    confusable!('\u{03B1}', "GREEK SMALL LETTER ALPHA", "a"),
+    confusable!('\u{03BF}', "GREEK SMALL LETTER OMICRON", "o"),
+    confusable!('\u{03BD}', "GREEK SMALL LETTER NU", "v"),
+    confusable!('\u{0391}', "GREEK CAPITAL LETTER ALPHA", "A"),
+    confusable!('\u{0392}', "GREEK CAPITAL LETTER BETA", "B"),
+    confusable!('\u{0395}', "GREEK CAPITAL LETTER EPSILON", "E"),
+    confusable!('\u{0397}', "GREEK CAPITAL LETTER ETA", "H"),
+    confusable!('\u{0399}', "GREEK CAPITAL LETTER IOTA", "I"),
+    confusable!('\u{039A}', "GREEK CAPITAL LETTER KAPPA", "K"),
+    confusable!('\u{039C}', "GREEK CAPITAL LETTER MU", "M"),
+    confusable!('\u{039D}', "GREEK CAPITAL LETTER NU", "N"),
+    confusable!('\u{039F}', "GREEK CAPITAL LETTER OMICRON", "O"),
+    confusable!('\u{03A1}', "GREEK CAPITAL LETTER RHO", "P"),
+    confusable!('\u{03A4}', "GREEK CAPITAL LETTER TAU", "T"),
+    confusable!('\u{03A7}', "GREEK CAPITAL LETTER CHI", "X"),
+    // Typographic punctuation
+    confusable!('\u{2018}', "LEFT SINGLE QUOTATION MARK", "'"),
+    confusable!('\u{2019}', "RIGHT SINGLE QUOTATION MARK", "'"),
+    confusable!('\u{201A}', "SINGLE LOW-9 QUOTATION MARK", "'"),
+    confusable!('\u{201C}', "LEFT DOUBLE QUOTATION MARK", "\""),
+    confusable!('\u{201D}', "RIGHT DOUBLE QUOTATION MARK", "\""),
+    confusable!('\u{201E}', "DOUBLE LOW-9 QUOTATION MARK", "\""),
+    confusable!('\u{2032}', "PRIME", "'"),
+    confusable!('\u{2033}', "DOUBLE PRIME", "\""),
+    confusable!('\u{2010}', "HYPHEN", "-"),
+    confusable!('\u{2011}', "NON-BREAKING HYPHEN", "-"),
+    confusable!('\u{2013}', "EN DASH", "-"),
+    confusable!('\u{2014}', "EM DASH", "-"),
+    confusable!('\u{2212}', "MINUS SIGN", "-"),
+    confusable!('\u{2026}', "HORIZONTAL ELLIPSIS", "..."),
+    confusable!('\u{00A0}', "NO-BREAK SPACE", " "),
+];
+
+fn confusable_character(character: char) -> Option<&'static ConfusableCharacter> {
+    CONFUSABLES
+        .iter()
+        .find(|confusable| confusable.character == character)
+}
+
+/// Find all confusable characters in the text. Returns the bytes index of every confusable
+/// character found, with the character's details.
+///
+/// Letters from other scripts are only reported when they're mixed with Latin letters in the same
+/// word, or when all other letters in the text are Latin letters. This way text written in, for
+/// example, Russian or Greek is not reported.
+pub fn find_confusables(text: &str) -> Vec<(usize, &'static ConfusableCharacter)> {
+    let only_latin_letters = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .all(|c| is_latin(c) || confusable_character(c).is_some());
+    let mut confusables = vec![];
+    let mut word_start = 0;
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word_index = text[word_start..]
+            .find(word)
+            .map_or(word_start, |index| word_start + index);
+        word_start = word_index + word.len();
+        if word.is_empty() {
+            continue;
+        }
+        let mixed_script = word.chars().any(is_latin);
+        if !mixed_script && !only_latin_letters {
+            continue;
+        }
+        for (index, character) in word.char_indices() {
+            if let Some(confusable) = confusable_character(character) {
+                confusables.push((word_index + index, confusable));
+            }
+        }
+    }
+    // Punctuation and spaces are not part of words, so find them separately.
+    for (index, character) in text.char_indices() {
+        if character.is_alphanumeric() {
+            continue;
+        }
+        if let Some(confusable) = confusable_character(character) {
+            confusables.push((index, confusable));
+        }
+    }
+    confusables.sort_by_key(|(index, _)| *index);
+    confusables
+}
+
+// Latin letters, including accented letters like `é`, by their Unicode Script property.
+fn is_latin(character: char) -> bool {
+    LATIN.is_match(character.encode_utf8(&mut [0; 4]))
+}
+
+/// A character that is not visible when printed, but changes how text is rendered or searched.
+#[derive(Debug, PartialEq)]
+pub struct InvisibleCharacter {
+    pub character: char,
+    pub name: &'static str,
+}
+
+impl InvisibleCharacter {
+    /// Returns the code point and name of the character, like `U+200B ZERO WIDTH SPACE`.
+    pub fn code_point(&self) -> String {
+        format!("U+{:04X} {}", self.character as u32, self.name)
+    }
+}
+
+macro_rules! invisible {
+    ($character:expr, $name:expr) => {
+        InvisibleCharacter {
+            character: $character,
+            name: $name,
+        }
+    };
+}
+
+// Zero-width characters and bidirectional control characters. Bidirectional control characters
+// change the order in which text is displayed, which can be used to make text look different from
+// what it actually is.
+const INVISIBLE_CHARACTERS: &[InvisibleCharacter] = &[
+    // Zero-width characters
+    invisible!('\u{00AD}', "SOFT HYPHEN"),
+    invisible!('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    invisible!('\u{200B}', "ZERO WIDTH SPACE"),
+    invisible!('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    invisible!('\u{200D}', "ZERO WIDTH JOINER"),
+    invisible!('\u{2060}', "WORD JOINER"),
+    invisible!('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+    // Bidirectional control characters
+    invisible!('\u{061C}', "ARABIC LETTER MARK"),
+    invisible!('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    invisible!('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    invisible!('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    invisible!('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    invisible!('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    invisible!('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    invisible!('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    invisible!('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    invisible!('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    invisible!('\u{2068}', "FIRST STRONG ISOLATE"),
+    invisible!('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+];
+
+fn invisible_character(character: char) -> Option<&'static InvisibleCharacter> {
+    INVISIBLE_CHARACTERS
+        .iter()
+        .find(|invisible| invisible.character == character)
+}
+
+/// Find all zero-width and bidirectional control characters in the text. Returns the bytes index
+/// of every character found, with the character's details.
+///
+/// Zero width joiners that combine emoji, like in family emoji, are not reported.
+pub fn find_invisible_characters(text: &str) -> Vec<(usize, &'static InvisibleCharacter)> {
+    let mut characters = vec![];
+    let mut previous_character = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, character)) = chars.next() {
+        if let Some(invisible) = invisible_character(character) {
+            let next_character = chars.peek().map(|(_, c)| *c);
+            let joins_emoji = character == '\u{200D}'
+                && matches!(previous_character, Some(c) if is_emoji(c))
+                && matches!(next_character, Some(c) if is_emoji(c));
+            if !joins_emoji {
+                characters.push((index, invisible));
+            }
+        }
+        previous_character = Some(character);
+    }
+    characters
+}
+
+fn is_emoji(character: char) -> bool {
+    let mut buffer = [0; 4];
+    EMOJI.is_match(character.encode_utf8(&mut buffer))
+}
+
+/// Replace all zero-width and bidirectional control characters with a visible placeholder, like
+/// `<U+200B>`, so that they can be printed and pointed out.
+pub fn escape_invisible_characters(text: &str) -> String {
+    text.chars()
+        .map(|character| match invisible_character(character) {
+            Some(_) => format!("<U+{:04X}>", character as u32),
+            None => character.to_string(),
+        })
+        .collect()
+}
+
+/// Returns the range of the placeholder of the invisible character at the bytes index in the text
+/// escaped with `escape_invisible_characters`.
+pub fn escaped_invisible_character_range(text: &str, index: usize) -> Range<usize> {
+    let start = escape_invisible_characters(&text[..index]).len();
+    let character = text[index..].chars().next().unwrap_or_default();
+    Range {
+        start,
+        end: start + escape_invisible_characters(&character.to_string()).len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        escape_invisible_characters, escaped_invisible_character_range, find_confusables,
+        find_invisible_characters,
+    };
+
+    fn confusable_indexes(text: &str) -> Vec<(usize, &'static str)> {
+        find_confusables(text)
+            .iter()
+            .map(|(index, confusable)| (*index, confusable.replacement))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_confusables() {
+        assert_eq!(confusable_indexes("Fix bug in user signup"), vec![]);
+        // Cyrillic o in a Latin word
+        assert_eq!(confusable_indexes("Fix b\u{043E}ok"), vec![(5, "o")]);
+        // Word completely of Cyrillic lookalikes in a Latin text
+        assert_eq!(
+            confusable_indexes("Fix \u{0441}\u{043E}\u{0441}\u{043E}"),
+            vec![(4, "c"), (6, "o"), (8, "c"), (10, "o")]
+        );
+        assert_eq!(
+            confusable_indexes("Fix \u{201C}quotes\u{201D}"),
+            vec![(4, "\""), (13, "\"")]
+        );
+        assert_eq!(confusable_indexes("Fix\u{00A0}space"), vec![(3, " ")]);
+        // Accented Latin letters are Latin letters too
+        assert_eq!(
+            confusable_indexes("Fix caf\u{00E9} \u{0441}\u{043E}\u{0441}\u{043E}"),
+            vec![(10, "c"), (12, "o"), (14, "c"), (16, "o")]
+        );
+        assert_eq!(confusable_indexes("Fix \u{00E9}t\u{0435}"), vec![(7, "e")]);
+    }
+
+    #[test]
+    fn test_find_confusables_other_scripts() {
+        // Russian text with letters that are not confusable
+        assert_eq!(confusable_indexes("Исправить ошибку"), vec![]);
+        // Greek text
+        assert_eq!(confusable_indexes("Διόρθωση σφάλματος"), vec![]);
+        // Text with accents
+        assert_eq!(confusable_indexes("Fix café"), vec![]);
+    }
+
+    #[test]
+    fn test_code_point() {
+        let (_, confusable) = find_confusables("b\u{043E}ok")[0];
+        assert_eq!(confusable.code_point(), "U+043E CYRILLIC SMALL LETTER O");
+    }
+
+    fn invisible_indexes(text: &str) -> Vec<(usize, &'static str)> {
+        find_invisible_characters(text)
+            .iter()
+            .map(|(index, invisible)| (*index, invisible.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_invisible_characters() {
+        assert_eq!(invisible_indexes("Fix bug in user signup"), vec![]);
+        assert_eq!(
+            invisible_indexes("Fix\u{200B}bug"),
+            vec![(3, "ZERO WIDTH SPACE")]
+        );
+        assert_eq!(
+            invisible_indexes("Fix \u{202E}gub\u{202C}"),
+            vec![
+                (4, "RIGHT-TO-LEFT OVERRIDE"),
+                (10, "POP DIRECTIONAL FORMATTING")
+            ]
+        );
+        assert_eq!(
+            invisible_indexes("a\u{200D}b"),
+            vec![(1, "ZERO WIDTH JOINER")]
+        );
+        // Family emoji are combined with zero width joiners
+        assert_eq!(
+            invisible_indexes("Add \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} emoji"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_escape_invisible_characters() {
+        assert_eq!(escape_invisible_characters("Fix bug"), "Fix bug");
+        assert_eq!(
+            escape_invisible_characters("Fix\u{200B}bug\u{202E}"),
+            "Fix<U+200B>bug<U+202E>"
+        );
+        assert_eq!(
+            escaped_invisible_character_range("Fix\u{200B}bug\u{202E}", 9),
+            14..22
+        );
+    }
+}
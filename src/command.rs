@@ -1,5 +1,11 @@
 use std::ffi::OsStr;
-use std::process::Command;
+use std::io::{BufRead, BufReader, Write};
+use std::panic;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::Instant;
+
+use crate::stats;
 
 pub struct CommandError {
     pub code: Option<i32>,
@@ -22,64 +28,176 @@ pub fn run_command<S: AsRef<OsStr> + std::fmt::Debug>(
     cmd: &str,
     args: &[S],
 ) -> Result<String, CommandError> {
+    run_command_with_input(cmd, args, None)
+}
+
+/// Run the command and write the input to its STDIN. Use this to pass secrets, like API tokens, to
+/// a command without them showing up in the process list.
+pub fn run_command_with_input<S: AsRef<OsStr> + std::fmt::Debug>(
+    cmd: &str,
+    args: &[S],
+    input: Option<&str>,
+) -> Result<String, CommandError> {
+    let start = Instant::now();
     let mut command = Command::new(cmd);
-    command.args(args);
-    match command.output() {
+    command
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let result = command.spawn().and_then(|mut child| {
+        // Write the input from another thread while the output is read. Otherwise the command
+        // blocks on writing its output once the pipe buffer is full, while we block on writing
+        // the rest of the input.
+        let writer = match (input, child.stdin.take()) {
+            (Some(input), Some(mut stdin)) => {
+                let input = input.to_string();
+                Some(thread::spawn(move || stdin.write_all(input.as_bytes())))
+            }
+            _ => None,
+        };
+        let output = child.wait_with_output()?;
+        if let Some(writer) = writer {
+            writer.join().unwrap_or_else(|e| panic::resume_unwind(e))?;
+        }
+        Ok(output)
+    });
+    stats::record_command(command_line(cmd, args), start.elapsed());
+    match result {
         Ok(output) => {
-            let status = output.status;
             let stdout = String::from_utf8_lossy(&output.stdout);
-            if status.success() {
+            if output.status.success() {
                 Ok(stdout.to_string())
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                let status_code = status.code();
-                let (exit_code, additional_message) = match status_code {
-                    Some(127) => {
-                        // I've only seen this happen on emulated systems: host
-                        // architecture is different from the Docker image.
-                        // Otherwise it returns the OS error ErrorKind::NotFound.
-                        ("127".to_string(), " Is it installed?")
-                    }
-                    Some(code) => (code.to_string(), ""),
-                    None => ("unknown".to_string(), ""),
-                };
-                return Err(CommandError {
-                    code: status_code,
-                    message: format!(
-                        "Failed to run command.{}\n\
-                        Command: {}\n\
-                        Arguments: {:?}\n\
-                        Exit code: {}\n\
-                        STDOUT: {}\n\
-                        STDERR: {}",
-                        additional_message, cmd, args, exit_code, stdout, stderr
-                    ),
-                });
+                Err(failed_command_error(
+                    cmd,
+                    args,
+                    output.status,
+                    &stdout,
+                    &stderr,
+                ))
             }
         }
-        Err(e) => {
-            let additional_message = if e.kind() == std::io::ErrorKind::NotFound {
-                " Is it installed?"
-            } else {
-                ""
-            };
-            return Err(CommandError {
-                code: None,
-                message: format!(
-                    "Failed to run command.{}\n\
-                    Command: {}\n\
-                    Arguments: {:?}\n\
-                    Error: {}",
-                    additional_message, cmd, args, e
-                ),
-            });
+        Err(e) => Err(command_error(cmd, args, &e)),
+    }
+}
+
+/// Run the command and call the callback with every line of its STDOUT output while the command
+/// runs, without keeping the entire output in memory. Use this for commands with a lot of output.
+pub fn run_command_streaming<S, F>(
+    cmd: &str,
+    args: &[S],
+    mut on_line: F,
+) -> Result<(), CommandError>
+where
+    S: AsRef<OsStr> + std::fmt::Debug,
+    F: FnMut(&str),
+{
+    let start = Instant::now();
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| command_error(cmd, args, &e))?;
+    if let Some(stdout) = child.stdout.take() {
+        let mut reader = BufReader::new(stdout);
+        let mut line = vec![];
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    let text = String::from_utf8_lossy(&line);
+                    on_line(text.trim_end_matches(&['\r', '\n'][..]));
+                }
+                Err(e) => {
+                    let _ = child.kill();
+                    return Err(command_error(cmd, args, &e));
+                }
+            }
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| command_error(cmd, args, &e))?;
+    stats::record_command(command_line(cmd, args), start.elapsed());
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(failed_command_error(cmd, args, output.status, "", &stderr))
+    }
+}
+
+fn command_line<S: AsRef<OsStr>>(cmd: &str, args: &[S]) -> String {
+    let mut command_line = cmd.to_string();
+    for arg in args {
+        command_line.push(' ');
+        command_line.push_str(&arg.as_ref().to_string_lossy());
+    }
+    command_line
+}
+
+fn failed_command_error<S: std::fmt::Debug>(
+    cmd: &str,
+    args: &[S],
+    status: ExitStatus,
+    stdout: &str,
+    stderr: &str,
+) -> CommandError {
+    let status_code = status.code();
+    let (exit_code, additional_message) = match status_code {
+        Some(127) => {
+            // I've only seen this happen on emulated systems: host
+            // architecture is different from the Docker image.
+            // Otherwise it returns the OS error ErrorKind::NotFound.
+            ("127".to_string(), " Is it installed?")
         }
+        Some(code) => (code.to_string(), ""),
+        None => ("unknown".to_string(), ""),
+    };
+    CommandError {
+        code: status_code,
+        message: format!(
+            "Failed to run command.{}\n\
+            Command: {}\n\
+            Arguments: {:?}\n\
+            Exit code: {}\n\
+            STDOUT: {}\n\
+            STDERR: {}",
+            additional_message, cmd, args, exit_code, stdout, stderr
+        ),
+    }
+}
+
+fn command_error<S: std::fmt::Debug>(cmd: &str, args: &[S], e: &std::io::Error) -> CommandError {
+    let additional_message = if e.kind() == std::io::ErrorKind::NotFound {
+        " Is it installed?"
+    } else {
+        ""
+    };
+    CommandError {
+        code: None,
+        message: format!(
+            "Failed to run command.{}\n\
+            Command: {}\n\
+            Arguments: {:?}\n\
+            Error: {}",
+            additional_message, cmd, args, e
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::run_command;
+    use super::{run_command, run_command_streaming, run_command_with_input};
 
     #[test]
     fn test_command_success() {
@@ -89,6 +207,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_with_input() {
+        match run_command_with_input("cat", &["-"], Some("input text")) {
+            Ok(result) => assert_eq!(result, "input text"),
+            Err(e) => panic!("Unexpected failure: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_command_with_large_input() {
+        // More input and output than fits in the pipe buffers
+        let input = "0123456789abcdef\n".repeat(100_000);
+        match run_command_with_input("cat", &["-"], Some(&input)) {
+            Ok(result) => assert_eq!(result, input),
+            Err(e) => panic!("Unexpected failure: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_command_streaming() {
+        let mut lines = vec![];
+        match run_command_streaming("printf", &["line 1\\nline 2\\r\\n\\nline 4"], |line| {
+            lines.push(line.to_string());
+        }) {
+            Ok(()) => assert_eq!(lines, vec!["line 1", "line 2", "", "line 4"]),
+            Err(e) => panic!("Unexpected failure: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_command_streaming_failure() {
+        match run_command_streaming("support/test/failure_script", &["5", "hello"], |_| {}) {
+            Ok(()) => panic!("Unexpected success"),
+            Err(e) => {
+                assert_eq!(e.code, Some(5));
+                assert!(e.message.contains("STDERR: STDERR message\n"));
+            }
+        }
+    }
+
     #[test]
     fn test_command_exit_failure() {
         match run_command("support/test/failure_script", &["5", "hello"]) {
@@ -1,5 +1,26 @@
-use clap::{AppSettings, Parser};
-use std::path::PathBuf;
+use clap::{
+    AppSettings, ArgEnum, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand,
+    ValueSource,
+};
+use lintje::bitbucket;
+use lintje::git::{self, CommitSource};
+use lintje::github::{self, GitHub, PullRequestReference};
+use lintje::gitlab::{self, GitLabToken, MergeRequestReference};
+use lintje::http::http_get;
+use lintje::i18n::Language;
+use lintje::issue::{Issue, IssueType};
+use lintje::jira::Jira;
+use lintje::options::{
+    FetchOptions, ForbiddenPhrase, Lockfile, PathPattern, RuleOptions, SpellingWord,
+};
+use lintje::rule::{rule_by_any_name, Rule};
+use lintje::tickets::TicketTracker;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -28,14 +49,50 @@ Lint Git commits and branch name.
     lintje main..develop
       Validate the difference between the main and develop branch.
 
+    lintje --changed-since-push
+      Validate the commits created or changed since the last push.
+
+    lintje --repo=service-a --repo=service-b main..HEAD
+      Validate the difference with the main branch in multiple repositories.
+
+    lintje --repos-file=repositories.txt
+      Validate the latest commit of every repository listed in the file.
+
+    lintje --github-pr=tombruijn/lintje#123
+      Validate the commits and branch of a GitHub pull request, fetched with the GitHub API.
+
+    lintje --gitlab-mr
+      Validate the commits and branch of the merge request of a GitLab CI pipeline.
+
+    lintje --bitbucket-pr
+      Validate the commits and branch of the pull request of a Bitbucket Pipelines build.
+
     lintje --hook-message-file=.git/COMMIT_EDITMSG
       Lints the given commit message file from the commit-msg hook.
 
+    lintje --hook-message-file=.git/COMMIT_EDITMSG --hook-commit-source=commit
+      Lints the given commit message file of an amended commit.
+
+    lintje --hook=pre-commit
+      Lints the staged changes from the pre-commit hook.
+
+    lintje --template=.gitmessage
+      Lints the commit message template, without its comment lines.
+
+    lintje --explain SubjectLength
+      Explain what the SubjectLength rule checks and why.
+
+    lintje --docs
+      Show the documentation of all rules.
+
     lintje --no-branch
       Disable branch name validation.
 
     lintje --color
       Enable color output.
+
+    lintje --format=plain
+      Describe issues in full sentences, without ASCII art. Useful for screen readers.
 */
 pub struct Lint {
     /// Disable branch validation
@@ -46,26 +103,772 @@ pub struct Lint {
     #[clap(long = "no-hints", parse(from_flag = std::ops::Not::not))]
     pub hints: bool,
 
-    /// Enable color output
-    #[clap(long = "color")]
-    pub color: bool,
+    /// Enable color output. By default color is enabled when the output is a terminal and the
+    /// `NO_COLOR` environment variable is not set, or when the `CLICOLOR_FORCE` environment
+    /// variable is set. Use `--color=always` to also enable color when the output is piped.
+    #[clap(
+        long = "color",
+        arg_enum,
+        value_name = "when",
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = "always"
+    )]
+    pub color: Option<ColorWhen>,
 
     /// Disable color output
     #[clap(long = "no-color")]
     pub no_color: bool,
 
+    /// Only print the summary line when errors are found, without the issues. Prints nothing when
+    /// no errors are found.
+    #[clap(short, long, conflicts_with = "format")]
+    pub quiet: bool,
+
+    /// Print the number of issues per rule after the summary, to see which rules are reported the
+    /// most.
+    #[clap(long)]
+    pub rule_stats: bool,
+
+    /// Output format of the issues. The plain format describes issues in full sentences, without
+    /// gutters and underlines, for screen readers and logs. The compact format prints one line
+    /// per issue, for editors and `grep`.
+    #[clap(long, arg_enum, default_value = "text", value_name = "format")]
+    pub format: OutputFormat,
+
+    /// Group the issues of the text and plain formats. The commit group prints a header line per
+    /// commit with its number of issues, followed by its issues indented beneath it. The rule
+    /// group prints a header line per violated rule, followed by the issues of all commits that
+    /// violate it.
+    #[clap(long, arg_enum, value_name = "group")]
+    pub group_by: Option<GroupBy>,
+
+    /// Language of the issue messages. The rule names are not translated.
+    #[clap(
+        long,
+        arg_enum,
+        env = "LINTJE_LANG",
+        default_value = "en",
+        value_name = "language"
+    )]
+    pub lang: Language,
+
+    /// Lint all commits created or changed since the last push to the remote tracking branch.
+    #[clap(
+        long,
+        conflicts_with_all = &["commit (range)", "hook-message-file", "hook"]
+    )]
+    pub changed_since_push: bool,
+
+    /// Lint all commits reachable from HEAD, including the first commit of the repository.
+    #[clap(
+        long,
+        conflicts_with_all = &["commit (range)", "hook-message-file", "hook", "changed-since-push"]
+    )]
+    pub all: bool,
+
+    /// Lint the commits of the pull request with the given number. The pull request is fetched from
+    /// the `origin` remote if it's not available locally.
+    #[clap(
+        long = "pr",
+        value_name = "number",
+        conflicts_with_all = &["commit (range)", "hook-message-file", "hook", "changed-since-push", "all"]
+    )]
+    pub pull_request: Option<u32>,
+
+    /// Lint the commits of the current branch that are not on the default branch of the `origin`
+    /// remote, starting from the commit the branch was created from.
+    #[clap(
+        long,
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+        ]
+    )]
+    pub branch_range: bool,
+
+    /// Only lint the commits in the selected commit (range) by authors matching the pattern. The
+    /// pattern is a regular expression matched against the author's name and email address, like
+    /// Git's `--author` option.
+    #[clap(
+        long,
+        value_name = "pattern",
+        conflicts_with_all = &["hook-message-file", "hook", "stdin"]
+    )]
+    pub author: Option<String>,
+
+    /// Lint all commits made since the given date, like "2 weeks ago" or "2024-01-31". Commits
+    /// are selected from HEAD, or from the given commit (range).
+    #[clap(
+        long,
+        value_name = "date",
+        conflicts_with_all = &["hook-message-file", "hook", "stdin"]
+    )]
+    pub since: Option<String>,
+
+    /// Lint the commit message, or the commits in the `--stdin-format` format, read from STDIN
+    /// without reading commits from Git. Also enabled with `-` as the commit (range). The branch
+    /// is not validated.
+    #[clap(
+        long,
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+        ]
+    )]
+    pub stdin: bool,
+
+    /// Format of the input read from STDIN. The json format is an array of commit objects with a
+    /// `subject` and a `message` body, and optionally a `sha`, `has_changes` boolean and `author`
+    /// object with a `name` and `email`. The commits are listed like Git logs them, the newest
+    /// commit first.
+    #[clap(long, arg_enum, default_value = "message", value_name = "format")]
+    pub stdin_format: StdinFormat,
+
+    /// Lint the given commit message, without reading commits from Git. The branch is not
+    /// validated.
+    #[clap(
+        long,
+        value_name = "message",
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+        ]
+    )]
+    pub message: Option<String>,
+
+    /// Lint the commits and branch of the GitHub pull request, like `tombruijn/lintje#123`. The
+    /// pull request is fetched with the GitHub API, so the commits don't need to be available
+    /// locally, like in a shallow clone. The files changed in the commits are not validated.
+    #[clap(
+        long,
+        value_name = "owner/repo#number",
+        parse(try_from_str = PullRequestReference::parse),
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+            "message",
+        ]
+    )]
+    pub github_pr: Option<PullRequestReference>,
+
+    /// Lint the commits and branch of the GitLab merge request, like `group/project!123`. Without
+    /// a value, the merge request of the GitLab CI merge request pipeline is linted. The merge
+    /// request is fetched with the GitLab API, so the commits don't need to be available locally.
+    /// The files changed in the commits are not validated.
+    #[clap(
+        long,
+        value_name = "project!number",
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+            "message",
+            "github-pr",
+        ]
+    )]
+    #[allow(clippy::option_option)]
+    pub gitlab_mr: Option<Option<String>>,
+
+    /// Lint the commits and branch of the Bitbucket Cloud pull request, like
+    /// `workspace/repo#123`. Without a value, the pull request of the Bitbucket Pipelines build is
+    /// linted. The pull request is fetched with the Bitbucket API, so the commits don't need to be
+    /// available locally. The files changed in the commits are not validated.
+    #[clap(
+        long,
+        value_name = "workspace/repo#number",
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+            "message",
+            "github-pr",
+            "gitlab-mr",
+        ]
+    )]
+    #[allow(clippy::option_option)]
+    pub bitbucket_pr: Option<Option<String>>,
+
+    /// Start a Language Server Protocol server on STDIN and STDOUT, so editors can show the issues
+    /// while the commit message is written in the `COMMIT_EDITMSG` file, and the issues of the
+    /// commits in the `git-rebase-todo` file of an interactive rebase.
+    #[clap(
+        long,
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+            "message",
+            "github-pr",
+            "gitlab-mr",
+            "bitbucket-pr",
+        ]
+    )]
+    pub lsp: bool,
+
+    /// Lint the commit message template file, like `.gitmessage`, to check that the template
+    /// itself doesn't cause issues. Lines starting with the comment character are ignored, like
+    /// Git does when committing. Without a value, the template configured with the
+    /// `commit.template` Git config is linted. The branch is not validated.
+    #[clap(
+        long,
+        value_name = "file",
+        conflicts_with_all = &[
+            "commit (range)",
+            "hook-message-file",
+            "hook",
+            "changed-since-push",
+            "all",
+            "pull-request",
+            "branch-range",
+            "repositories",
+            "repos-file",
+            "stdin",
+            "message",
+            "github-pr",
+            "gitlab-mr",
+            "bitbucket-pr",
+            "lsp",
+        ]
+    )]
+    #[allow(clippy::option_option)]
+    pub template: Option<Option<PathBuf>>,
+
+    /// Lint the repository at the given path. Can be specified multiple times to lint multiple
+    /// repositories in one run. Paths with `*` and `?` wildcards, like `services/*`, lint every
+    /// repository that matches. Every repository is linted with the settings of its own config
+    /// files. The output settings, like the format, are those of the current directory.
+    #[clap(
+        long = "repo",
+        value_name = "path",
+        multiple_occurrences = true,
+        parse(from_os_str),
+        conflicts_with_all = &["hook-message-file", "hook"]
+    )]
+    pub repositories: Vec<PathBuf>,
+
+    /// Lint the repositories listed in the file, one path per line. Paths can contain wildcards.
+    /// Empty lines and lines starting with `#` are ignored.
+    #[clap(
+        long,
+        value_name = "file",
+        parse(from_os_str),
+        conflicts_with_all = &["hook-message-file", "hook"]
+    )]
+    pub repos_file: Option<PathBuf>,
+
+    /// Run as if Lintje was started in the given directory, like `git -C`. Other relative paths,
+    /// like the `--git-dir` path, are relative to this directory.
+    #[clap(short = 'C', value_name = "path", parse(from_os_str))]
+    pub directory: Option<PathBuf>,
+
+    /// Lint the repository of the Git directory at the given path, like a bare repository on a
+    /// server. Defaults to the repository of the working directory.
+    #[clap(
+        long,
+        value_name = "path",
+        parse(from_os_str),
+        conflicts_with_all = &["repositories", "repos-file"]
+    )]
+    pub git_dir: Option<PathBuf>,
+
+    /// Verify the Jira tickets referenced in commits exist, using the Jira instance at the URL.
+    #[clap(long, env = "LINTJE_JIRA_URL", value_name = "url")]
+    pub jira_url: Option<String>,
+
+    /// The Jira user to authenticate with, usually an email address.
+    #[clap(long, env = "LINTJE_JIRA_USER", value_name = "user")]
+    pub jira_user: Option<String>,
+
+    /// The Jira API token to authenticate with.
+    #[clap(
+        long,
+        env = "LINTJE_JIRA_TOKEN",
+        hide_env_values = true,
+        value_name = "token"
+    )]
+    pub jira_token: Option<String>,
+
+    /// Verify the tickets of this Jira project key, like `JIRA`. Required with the Jira URL. Can be
+    /// specified multiple times.
+    #[clap(long = "jira-project", value_name = "key", multiple_occurrences = true)]
+    pub jira_projects: Vec<String>,
+
+    /// Verify the GitHub issues and pull requests referenced in commits exist.
+    #[clap(long)]
+    pub verify_github_issues: bool,
+
+    /// The GitHub repository to verify issue references like `#123` with, like
+    /// `tombruijn/lintje`. Defaults to the repository of the `origin` remote.
+    #[clap(long, env = "GITHUB_REPOSITORY", value_name = "owner/repo")]
+    pub github_repository: Option<String>,
+
+    /// The GitHub API token to authenticate with. Required for private repositories.
+    #[clap(
+        long,
+        env = "LINTJE_GITHUB_TOKEN",
+        hide_env_values = true,
+        value_name = "token"
+    )]
+    pub github_token: Option<String>,
+
+    /// The GitHub API URL, for GitHub Enterprise Server.
+    #[clap(
+        long,
+        env = "GITHUB_API_URL",
+        default_value = github::DEFAULT_API_URL,
+        value_name = "url"
+    )]
+    pub github_api_url: String,
+
+    /// The GitLab API token to authenticate with. Defaults to the `CI_JOB_TOKEN` of the job in
+    /// GitLab CI.
+    #[clap(
+        long,
+        env = "LINTJE_GITLAB_TOKEN",
+        hide_env_values = true,
+        value_name = "token"
+    )]
+    pub gitlab_token: Option<String>,
+
+    /// The GitLab API URL, for self-managed GitLab instances. Defaults to the API URL of the
+    /// instance in GitLab CI.
+    #[clap(
+        long,
+        env = "CI_API_V4_URL",
+        default_value = gitlab::DEFAULT_API_URL,
+        value_name = "url"
+    )]
+    pub gitlab_api_url: String,
+
+    /// The Bitbucket user to authenticate with. With a user, the Bitbucket token is used as an app
+    /// password.
+    #[clap(long, env = "LINTJE_BITBUCKET_USER", value_name = "user")]
+    pub bitbucket_user: Option<String>,
+
+    /// The Bitbucket access token or app password to authenticate with. Required for private
+    /// repositories.
+    #[clap(
+        long,
+        env = "LINTJE_BITBUCKET_TOKEN",
+        hide_env_values = true,
+        value_name = "token"
+    )]
+    pub bitbucket_token: Option<String>,
+
+    /// The Bitbucket API URL.
+    #[clap(
+        long,
+        default_value = bitbucket::DEFAULT_API_URL,
+        value_name = "url"
+    )]
+    pub bitbucket_api_url: String,
+
+    /// Report verified tickets that are closed, on top of tickets that do not exist.
+    #[clap(long)]
+    pub require_open_tickets: bool,
+
     /// Lint the contents the Git hook commit-msg commit message file.
     #[clap(long, parse(from_os_str))]
     pub hook_message_file: Option<PathBuf>,
 
+    /// Lint the staged changes from the given Git hook, before the commit message is written.
+    #[clap(long, arg_enum, conflicts_with = "hook-message-file")]
+    pub hook: Option<Hook>,
+
+    /// The commit source of the commit message file, as given to the prepare-commit-msg hook.
+    /// Merge and squash commits are detected automatically.
+    #[clap(long, arg_enum, requires = "hook-message-file")]
+    pub hook_commit_source: Option<CommitSource>,
+
+    /// Explain what the rule checks and why, with examples.
+    #[clap(long, value_name = "rule")]
+    pub explain: Option<String>,
+
+    /// Show the documentation of all rules, or of the given rule, without an internet connection.
+    #[clap(long, value_name = "rule")]
+    pub docs: Option<Option<String>>,
+
     /// Prints debug information
     #[clap(long)]
     pub debug: bool,
 
+    /// Start from the settings of a built-in preset. The config files and command line options
+    /// override the settings of the preset.
+    #[clap(long, arg_enum, value_name = "preset")]
+    pub preset: Option<Preset>,
+
+    /// Maximum width of the subject in characters. Defaults to 50.
+    #[clap(long, value_name = "characters")]
+    pub max_subject_length: Option<usize>,
+
+    /// Consider the subject phrase a cliché. Can be specified multiple times.
+    #[clap(
+        long = "cliche-phrase",
+        value_name = "phrase",
+        multiple_occurrences = true
+    )]
+    pub cliche_phrases: Vec<String>,
+
+    /// Consider subjects of the word, optionally followed by one other word, a cliché, like
+    /// "Bump version". Can be specified multiple times.
+    #[clap(long = "cliche-word", value_name = "word", multiple_occurrences = true)]
+    pub cliche_words: Vec<String>,
+
+    /// Only consider the words of the `--cliche-word` option cliché words, not the built-in words
+    /// like "fix", "add" and "update".
+    #[clap(long)]
+    pub replace_cliche_words: bool,
+
+    /// Consider subjects starting with the word not in the imperative mood, like "Behoben" for
+    /// commits in German. Can be specified multiple times.
+    #[clap(long = "mood-word", value_name = "word", multiple_occurrences = true)]
+    pub mood_words: Vec<String>,
+
+    /// Only use the words of the `--mood-word` option for the `SubjectMood` rule, not the
+    /// built-in English words like "fixed" and "adding".
+    #[clap(long)]
+    pub replace_mood_words: bool,
+
+    /// Allow subjects to start with an emoji or emoji code, like "✨ Add feature" or ":sparkles:
+    /// Add feature", for projects that use gitmoji.
+    #[clap(long)]
+    pub allow_gitmoji: bool,
+
+    /// Do not allow the word in branch names. Can be specified multiple times.
+    #[clap(
+        long = "forbidden-branch-word",
+        value_name = "word",
+        multiple_occurrences = true
+    )]
+    pub forbidden_branch_words: Vec<String>,
+
+    /// Minimum number of `/` separated segments in the branch name.
+    #[clap(long, value_name = "segments")]
+    pub branch_min_depth: Option<usize>,
+
+    /// Maximum number of `/` separated segments in the branch name.
+    #[clap(long, value_name = "segments")]
+    pub branch_max_depth: Option<usize>,
+
+    /// Allow the personal prefix in branch names, like `tom/`. Only the part of the branch name
+    /// after the prefix is checked by the `BranchNameTicketNumber` and `BranchNameCliche` rules.
+    /// Can be specified multiple times.
+    #[clap(
+        long = "branch-prefix",
+        value_name = "prefix",
+        multiple_occurrences = true
+    )]
+    pub branch_prefixes: Vec<String>,
+
+    /// Require a ticket number in the branch name that matches the branch ticket patterns, with
+    /// the `BranchNameTicketPresence` rule. Disables the `BranchNameTicketNumber` rule.
+    #[clap(long)]
+    pub require_branch_ticket: bool,
+
+    /// Consider text in the branch name matching the pattern a ticket number for the
+    /// `BranchNameTicketPresence` rule. Replaces the built-in Jira ticket pattern, like
+    /// `JIRA-123`. Can be specified multiple times.
+    #[clap(
+        long = "branch-ticket-pattern",
+        value_name = "pattern",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_regex)
+    )]
+    pub branch_ticket_patterns: Vec<Regex>,
+
+    /// Do not allow uppercase characters in the branch name, for teams on case-insensitive file
+    /// systems.
+    #[clap(long)]
+    pub check_branch_case: bool,
+
+    /// Only allow ASCII letters, numbers, `-`, `/` and `_` in the branch name, and the
+    /// characters configured with `--branch-characters`.
+    #[clap(long)]
+    pub check_branch_characters: bool,
+
+    /// Allow the characters in the branch name when checking the branch name characters, like
+    /// `.`. Can be specified multiple times.
+    #[clap(
+        long = "branch-characters",
+        value_name = "characters",
+        multiple_occurrences = true
+    )]
+    pub branch_characters: Vec<String>,
+
+    /// Allow branch names generated by GitHub, like `patch-1`.
+    #[clap(long)]
+    pub allow_generated_branch_names: bool,
+
+    /// Report fixup, squash and work in progress commits as hints rather than errors, for draft
+    /// branches that are squashed before they are merged.
+    #[clap(long)]
+    pub allow_fixup: bool,
+
+    /// Do not allow commits on protected branches: `main`, `master` and `develop`, or the branches
+    /// configured with `--protected-branch`.
+    #[clap(long)]
+    pub check_protected_branch: bool,
+
+    /// Do not allow commits on the branch. Replaces the default protected branches. Can be
+    /// specified multiple times.
+    #[clap(
+        long = "protected-branch",
+        value_name = "branch",
+        multiple_occurrences = true
+    )]
+    pub protected_branches: Vec<String>,
+
+    /// Do not allow the word or phrase in the subject and message body. Add advice about what to
+    /// use instead after a `=`, like `Project Falcon=Use the product name`. Can be specified
+    /// multiple times.
+    #[clap(
+        long = "forbidden-phrase",
+        value_name = "phrase",
+        multiple_occurrences = true
+    )]
+    pub forbidden_phrases: Vec<String>,
+
+    /// Require the trailer, like `Signed-off-by`, at the end of the message body. Can be specified
+    /// multiple times.
+    #[clap(
+        long = "require-trailer",
+        value_name = "trailer",
+        multiple_occurrences = true
+    )]
+    pub required_trailers: Vec<String>,
+
+    /// Require a `Signed-off-by` trailer with the name and email address of the commit author,
+    /// for projects using the Developer Certificate of Origin.
+    #[clap(long)]
+    pub require_dco: bool,
+
+    /// Require commits to be signed with a valid GPG or SSH signature. Signatures are checked
+    /// with `git log`, so the keys must be known to Git to be verified.
+    #[clap(long)]
+    pub require_signature: bool,
+
+    /// Check the subject for characters that are not ASCII, for tools that do not handle other
+    /// characters well.
+    #[clap(long)]
+    pub check_subject_ascii: bool,
+
+    /// Allow the characters in the subject when checking for non-ASCII characters, like `éü`.
+    /// Can be specified multiple times.
+    #[clap(
+        long = "subject-characters",
+        value_name = "characters",
+        multiple_occurrences = true
+    )]
+    pub subject_characters: Vec<String>,
+
+    /// Minimum number of words in the subject, for subjects like "Refactor" that do not describe
+    /// the change. Without a value the minimum is 2 words.
+    #[clap(
+        long,
+        value_name = "words",
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = "2"
+    )]
+    pub subject_min_words: Option<usize>,
+
+    /// Minimum number of paragraphs in the message body, not counting the trailers, to explain
+    /// what changed and why. Without a value the minimum is 2 paragraphs.
+    #[clap(
+        long,
+        value_name = "paragraphs",
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = "2"
+    )]
+    pub message_min_paragraphs: Option<usize>,
+
+    /// Check the subject for words from the list of common misspellings.
+    #[clap(long)]
+    pub check_spelling: bool,
+
+    /// Do not report the word as misspelled. Add a misspelling with its correction after a `=`,
+    /// like `wrok=work`. Can be specified multiple times.
+    #[clap(
+        long = "spelling-word",
+        value_name = "word",
+        multiple_occurrences = true
+    )]
+    pub spelling_words: Vec<String>,
+
+    /// Allow lines in the message body matching the regular expression to be longer than 72
+    /// characters, like `^\[\d+\]: ` for Markdown reference links. Lines with URLs are always
+    /// allowed. Can be specified multiple times.
+    #[clap(
+        long = "line-length-exemption",
+        value_name = "pattern",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_regex)
+    )]
+    pub line_length_exemptions: Vec<Regex>,
+
+    /// Consider text in the subject matching the pattern a ticket number for the
+    /// `SubjectTicketNumber` rule. Replaces the built-in Jira ticket pattern, like `JIRA-123`.
+    /// Can be specified multiple times.
+    #[clap(
+        long = "ticket-pattern",
+        value_name = "pattern",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_regex)
+    )]
+    pub ticket_patterns: Vec<Regex>,
+
+    /// Never consider the text a ticket number for the `SubjectTicketNumber` and
+    /// `TicketReference` rules, like `UTF-8` or `ISO-8601`. Can be specified multiple times.
+    #[clap(
+        long = "ticket-exemption",
+        value_name = "text",
+        multiple_occurrences = true
+    )]
+    pub ticket_exemptions: Vec<String>,
+
+    /// Require a ticket number in the subject that matches the ticket patterns, with the
+    /// `SubjectTicketPresence` rule. Disables the `SubjectTicketNumber` rule.
+    #[clap(long)]
+    pub require_subject_ticket: bool,
+
+    /// Accept text in the message body matching the pattern as a ticket reference for the
+    /// `MessageTicketNumber` rule, like Jira ticket numbers or issue URLs. The `Fixes #123` and
+    /// `Part of #123` references are always accepted. Can be specified multiple times.
+    #[clap(
+        long = "ticket-reference-pattern",
+        value_name = "pattern",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_regex)
+    )]
+    pub ticket_reference_patterns: Vec<Regex>,
+
+    /// Lint the squash merge commits made by GitHub, like `Fix bug (#123)`, instead of ignoring
+    /// them. The pull request reference is not counted towards the subject length, and not
+    /// reported as a ticket number.
+    #[clap(long)]
+    pub lint_squash_merges: bool,
+
+    /// Maximum size in bytes of files added in commits. Defaults to 1048576 (1 MB).
+    #[clap(long, value_name = "bytes")]
+    pub max_file_size: Option<usize>,
+
+    /// Maximum number of files changed in a commit. Defaults to 50.
+    #[clap(long, value_name = "count")]
+    pub max_files_changed: Option<usize>,
+
+    /// Maximum number of top-level directories changed in a commit. Defaults to 5.
+    #[clap(long, value_name = "count")]
+    pub max_directories: Option<usize>,
+
+    /// Check if the lockfile only changes together with its manifest, in the
+    /// `lockfile=manifest` format, like `Cargo.lock=Cargo.toml`. Replaces the built-in lockfiles.
+    /// Can be specified multiple times.
+    #[clap(
+        long = "lockfile",
+        value_name = "lockfile=manifest",
+        multiple_occurrences = true,
+        parse(try_from_str = Lockfile::parse)
+    )]
+    pub lockfiles: Vec<Lockfile>,
+
+    /// Report commits that change files matching the pattern, in the `.gitignore` format, like
+    /// `.env`, `*.pem` or `secrets/`. Can be specified multiple times.
+    #[clap(
+        long = "forbidden-path",
+        value_name = "pattern",
+        multiple_occurrences = true,
+        parse(try_from_str = PathPattern::parse)
+    )]
+    pub forbidden_paths: Vec<PathPattern>,
+
+    /// Do not validate the rule, like `SubjectTicketNumber`, for all commits or the branch. Can be
+    /// specified multiple times.
+    #[clap(
+        long = "disable-rule",
+        value_name = "rule",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_rule)
+    )]
+    pub disabled_rules: Vec<Rule>,
+
+    /// Do not report the hints of the rule, like `MessageTicketNumber`, while still reporting its
+    /// errors and the hints of other rules. Can be specified multiple times.
+    #[clap(
+        long = "hide-hint",
+        value_name = "rule",
+        multiple_occurrences = true,
+        parse(try_from_str = parse_rule)
+    )]
+    pub hidden_hint_rules: Vec<Rule>,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
     /// Lint commits by Git commit SHA or by a range of commits. When no <commit> is specified, it
-    /// defaults to linting the latest commit.
+    /// defaults to linting the latest commit. Use `-` to lint the commit message read from STDIN.
     #[clap(name = "commit (range)")]
     pub selection: Option<String>,
+
+    /// The warnings of loading the config files, logged once the logger is initialized.
+    #[clap(skip)]
+    pub config_warnings: Vec<String>,
+
+    /// The directory Lintje was run in, which the hook message file path is relative to.
+    #[clap(skip)]
+    pub hook_directory: PathBuf,
+
+    #[clap(skip)]
+    matches: ArgMatches,
 }
 
 impl Lint {
@@ -73,37 +876,2815 @@ impl Lint {
         if self.no_color {
             return false;
         }
-        if self.color {
-            return true;
+        match self.color {
+            Some(ColorWhen::Always) => true,
+            Some(ColorWhen::Never) => false,
+            Some(ColorWhen::Auto) | None => detect_color(
+                atty::is(atty::Stream::Stdout),
+                std::env::var_os("NO_COLOR"),
+                std::env::var_os("CLICOLOR_FORCE"),
+            ),
+        }
+    }
+
+    /// Parse the command line arguments. Exits with the usage error if they're invalid. The
+    /// settings are applied with `load_settings`.
+    pub fn parse_args() -> Self {
+        let matches = Lint::command().get_matches();
+        let mut lint = match Lint::from_arg_matches(&matches) {
+            Ok(lint) => lint,
+            Err(e) => e.exit(),
+        };
+        lint.matches = matches;
+        lint
+    }
+
+    /// Apply the settings from the Git config and config files on top of the command line
+    /// arguments. Command line arguments and environment variables take precedence over the
+    /// settings.
+    ///
+    /// The settings are loaded after the `-C` and `--git-dir` options are applied, so the Git
+    /// config of that repository is used. Hooks can be run from another directory than the
+    /// repository, so for hooks the settings are loaded from the repository root.
+    pub fn load_settings(&mut self) -> Result<(), String> {
+        if let Some(directory) = &self.directory {
+            std::env::set_current_dir(directory).map_err(|e| {
+                format!(
+                    "Unable to change the working directory to {}: {}",
+                    directory.to_string_lossy(),
+                    e
+                )
+            })?;
+        }
+        if let Some(git_dir) = &self.git_dir {
+            git::use_git_dir(git_dir)?;
+        }
+        self.hook_directory = std::env::current_dir().unwrap_or_default();
+        if self.hook.is_some() || self.hook_message_file.is_some() {
+            git::enter_repository_root()?;
+        }
+        self.apply_config_files()
+    }
+
+    /// Parse the command line options again, with the settings of the repository in the current
+    /// working directory. Used to lint every repository of a batch with its own settings.
+    pub fn parse_with_repository_settings() -> Result<Self, String> {
+        let mut lint = Self::parse_args();
+        lint.apply_config_files()?;
+        Ok(lint)
+    }
+
+    fn apply_config_files(&mut self) -> Result<(), String> {
+        let settings = load_settings()?;
+        let matches = std::mem::take(&mut self.matches);
+        let result = self.apply_settings(&matches, &settings);
+        self.matches = matches;
+        result?;
+        self.config_warnings = settings.warnings;
+        Ok(())
+    }
+
+    /// Apply the settings for all options that were not set on the command line or with an
+    /// environment variable. The settings override the settings of the selected preset.
+    fn apply_settings(&mut self, matches: &ArgMatches, config: &Settings) -> Result<(), String> {
+        if matches.value_source("preset") != Some(ValueSource::CommandLine) {
+            if let Some(value) = config.get("preset") {
+                self.preset = Some(Preset::from_str(value, true).map_err(|_| {
+                    format!("Invalid value for {}: {}", config.label("preset"), value)
+                })?);
+            }
+        }
+        match self.preset {
+            Some(preset) => {
+                let mut settings = preset.settings();
+                settings.merge(config.clone());
+                self.apply_config_settings(matches, &settings)?;
+            }
+            None => self.apply_config_settings(matches, config)?,
+        }
+        // Without project keys, text like `UTF-8` and `SHA-256` would be looked up as tickets
+        if self.jira_url.is_some() && self.jira_projects.is_empty() {
+            return Err(
+                "The Jira project keys are required to verify Jira tickets. \
+                Configure them with the `--jira-project` option."
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn apply_config_settings(
+        &mut self,
+        matches: &ArgMatches,
+        config: &Settings,
+    ) -> Result<(), String> {
+        let unset = |id: &str| {
+            !matches!(
+                matches.value_source(id),
+                Some(ValueSource::CommandLine) | Some(ValueSource::EnvVariable)
+            )
+        };
+        if unset("branch-validation") {
+            if let Some(value) = config.get_bool("branch")? {
+                self.branch_validation = value;
+            }
+        }
+        if unset("hints") {
+            if let Some(value) = config.get_bool("hints")? {
+                self.hints = value;
+            }
+        }
+        if unset("color") && unset("no-color") {
+            if config.get("color") == Some("auto") {
+                self.color = Some(ColorWhen::Auto);
+            } else if let Some(value) = config.get_bool("color")? {
+                self.color = Some(if value {
+                    ColorWhen::Always
+                } else {
+                    ColorWhen::Never
+                });
+            }
+        }
+        if unset("quiet") {
+            if let Some(value) = config.get_bool("quiet")? {
+                self.quiet = value;
+            }
+        }
+        if unset("rule-stats") {
+            if let Some(value) = config.get_bool("ruleStats")? {
+                self.rule_stats = value;
+            }
+        }
+        if unset("format") {
+            if let Some(value) = config.get("format") {
+                self.format = OutputFormat::from_str(value, true).map_err(|_| {
+                    format!("Invalid value for {}: {}", config.label("format"), value)
+                })?;
+            }
+        }
+        if unset("lang") {
+            if let Some(value) = config.get("lang") {
+                self.lang = Language::from_str(value, true).map_err(|_| {
+                    format!("Invalid value for {}: {}", config.label("lang"), value)
+                })?;
+            }
+        }
+        if unset("group-by") {
+            if let Some(value) = config.get("groupBy") {
+                self.group_by = Some(GroupBy::from_str(value, true).map_err(|_| {
+                    format!("Invalid value for {}: {}", config.label("groupBy"), value)
+                })?);
+            }
+        }
+        if unset("max-subject-length") {
+            self.max_subject_length = config.get_usize("maxSubjectLength")?;
+        }
+        if unset("cliche-phrases") {
+            self.cliche_phrases = config.get_all("clichePhrase");
+        }
+        if unset("cliche-words") {
+            self.cliche_words = config.get_all("clicheWord");
+        }
+        if unset("replace-cliche-words") {
+            if let Some(value) = config.get_bool("replaceClicheWords")? {
+                self.replace_cliche_words = value;
+            }
+        }
+        if unset("mood-words") {
+            self.mood_words = config.get_all("moodWord");
+        }
+        if unset("replace-mood-words") {
+            if let Some(value) = config.get_bool("replaceMoodWords")? {
+                self.replace_mood_words = value;
+            }
+        }
+        if unset("allow-gitmoji") {
+            if let Some(value) = config.get_bool("allowGitmoji")? {
+                self.allow_gitmoji = value;
+            }
+        }
+        if unset("forbidden-branch-words") {
+            self.forbidden_branch_words = config.get_all("forbiddenBranchWord");
+        }
+        if unset("branch-min-depth") {
+            self.branch_min_depth = config.get_usize("branchMinDepth")?;
+        }
+        if unset("branch-max-depth") {
+            self.branch_max_depth = config.get_usize("branchMaxDepth")?;
+        }
+        if unset("branch-prefixes") {
+            self.branch_prefixes = config.get_all("branchPrefix");
+        }
+        if unset("require-branch-ticket") {
+            if let Some(value) = config.get_bool("requireBranchTicket")? {
+                self.require_branch_ticket = value;
+            }
+        }
+        if unset("branch-ticket-patterns") {
+            self.branch_ticket_patterns =
+                config.get_all_parsed("branchTicketPattern", parse_regex)?;
+        }
+        if unset("check-branch-case") {
+            if let Some(value) = config.get_bool("checkBranchCase")? {
+                self.check_branch_case = value;
+            }
+        }
+        if unset("check-branch-characters") {
+            if let Some(value) = config.get_bool("checkBranchCharacters")? {
+                self.check_branch_characters = value;
+            }
+        }
+        if unset("branch-characters") {
+            self.branch_characters = config.get_all("branchCharacters");
+        }
+        if unset("allow-generated-branch-names") {
+            if let Some(value) = config.get_bool("allowGeneratedBranchNames")? {
+                self.allow_generated_branch_names = value;
+            }
+        }
+        if unset("allow-fixup") {
+            if let Some(value) = config.get_bool("allowFixup")? {
+                self.allow_fixup = value;
+            }
+        }
+        if unset("check-protected-branch") {
+            if let Some(value) = config.get_bool("checkProtectedBranch")? {
+                self.check_protected_branch = value;
+            }
+        }
+        if unset("protected-branches") {
+            self.protected_branches = config.get_all("protectedBranch");
+        }
+        if unset("forbidden-phrases") {
+            self.forbidden_phrases = config.get_all("forbiddenPhrase");
+        }
+        if unset("required-trailers") {
+            self.required_trailers = config.get_all("requireTrailer");
+        }
+        if unset("require-dco") {
+            if let Some(value) = config.get_bool("requireDco")? {
+                self.require_dco = value;
+            }
+        }
+        if unset("require-signature") {
+            if let Some(value) = config.get_bool("requireSignature")? {
+                self.require_signature = value;
+            }
+        }
+        if unset("check-subject-ascii") {
+            if let Some(value) = config.get_bool("checkSubjectAscii")? {
+                self.check_subject_ascii = value;
+            }
+        }
+        if unset("subject-characters") {
+            self.subject_characters = config.get_all("subjectCharacters");
+        }
+        if unset("subject-min-words") {
+            self.subject_min_words = config.get_usize("subjectMinWords")?;
+        }
+        if unset("message-min-paragraphs") {
+            self.message_min_paragraphs = config.get_usize("messageMinParagraphs")?;
+        }
+        if unset("check-spelling") {
+            if let Some(value) = config.get_bool("checkSpelling")? {
+                self.check_spelling = value;
+            }
+        }
+        if unset("spelling-words") {
+            self.spelling_words = config.get_all("spellingWord");
+        }
+        if unset("line-length-exemptions") {
+            self.line_length_exemptions =
+                config.get_all_parsed("lineLengthExemption", parse_regex)?;
+        }
+        if unset("ticket-patterns") {
+            self.ticket_patterns = config.get_all_parsed("ticketPattern", parse_regex)?;
+        }
+        if unset("ticket-exemptions") {
+            self.ticket_exemptions = config.get_all("ticketExemption");
+        }
+        if unset("ticket-reference-patterns") {
+            self.ticket_reference_patterns =
+                config.get_all_parsed("ticketReferencePattern", parse_regex)?;
+        }
+        if unset("lint-squash-merges") {
+            if let Some(value) = config.get_bool("lintSquashMerges")? {
+                self.lint_squash_merges = value;
+            }
+        }
+        if unset("require-subject-ticket") {
+            if let Some(value) = config.get_bool("requireSubjectTicket")? {
+                self.require_subject_ticket = value;
+            }
+        }
+        if unset("max-file-size") {
+            self.max_file_size = config.get_usize("maxFileSize")?;
+        }
+        if unset("max-files-changed") {
+            self.max_files_changed = config.get_usize("maxFilesChanged")?;
+        }
+        if unset("max-directories") {
+            self.max_directories = config.get_usize("maxDirectories")?;
+        }
+        if unset("lockfiles") {
+            self.lockfiles = config.get_all_parsed("lockfile", Lockfile::parse)?;
+        }
+        if unset("forbidden-paths") {
+            self.forbidden_paths = config.get_all_parsed("forbiddenPath", PathPattern::parse)?;
+        }
+        if unset("disabled-rules") {
+            self.disabled_rules = config.get_all_parsed("disableRule", parse_rule)?;
+        }
+        if unset("hidden-hint-rules") {
+            self.hidden_hint_rules = config.get_all_parsed("hideHint", parse_rule)?;
+        }
+        if unset("jira-url") {
+            self.jira_url = config.get("jiraUrl").map(String::from);
+        }
+        if unset("jira-user") {
+            self.jira_user = config.get("jiraUser").map(String::from);
+        }
+        if unset("jira-projects") {
+            self.jira_projects = config.get_all("jiraProject");
+        }
+        if unset("verify-github-issues") {
+            if let Some(value) = config.get_bool("verifyGithubIssues")? {
+                self.verify_github_issues = value;
+            }
+        }
+        if unset("github-repository") {
+            self.github_repository = config.get("githubRepository").map(String::from);
+        }
+        if unset("github-api-url") {
+            if let Some(value) = config.get("githubApiUrl") {
+                self.github_api_url = value.to_string();
+            }
+        }
+        if unset("gitlab-api-url") {
+            if let Some(value) = config.get("gitlabApiUrl") {
+                self.gitlab_api_url = value.to_string();
+            }
+        }
+        if unset("require-open-tickets") {
+            if let Some(value) = config.get_bool("requireOpenTickets")? {
+                self.require_open_tickets = value;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true when the commit message is read from STDIN.
+    pub fn stdin(&self) -> bool {
+        self.stdin || self.selection.as_deref() == Some("-")
+    }
+
+    /// Returns true when multiple repositories are linted in one run.
+    pub fn is_batch(&self) -> bool {
+        !self.repositories.is_empty() || self.repos_file.is_some()
+    }
+
+    /// Returns the absolute paths of the repositories to lint, from the `--repo` options and
+    /// the `--repos-file` file. Relative paths in the file are relative to the file's directory.
+    pub fn repositories(&self) -> Result<Vec<PathBuf>, String> {
+        let current_dir = std::env::current_dir()
+            .map_err(|e| format!("Unable to determine current directory: {}", e))?;
+        let mut repositories = self
+            .repositories
+            .iter()
+            .map(|path| current_dir.join(path))
+            .collect::<Vec<PathBuf>>();
+        if let Some(file) = &self.repos_file {
+            let contents = fs::read_to_string(file).map_err(|e| {
+                format!(
+                    "Unable to read repositories file: {}\n{}",
+                    file.to_string_lossy(),
+                    e
+                )
+            })?;
+            let base_dir = current_dir.join(file.parent().unwrap_or_else(|| Path::new("")));
+            repositories.extend(parse_repositories_file(&contents, &base_dir));
+        }
+        Ok(repositories
+            .iter()
+            .flat_map(|path| expand_repository_path(path))
+            .collect())
+    }
+
+    /// Returns the GitLab merge request to lint, if the `--gitlab-mr` option is given. Without a
+    /// value, the merge request of the GitLab CI merge request pipeline is used.
+    pub fn gitlab_merge_request(&self) -> Option<Result<MergeRequestReference, String>> {
+        let reference = self.gitlab_mr.as_ref()?;
+        Some(match reference {
+            Some(reference) => MergeRequestReference::parse(reference),
+            None => match (
+                std::env::var("CI_MERGE_REQUEST_PROJECT_ID"),
+                std::env::var("CI_MERGE_REQUEST_IID"),
+            ) {
+                (Ok(project), Ok(iid)) => {
+                    MergeRequestReference::parse(&format!("{}!{}", project, iid))
+                }
+                _ => Err(
+                    "Unable to determine the GitLab merge request. Specify it like \
+                    `group/project!123`, or run Lintje in a GitLab CI merge request pipeline."
+                        .to_string(),
+                ),
+            },
+        })
+    }
+
+    /// Returns the token to authenticate with the GitLab API. The `CI_JOB_TOKEN` of the GitLab CI
+    /// job is used if no token is configured.
+    pub fn gitlab_token(&self) -> Option<GitLabToken> {
+        match &self.gitlab_token {
+            Some(token) => Some(GitLabToken::Private(token.clone())),
+            None => std::env::var("CI_JOB_TOKEN").ok().map(GitLabToken::Job),
+        }
+    }
+
+    /// Returns the Bitbucket pull request to lint, if the `--bitbucket-pr` option is given.
+    /// Without a value, the pull request of the Bitbucket Pipelines build is used.
+    pub fn bitbucket_pull_request(
+        &self,
+    ) -> Option<Result<bitbucket::PullRequestReference, String>> {
+        let reference = self.bitbucket_pr.as_ref()?;
+        Some(match reference {
+            Some(reference) => bitbucket::PullRequestReference::parse(reference),
+            None => match (
+                std::env::var("BITBUCKET_REPO_FULL_NAME"),
+                std::env::var("BITBUCKET_PR_ID"),
+            ) {
+                (Ok(repository), Ok(id)) => {
+                    bitbucket::PullRequestReference::parse(&format!("{}#{}", repository, id))
+                }
+                _ => Err(
+                    "Unable to determine the Bitbucket pull request. Specify it like \
+                    `workspace/repo#123`, or run Lintje in a Bitbucket Pipelines pull request \
+                    build."
+                        .to_string(),
+                ),
+            },
+        })
+    }
+
+    pub fn fetch_options(&self) -> FetchOptions {
+        FetchOptions {
+            author: self.author.clone(),
+            since: self.since.clone(),
+        }
+    }
+
+    pub fn rule_options(&self) -> RuleOptions {
+        let mut ticket_trackers: Vec<Box<dyn TicketTracker>> = vec![];
+        if let Some(url) = &self.jira_url {
+            ticket_trackers.push(Box::new(Jira::new(
+                url,
+                self.jira_user.clone(),
+                self.jira_token.clone(),
+                self.jira_projects.clone(),
+            )));
+        }
+        if self.verify_github_issues {
+            ticket_trackers.push(Box::new(GitHub::new(
+                &self.github_api_url,
+                self.github_repository
+                    .clone()
+                    .or_else(git::github_repository),
+                self.github_token.clone(),
+            )));
+        }
+        RuleOptions {
+            max_subject_length: self.max_subject_length,
+            cliche_phrases: self.cliche_phrases.clone(),
+            cliche_words: self.cliche_words.clone(),
+            replace_cliche_words: self.replace_cliche_words,
+            mood_words: self.mood_words.clone(),
+            replace_mood_words: self.replace_mood_words,
+            allow_gitmoji: self.allow_gitmoji,
+            forbidden_branch_words: self.forbidden_branch_words.clone(),
+            branch_min_depth: self.branch_min_depth,
+            branch_max_depth: self.branch_max_depth,
+            branch_prefixes: self.branch_prefixes.clone(),
+            require_branch_ticket: self.require_branch_ticket,
+            branch_ticket_patterns: self.branch_ticket_patterns.clone(),
+            check_branch_case: self.check_branch_case,
+            check_branch_characters: self.check_branch_characters,
+            branch_characters: self.branch_characters.concat(),
+            allow_generated_branch_names: self.allow_generated_branch_names,
+            allow_fixup: self.allow_fixup,
+            protected_branches: self.protected_branches(),
+            ticket_trackers,
+            require_open_tickets: self.require_open_tickets,
+            forbidden_phrases: self
+                .forbidden_phrases
+                .iter()
+                .map(|phrase| ForbiddenPhrase::parse(phrase))
+                .collect(),
+            required_trailers: self.required_trailers.clone(),
+            require_dco: self.require_dco,
+            require_signature: self.require_signature,
+            check_subject_ascii: self.check_subject_ascii,
+            subject_characters: self.subject_characters.concat(),
+            subject_min_words: self.subject_min_words,
+            message_min_paragraphs: self.message_min_paragraphs,
+            check_spelling: self.check_spelling,
+            spelling_words: self
+                .spelling_words
+                .iter()
+                .map(|word| SpellingWord::parse(word))
+                .collect(),
+            line_length_exemptions: self.line_length_exemptions.clone(),
+            ticket_patterns: self.ticket_patterns.clone(),
+            ticket_exemptions: self.ticket_exemptions.clone(),
+            require_subject_ticket: self.require_subject_ticket,
+            ticket_reference_patterns: self.ticket_reference_patterns.clone(),
+            lint_squash_merges: self.lint_squash_merges,
+            max_file_size: self.max_file_size,
+            max_files_changed: self.max_files_changed,
+            max_directories: self.max_directories,
+            lockfiles: self.lockfiles.clone(),
+            forbidden_paths: self.forbidden_paths.clone(),
+            disabled_rules: self.disabled_rules.clone(),
+        }
+    }
+
+    fn protected_branches(&self) -> Vec<String> {
+        if !self.protected_branches.is_empty() {
+            self.protected_branches.clone()
+        } else if self.check_protected_branch {
+            DEFAULT_PROTECTED_BRANCHES
+                .iter()
+                .map(|name| name.to_string())
+                .collect()
+        } else {
+            vec![]
         }
-        false // By default color is turned off
     }
 }
 
-#[derive(Debug)]
-pub struct Options {
-    pub debug: bool,
-    pub color: bool,
-    pub hints: bool,
+fn parse_rule(name: &str) -> Result<Rule, String> {
+    rule_by_any_name(name).ok_or_else(|| format!("Unknown rule: {}", name))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Lint;
-    use clap::Parser;
+// Color is enabled for terminals, unless the `NO_COLOR` environment variable is set to a non-empty
+// value, as described on https://no-color.org. The `CLICOLOR_FORCE` environment variable, set to
+// anything but `0`, enables color for all output, like piped output.
+fn detect_color(
+    is_terminal: bool,
+    no_color: Option<OsString>,
+    clicolor_force: Option<OsString>,
+) -> bool {
+    if matches!(no_color, Some(value) if !value.is_empty()) {
+        return false;
+    }
+    match clicolor_force {
+        Some(value) if !value.is_empty() && value != "0" => true,
+        _ => is_terminal,
+    }
+}
 
-    #[test]
-    fn test_color_flags() {
-        // Both color flags set, but --no-color is leading
-        assert!(!Lint::parse_from(["lintje", "--color", "--no-color"]).color());
+fn parse_regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("Invalid regular expression: {}", e))
+}
 
-        // Only --color is set
-        assert!(Lint::parse_from(["lintje", "--color"]).color());
+fn parse_repositories_file(contents: &str, base_dir: &Path) -> Vec<PathBuf> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base_dir.join(line))
+        .collect()
+}
 
-        // Only --no-color is set
-        assert!(!Lint::parse_from(["lintje", "--no-color"]).color());
+// Expand the `*` and `?` wildcards in the repository path to the Git repositories that match.
+// Paths without wildcards are returned as is, so an error is reported if the repository does not
+// exist.
+fn expand_repository_path(path: &Path) -> Vec<PathBuf> {
+    let is_wildcard = |name: &str| name.contains(['*', '?']);
+    if !is_wildcard(&path.to_string_lossy()) {
+        return vec![path.to_path_buf()];
+    }
+    let mut paths = vec![PathBuf::new()];
+    for component in path.components() {
+        let name = component.as_os_str().to_string_lossy();
+        if !is_wildcard(&name) {
+            for path in &mut paths {
+                path.push(component);
+            }
+            continue;
+        }
+        let pattern = match PathPattern::parse(&name) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                warn!("{}", e);
+                return vec![];
+            }
+        };
+        paths = paths
+            .iter()
+            .flat_map(|directory| matching_directories(directory, &pattern))
+            .collect();
+    }
+    paths.retain(|path| is_repository(path));
+    if paths.is_empty() {
+        warn!("No repositories found matching: {}", path.to_string_lossy());
+    }
+    paths
+}
 
-        // No flags are set
-        assert!(!Lint::parse_from(["lintje"]).color());
+// The directories in the directory with a name that matches the pattern, sorted by name.
+fn matching_directories(directory: &Path, pattern: &PathPattern) -> Vec<PathBuf> {
+    let mut directories = match fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter(|entry| pattern.is_match(&entry.file_name().to_string_lossy()))
+            .map(|entry| entry.path())
+            .collect::<Vec<PathBuf>>(),
+        Err(_) => vec![],
+    };
+    directories.sort();
+    directories
+}
+
+// Returns true for a Git repository with a working tree, or a bare repository.
+fn is_repository(path: &Path) -> bool {
+    path.join(".git").exists() || path.join("HEAD").is_file()
+}
+
+// The branches the BranchNameProtected rule checks with the `--check-protected-branch` option.
+const DEFAULT_PROTECTED_BRANCHES: [&str; 3] = ["main", "master", "develop"];
+
+/// The name of the config file Lintje looks for in the repository.
+pub const CONFIG_FILE_NAME: &str = ".lintje.toml";
+
+// The settings that can be set in the config files, in the Git config key format. Secrets, like
+// the Jira and GitHub tokens, can only be set with environment variables, so they're not
+// committed to the repository by accident. The URLs of the services the tokens are sent to are
+// listed in `SERVICE_URL_KEYS` instead.
+const SETTING_KEYS: [&str; 57] = [
+    "preset",
+    "branch",
+    "hints",
+    "color",
+    "quiet",
+    "ruleStats",
+    "format",
+    "groupBy",
+    "lang",
+    "maxSubjectLength",
+    "clichePhrase",
+    "clicheWord",
+    "replaceClicheWords",
+    "moodWord",
+    "replaceMoodWords",
+    "allowGitmoji",
+    "forbiddenBranchWord",
+    "branchMinDepth",
+    "branchMaxDepth",
+    "branchPrefix",
+    "requireBranchTicket",
+    "branchTicketPattern",
+    "checkBranchCase",
+    "checkBranchCharacters",
+    "branchCharacters",
+    "allowGeneratedBranchNames",
+    "allowFixup",
+    "checkProtectedBranch",
+    "protectedBranch",
+    "forbiddenPhrase",
+    "requireTrailer",
+    "requireDco",
+    "requireSignature",
+    "checkSubjectAscii",
+    "subjectMinWords",
+    "messageMinParagraphs",
+    "subjectCharacters",
+    "checkSpelling",
+    "spellingWord",
+    "lineLengthExemption",
+    "ticketPattern",
+    "ticketExemption",
+    "requireSubjectTicket",
+    "ticketReferencePattern",
+    "lintSquashMerges",
+    "maxFileSize",
+    "maxFilesChanged",
+    "maxDirectories",
+    "lockfile",
+    "forbiddenPath",
+    "disableRule",
+    "hideHint",
+    "jiraUser",
+    "jiraProject",
+    "verifyGithubIssues",
+    "githubRepository",
+    "requireOpenTickets",
+];
+
+// The URLs of the services Lintje sends API tokens to. They can't be set in config files: a
+// change to the config file in a pull request would otherwise send the token of the CI build to
+// another host. They can be set with the command line options, environment variables and the
+// Git config.
+const SERVICE_URL_KEYS: [&str; 3] = ["jiraUrl", "githubApiUrl", "gitlabApiUrl"];
+
+// How long config files fetched from a URL are cached.
+const CONFIG_CACHE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Load the settings from all sources, from least to most specific: the `lintje.*` keys in the
+/// Git config, the user config file and the config files in the home directory, the repository
+/// and the current directory.
+pub fn load_settings() -> Result<Settings, String> {
+    let mut settings = Settings::from_git_config(&git::lintje_config());
+    let current_dir = std::env::current_dir()
+        .map_err(|e| format!("Unable to determine current directory: {}", e))?;
+    let repository_root = git::repository_root().ok();
+    let home = home_dir();
+    let config_files = user_config_file()
+        .filter(|path| path.is_file())
+        .into_iter()
+        .chain(find_config_files(
+            &current_dir,
+            repository_root.as_deref(),
+            home.as_deref(),
+        ));
+    for path in config_files {
+        let location = path.to_string_lossy().to_string();
+        settings.merge(load_config_file(&location, &mut vec![])?);
+    }
+    Ok(settings)
+}
+
+/// Load the config file at the path or URL, on top of the config files it extends. The config
+/// files it extends are loaded first, so the config file itself overrides their settings.
+///
+/// The `extending` list contains the config files that extend this config file, to detect config
+/// files that extend each other.
+fn load_config_file(location: &str, extending: &mut Vec<String>) -> Result<Settings, String> {
+    // Resolve paths like `shared/../.lintje.toml`, so they match the path of the same file
+    let location = &match fs::canonicalize(location) {
+        Ok(path) if !is_url(location) => path.to_string_lossy().to_string(),
+        _ => location.to_string(),
+    };
+    if extending.iter().any(|config| config == location) {
+        return Err(format!(
+            "Config file extends itself: {}",
+            extending.join(" -> ") + " -> " + location
+        ));
+    }
+    debug!("Using config file: {}", location);
+    let contents = if is_url(location) {
+        match fetch_config_file(location, cache_dir().as_deref())? {
+            Ok(contents) => contents,
+            Err(warning) => {
+                return Ok(Settings {
+                    warnings: vec![warning],
+                    ..Settings::default()
+                })
+            }
+        }
+    } else {
+        fs::read_to_string(location)
+            .map_err(|e| format!("Unable to read config file: {}\n{}", location, e))?
+    };
+    let config = Settings::from_toml(&contents, Path::new(location))?;
+
+    let mut settings = Settings::default();
+    extending.push(location.to_string());
+    for extends in &config.extends {
+        let extended = extended_config_location(location, extends);
+        settings.merge(load_config_file(&extended, extending)?);
+    }
+    extending.pop();
+    settings.merge(config);
+    Ok(settings)
+}
+
+/// Fetch the config file at the URL. Fetched config files are cached for an hour, so they're not
+/// fetched on every run, like in the commit-msg hook. When the config file can't be fetched, the
+/// cached copy is used, even when it's older. Without a cached copy, a warning is returned
+/// instead of the contents, so commits can still be made offline.
+///
+/// Config files are only fetched over HTTPS, so they can't be changed on the way. Other URLs are
+/// an error.
+fn fetch_config_file(
+    url: &str,
+    cache_dir: Option<&Path>,
+) -> Result<Result<String, String>, String> {
+    if !url.starts_with("https://") {
+        return Err(format!(
+            "Unable to fetch config file: {}\nConfig files can only be fetched over HTTPS",
+            url
+        ));
+    }
+    let cache_file = cache_dir.map(|dir| dir.join(cache_file_name(url)));
+    if let Some(cache_file) = &cache_file {
+        let cache_age = fs::metadata(cache_file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        if matches!(cache_age, Some(age) if age < CONFIG_CACHE_DURATION) {
+            if let Ok(contents) = fs::read_to_string(cache_file) {
+                debug!("Using cached config file: {}", cache_file.to_string_lossy());
+                return Ok(Ok(contents));
+            }
+        }
+    }
+    let error = match http_get(url, &[]) {
+        Ok(response) if response.status == 200 => {
+            if let (Some(cache_dir), Some(cache_file)) = (cache_dir, &cache_file) {
+                if let Err(e) = fs::create_dir_all(cache_dir)
+                    .and_then(|_| fs::write(cache_file, &response.body))
+                {
+                    debug!("Unable to cache config file: {}", e);
+                }
+            }
+            return Ok(Ok(response.body));
+        }
+        Ok(response) => format!("HTTP status {}", response.status),
+        Err(e) => e,
+    };
+    match cache_file.and_then(|cache_file| fs::read_to_string(cache_file).ok()) {
+        Some(contents) => {
+            debug!(
+                "Unable to fetch config file, using the cached copy: {}\n{}",
+                url, error
+            );
+            Ok(Ok(contents))
+        }
+        None => Ok(Err(format!(
+            "Unable to fetch config file, skipping it: {}\n{}",
+            url, error
+        ))),
+    }
+}
+
+// The file name of the cached copy of the config file at the URL, with all characters that can't
+// be used in file names replaced.
+fn cache_file_name(url: &str) -> String {
+    let name = url
+        .trim_start_matches("https://")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{}.toml", name)
+}
+
+/// Returns the directory config files fetched from a URL are cached in:
+/// `$XDG_CACHE_HOME/lintje`, or `~/.cache/lintje` when `XDG_CACHE_HOME` is not set.
+fn cache_dir() -> Option<PathBuf> {
+    let cache_home = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => home_dir()?.join(".cache"),
+    };
+    Some(cache_home.join("lintje"))
+}
+
+fn is_url(location: &str) -> bool {
+    location.starts_with("https://") || location.starts_with("http://")
+}
+
+/// Returns the location of the extended config file. Relative paths are relative to the
+/// directory of the config file that extends it, also for config files fetched from a URL.
+fn extended_config_location(location: &str, extends: &str) -> String {
+    if is_url(extends) || Path::new(extends).is_absolute() {
+        return extends.to_string();
+    }
+    if is_url(location) {
+        let base = location.rsplit_once('/').map_or(location, |(base, _)| base);
+        return format!("{}/{}", base, extends.trim_start_matches("./"));
+    }
+    Path::new(location)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(extends)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn home_dir() -> Option<PathBuf> {
+    match std::env::var_os("HOME") {
+        Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+        _ => None,
+    }
+}
+
+/// Returns the path of the user config file: `$XDG_CONFIG_HOME/lintje/config.toml`, or
+/// `~/.config/lintje/config.toml` when `XDG_CONFIG_HOME` is not set.
+fn user_config_file() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => home_dir()?.join(".config"),
+    };
+    Some(config_home.join("lintje").join("config.toml"))
+}
+
+/// Find the config files in the directory and its parent directories, up to the repository root.
+/// Outside a repository the parent directories are searched up to the home directory. The config
+/// file in the home directory applies to all repositories, like `~/.gitconfig` does for Git.
+///
+/// The files are returned from least to most specific, so the nearest config file overrides the
+/// settings of the config files further up.
+fn find_config_files(
+    dir: &Path,
+    repository_root: Option<&Path>,
+    home: Option<&Path>,
+) -> Vec<PathBuf> {
+    let root = repository_root
+        .or_else(|| home.filter(|home| dir.starts_with(home)))
+        .unwrap_or(dir);
+    let mut files = vec![];
+    for dir in dir.ancestors() {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if path.is_file() {
+            files.push(path);
+        }
+        if dir == root {
+            break;
+        }
+    }
+    if let Some(home) = home {
+        let path = home.join(CONFIG_FILE_NAME);
+        if path.is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files.reverse();
+    files
+}
+
+/// Settings from the `lintje.*` keys in the Git config, from all the Git config scopes: system,
+/// global and repository, and from the config files.
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    values: HashMap<String, Vec<String>>,
+    // The config file and key the last value of a key was read from. Keys without a file are
+    // read from the Git config.
+    origins: HashMap<String, String>,
+    // The paths and URLs of the config files a config file extends, with the `extends` key.
+    extends: Vec<String>,
+    // Problems loading the config files that don't stop Lintje from running, like config files
+    // that can't be fetched while offline.
+    warnings: Vec<String>,
+}
+
+impl Settings {
+    /// Parse the output of `git config --null --get-regexp`: every key and value pair is
+    /// separated by a NUL character, and the key is separated from the value by a newline.
+    pub fn from_git_config(output: &str) -> Self {
+        let mut values = HashMap::<String, Vec<String>>::new();
+        for entry in output.split('\0').filter(|entry| !entry.is_empty()) {
+            let (key, value) = match entry.split_once('\n') {
+                Some((key, value)) => (key, value),
+                // Keys without value, like `[lintje] hints`, are true booleans
+                None => (entry, "true"),
+            };
+            // Git config section and key names are case-insensitive. Git lowercases them in the
+            // output, but not in all versions.
+            let key = normalize_key(key.trim_start_matches("lintje."));
+            values.entry(key).or_default().push(value.to_string());
+        }
+        Self {
+            values,
+            ..Self::default()
+        }
+    }
+
+    /// Parse a TOML config file. The keys are the names of the command line options, like
+    /// `max-subject-length = 60`. Options that can be given multiple times accept a list.
+    /// Unknown keys are an error, so typos in shared config files don't go unnoticed.
+    ///
+    /// The `extends` key lists the paths and URLs of the config files this config file extends.
+    pub fn from_toml(contents: &str, path: &Path) -> Result<Self, String> {
+        let table = match contents.parse::<toml::Value>() {
+            Ok(toml::Value::Table(table)) => table,
+            Ok(_) => toml::value::Table::new(),
+            Err(e) => {
+                return Err(format!(
+                    "Invalid config file: {}\n{}",
+                    path.to_string_lossy(),
+                    e
+                ))
+            }
+        };
+        let mut settings = Self::default();
+        for (key, value) in table {
+            let label = format!("`{}` in {}", key, path.to_string_lossy());
+            let key = normalize_key(&key);
+            if key == "extends" {
+                settings.extends = match value {
+                    toml::Value::Array(items) => items
+                        .into_iter()
+                        .map(|item| toml_value(item, &label))
+                        .collect::<Result<Vec<String>, String>>()?,
+                    value => vec![toml_value(value, &label)?],
+                };
+                continue;
+            }
+            if SERVICE_URL_KEYS
+                .iter()
+                .any(|setting| normalize_key(setting) == key)
+            {
+                return Err(format!(
+                    "Unsupported option {}: service URLs can't be set in config files, so the \
+                    config file can't change where API tokens are sent. Use the command line \
+                    option, environment variable or Git config instead.",
+                    label
+                ));
+            }
+            if !SETTING_KEYS
+                .iter()
+                .any(|setting| normalize_key(setting) == key)
+            {
+                return Err(format!("Unknown option {}", label));
+            }
+            let values = match value {
+                toml::Value::Array(items) => items
+                    .into_iter()
+                    .map(|item| toml_value(item, &label))
+                    .collect::<Result<Vec<String>, String>>()?,
+                value => vec![toml_value(value, &label)?],
+            };
+            settings.values.insert(key.clone(), values);
+            settings.origins.insert(key, label);
+        }
+        Ok(settings)
+    }
+
+    /// Merge the settings on top of these settings. Values of keys that can be set once are
+    /// overwritten, values of keys that can be set multiple times are combined.
+    pub fn merge(&mut self, other: Settings) {
+        for (key, values) in other.values {
+            match other.origins.get(&key) {
+                Some(origin) => self.origins.insert(key.clone(), origin.to_string()),
+                None => self.origins.remove(&key),
+            };
+            self.values.entry(key).or_default().extend(values);
+        }
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Returns a description of where the key's value was set, for error messages.
+    pub fn label(&self, key: &str) -> String {
+        match self.origins.get(&normalize_key(key)) {
+            Some(origin) => origin.to_string(),
+            None => format!("lintje.{}", key),
+        }
+    }
+
+    /// Returns the value of the key. If the key is set multiple times, the last value is returned,
+    /// which is the value of the most specific scope.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(&normalize_key(key))
+            .and_then(|values| values.last())
+            .map(|value| value.as_str())
+    }
+
+    /// Returns all values of the key, of all scopes.
+    pub fn get_all(&self, key: &str) -> Vec<String> {
+        self.values
+            .get(&normalize_key(key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns all values of the key, of all scopes, parsed with the parser.
+    pub fn get_all_parsed<T, F>(&self, key: &str, parser: F) -> Result<Vec<T>, String>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        self.get_all(key)
+            .iter()
+            .map(|value| {
+                parser(value).map_err(|e| format!("Invalid value for {}: {}", self.label(key), e))
+            })
+            .collect()
+    }
+
+    pub fn get_bool(&self, key: &str) -> Result<Option<bool>, String> {
+        match self.get(key) {
+            Some(value) => match value.to_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => Ok(Some(true)),
+                "false" | "no" | "off" | "0" | "" => Ok(Some(false)),
+                _ => Err(format!(
+                    "Invalid boolean value for {}: {}",
+                    self.label(key),
+                    value
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_usize(&self, key: &str) -> Result<Option<usize>, String> {
+        match self.get(key) {
+            Some(value) => value
+                .trim()
+                .parse::<usize>()
+                .map(Some)
+                .map_err(|_| format!("Invalid number value for {}: {}", self.label(key), value)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Normalize the key so the Git config keys, like `maxSubjectLength`, and the config file keys,
+/// like `max-subject-length`, match.
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn toml_value(value: toml::Value, label: &str) -> Result<String, String> {
+    match value {
+        toml::Value::String(value) => Ok(value),
+        toml::Value::Integer(value) => Ok(value.to_string()),
+        toml::Value::Boolean(value) => Ok(value.to_string()),
+        _ => Err(format!("Unsupported value for {}: {}", label, value)),
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub debug: bool,
+    pub color: bool,
+    pub format: OutputFormat,
+    pub group_by: Option<GroupBy>,
+    pub language: Language,
+    pub hints: bool,
+    /// Rules of which the hints are not reported. The errors of these rules are still reported.
+    pub hidden_hint_rules: Vec<Rule>,
+    pub quiet: bool,
+    pub rule_stats: bool,
+}
+
+impl Options {
+    /// Returns true if the issue is a hint of a rule of which the hints are hidden. Hidden hints
+    /// are not reported or counted, unlike hints hidden with the `--no-hints` option.
+    pub fn hint_hidden(&self, issue: &Issue) -> bool {
+        issue.r#type == IssueType::Hint && self.hidden_hint_rules.contains(&issue.rule)
+    }
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Command {
+    /// Git hook scripts that run Lintje.
+    Hook {
+        #[clap(subcommand)]
+        command: HookCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum HookCommand {
+    /// Print the script of the given Git hook, to install it in the `.git/hooks` directory or add
+    /// it to a hook manager, like lefthook or husky.
+    Print {
+        #[clap(arg_enum)]
+        hook: HookScript,
+    },
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HookScript {
+    /// Lint the commit message after it's written.
+    CommitMsg,
+    /// Lint the commit message before the message editor is opened.
+    PrepareCommitMsg,
+    /// Lint the commits that are about to be pushed.
+    PrePush,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Issues with the problematic lines and underlined suggestions.
+    Text,
+    /// Issues described in full sentences, with explicit line and column numbers.
+    Plain,
+    /// One line per issue with its position, rule and message, for editors and `grep`.
+    Compact,
+    /// All issues and the totals as one JSON document, for other tools to process.
+    Json,
+    /// All issues as a SARIF log, for GitHub code scanning and other SARIF tools. Issues are
+    /// located in the commit message file or `.git/HEAD`, and in the commit or branch.
+    Sarif,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ColorWhen {
+    /// Color when the output is a terminal.
+    Auto,
+    /// Always color, also when the output is piped.
+    Always,
+    /// Never color.
+    Never,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum StdinFormat {
+    /// One commit message.
+    Message,
+    /// A JSON array of commits, for tools that fetch the commits themselves.
+    Json,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Preset {
+    /// Also check the spelling, the branch name characters and case, and protected branches.
+    Strict,
+    /// Allow longer subjects, commits without a message body and fixup commits, without hints.
+    Relaxed,
+    /// Allow Conventional Commits subjects, like `feat: add login form`.
+    Conventional,
+}
+
+impl Preset {
+    /// The settings of the preset, in the config file format.
+    fn config(self) -> &'static str {
+        match self {
+            Preset::Strict => {
+                "check-spelling = true\n\
+                subject-min-words = 2\n\
+                message-min-paragraphs = 2\n\
+                check-branch-case = true\n\
+                check-branch-characters = true\n\
+                check-protected-branch = true\n"
+            }
+            Preset::Relaxed => {
+                "hints = false\n\
+                max-subject-length = 72\n\
+                allow-fixup = true\n\
+                allow-generated-branch-names = true\n\
+                disable-rule = [\"MessagePresence\"]\n"
+            }
+            Preset::Conventional => {
+                "max-subject-length = 72\n\
+                disable-rule = [\"SubjectPrefix\", \"SubjectCapitalization\"]\n"
+            }
+        }
+    }
+
+    fn settings(self) -> Settings {
+        let name = self
+            .to_possible_value()
+            .map_or("", |value| value.get_name());
+        Settings::from_toml(self.config(), Path::new(&format!("the {} preset", name)))
+            .expect("Invalid preset config")
+    }
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum GroupBy {
+    /// A header line per commit, followed by the issues of the commit indented beneath it.
+    Commit,
+    /// A header line per rule, followed by the issues of the rule indented beneath it.
+    Rule,
+}
+
+#[derive(ArgEnum, Clone, Debug, PartialEq)]
+pub enum Hook {
+    PreCommit,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cache_file_name, detect_color, expand_repository_path, extended_config_location,
+        fetch_config_file, find_config_files, load_config_file, parse_repositories_file, ColorWhen,
+        Command, GroupBy, Hook, HookCommand, HookScript, Lint, OutputFormat, Preset, Settings,
+        StdinFormat,
+    };
+    use clap::{ArgEnum, CommandFactory, FromArgMatches, Parser};
+    use lintje::bitbucket;
+    use lintje::git::CommitSource;
+    use lintje::github::PullRequestReference;
+    use lintje::gitlab::{GitLabToken, MergeRequestReference};
+    use lintje::i18n::Language;
+    use lintje::options::{ForbiddenPhrase, Lockfile, SpellingWord};
+    use lintje::rule::Rule;
+    use std::ffi::OsString;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_color_flags() {
+        // Both color flags set, but --no-color is leading
+        assert!(!Lint::parse_from(["lintje", "--color", "--no-color"]).color());
+
+        // Only --color is set
+        assert!(Lint::parse_from(["lintje", "--color"]).color());
+
+        // Only --no-color is set
+        assert!(!Lint::parse_from(["lintje", "--no-color"]).color());
+
+        // No flags are set, STDOUT is not a terminal in tests
+        assert!(!Lint::parse_from(["lintje"]).color());
+
+        assert!(Lint::parse_from(["lintje", "--color=always"]).color());
+        assert!(!Lint::parse_from(["lintje", "--color=never"]).color());
+        assert!(!Lint::parse_from(["lintje", "--color=auto"]).color());
+        assert!(!Lint::parse_from(["lintje", "--color=always", "--no-color"]).color());
+        assert!(Lint::try_parse_from(["lintje", "--color=sometimes"]).is_err());
+        // The commit (range) is not the value of the color option
+        let lint = Lint::parse_from(["lintje", "--color", "HEAD~1"]);
+        assert_eq!(lint.color, Some(ColorWhen::Always));
+        assert_eq!(lint.selection, Some("HEAD~1".to_string()));
+    }
+
+    #[test]
+    fn test_detect_color() {
+        assert!(detect_color(true, None, None));
+        assert!(!detect_color(false, None, None));
+        assert!(!detect_color(true, Some(OsString::from("1")), None));
+        // An empty value is the same as not set
+        assert!(detect_color(true, Some(OsString::new()), None));
+
+        assert!(detect_color(false, None, Some(OsString::from("1"))));
+        assert!(!detect_color(false, None, Some(OsString::from("0"))));
+        assert!(!detect_color(false, None, Some(OsString::new())));
+        assert!(!detect_color(
+            false,
+            Some(OsString::from("1")),
+            Some(OsString::from("1"))
+        ));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Lint::parse_from(["lintje"]).format, OutputFormat::Text);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--format=plain"]).format,
+            OutputFormat::Plain
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "--format=compact"]).format,
+            OutputFormat::Compact
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "--format=json"]).format,
+            OutputFormat::Json
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "--format=sarif"]).format,
+            OutputFormat::Sarif
+        );
+        assert!(Lint::try_parse_from(["lintje", "--format=unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_group_by() {
+        assert_eq!(Lint::parse_from(["lintje"]).group_by, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--group-by=commit"]).group_by,
+            Some(GroupBy::Commit)
+        );
+        assert!(Lint::try_parse_from(["lintje", "--group-by=unknown"]).is_err());
+        assert_eq!(
+            Lint::parse_from(["lintje", "--group-by=rule"]).group_by,
+            Some(GroupBy::Rule)
+        );
+        let lint = lint_with_git_config(&["lintje"], "lintje.groupby\ncommit\0").unwrap();
+        assert_eq!(lint.group_by, Some(GroupBy::Commit));
+        let lint = lint_with_git_config(&["lintje"], "lintje.groupby\nrule\0").unwrap();
+        assert_eq!(lint.group_by, Some(GroupBy::Rule));
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.groupby\nunknown\0").unwrap_err(),
+            "Invalid value for lintje.groupBy: unknown"
+        );
+    }
+
+    #[test]
+    fn test_preset() {
+        for preset in Preset::value_variants() {
+            // Every preset is a valid config
+            lint_with_settings(&["lintje"], &preset.settings()).unwrap();
+        }
+        assert_eq!(Lint::parse_from(["lintje"]).preset, None);
+        assert!(Lint::try_parse_from(["lintje", "--preset=unknown"]).is_err());
+
+        let lint = lint_with_settings(&["lintje", "--preset=relaxed"], &Settings::default());
+        let lint = lint.unwrap();
+        assert_eq!(lint.preset, Some(Preset::Relaxed));
+        assert_eq!(lint.max_subject_length, Some(72));
+        assert!(!lint.hints);
+        assert_eq!(lint.disabled_rules, vec![Rule::MessagePresence]);
+
+        // The settings override the preset
+        let lint = lint_with_git_config(
+            &["lintje"],
+            "lintje.preset\nconventional\0lintje.maxsubjectlength\n60\0\
+            lintje.disablerule\nSubjectMood\0",
+        )
+        .unwrap();
+        assert_eq!(lint.preset, Some(Preset::Conventional));
+        assert_eq!(lint.max_subject_length, Some(60));
+        assert_eq!(
+            lint.disabled_rules,
+            vec![
+                Rule::SubjectPrefix,
+                Rule::SubjectCapitalization,
+                Rule::SubjectMood
+            ]
+        );
+
+        // The command line options override the settings and the preset
+        let lint = lint_with_git_config(
+            &["lintje", "--preset=strict", "--max-subject-length=40"],
+            "lintje.preset\nrelaxed\0",
+        )
+        .unwrap();
+        assert_eq!(lint.max_subject_length, Some(40));
+        assert!(lint.check_spelling);
+        assert!(lint.hints);
+
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.preset\nunknown\0").unwrap_err(),
+            "Invalid value for lintje.preset: unknown"
+        );
+    }
+
+    #[test]
+    fn test_lang() {
+        assert_eq!(Lint::parse_from(["lintje"]).lang, Language::En);
+        assert_eq!(Lint::parse_from(["lintje", "--lang=nl"]).lang, Language::Nl);
+        assert!(Lint::try_parse_from(["lintje", "--lang=unknown"]).is_err());
+        let lint = lint_with_git_config(&["lintje"], "lintje.lang\nnl\0").unwrap();
+        assert_eq!(lint.lang, Language::Nl);
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.lang\nunknown\0").unwrap_err(),
+            "Invalid value for lintje.lang: unknown"
+        );
+    }
+
+    #[test]
+    fn test_quiet() {
+        assert!(!Lint::parse_from(["lintje"]).quiet);
+        assert!(Lint::parse_from(["lintje", "--quiet"]).quiet);
+        assert!(Lint::parse_from(["lintje", "-q"]).quiet);
+        assert!(Lint::try_parse_from(["lintje", "--quiet", "--format=json"]).is_err());
+        let lint = lint_with_git_config(&["lintje"], "lintje.quiet\ntrue\0").unwrap();
+        assert!(lint.quiet);
+    }
+
+    #[test]
+    fn test_rule_stats() {
+        assert!(!Lint::parse_from(["lintje"]).rule_stats);
+        assert!(Lint::parse_from(["lintje", "--rule-stats"]).rule_stats);
+        let lint = lint_with_git_config(&["lintje"], "lintje.rulestats\ntrue\0").unwrap();
+        assert!(lint.rule_stats);
+    }
+
+    #[test]
+    fn test_changed_since_push() {
+        assert!(!Lint::parse_from(["lintje"]).changed_since_push);
+        assert!(Lint::parse_from(["lintje", "--changed-since-push"]).changed_since_push);
+        assert!(Lint::try_parse_from(["lintje", "--changed-since-push", "HEAD~1..HEAD"]).is_err());
+    }
+
+    #[test]
+    fn test_all() {
+        assert!(!Lint::parse_from(["lintje"]).all);
+        assert!(Lint::parse_from(["lintje", "--all"]).all);
+        assert!(Lint::try_parse_from(["lintje", "--all", "HEAD~1..HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--all", "--changed-since-push"]).is_err());
+    }
+
+    #[test]
+    fn test_branch_range() {
+        assert!(!Lint::parse_from(["lintje"]).branch_range);
+        assert!(Lint::parse_from(["lintje", "--branch-range"]).branch_range);
+        assert!(Lint::try_parse_from(["lintje", "--branch-range", "HEAD~1..HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--branch-range", "--pr", "482"]).is_err());
+    }
+
+    #[test]
+    fn test_author() {
+        assert_eq!(Lint::parse_from(["lintje"]).author, None);
+        let lint = Lint::parse_from(["lintje", "--author", "jane@example.com", "HEAD~3..HEAD"]);
+        assert_eq!(lint.author, Some("jane@example.com".to_string()));
+        assert_eq!(
+            lint.fetch_options().author,
+            Some("jane@example.com".to_string())
+        );
+        assert!(Lint::try_parse_from(["lintje", "--author", "jane", "--stdin"]).is_err());
+    }
+
+    #[test]
+    fn test_since() {
+        assert_eq!(Lint::parse_from(["lintje"]).since, None);
+        let lint = Lint::parse_from(["lintje", "--since", "2 weeks ago"]);
+        assert_eq!(lint.since, Some("2 weeks ago".to_string()));
+        assert_eq!(lint.fetch_options().since, Some("2 weeks ago".to_string()));
+        assert!(Lint::try_parse_from(["lintje", "--since", "2 weeks ago", "--stdin"]).is_err());
+    }
+
+    #[test]
+    fn test_pull_request() {
+        assert_eq!(Lint::parse_from(["lintje"]).pull_request, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--pr", "482"]).pull_request,
+            Some(482)
+        );
+        assert!(Lint::try_parse_from(["lintje", "--pr", "abc"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--pr", "482", "HEAD~1..HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--pr", "482", "--all"]).is_err());
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(Lint::parse_from(["lintje"]).message, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--message", "Fix the tests"]).message,
+            Some("Fix the tests".to_string())
+        );
+        assert!(Lint::try_parse_from(["lintje", "--message", "Fix", "HEAD~1"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--message", "Fix", "--stdin"]).is_err());
+    }
+
+    #[test]
+    fn test_template() {
+        assert_eq!(Lint::parse_from(["lintje"]).template, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--template"]).template,
+            Some(None)
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "--template=.gitmessage"]).template,
+            Some(Some(PathBuf::from(".gitmessage")))
+        );
+        assert!(Lint::try_parse_from(["lintje", "--template", "--message", "Fix"]).is_err());
+    }
+
+    #[test]
+    fn test_stdin() {
+        assert!(!Lint::parse_from(["lintje"]).stdin());
+        assert!(!Lint::parse_from(["lintje", "HEAD~1"]).stdin());
+        assert!(Lint::parse_from(["lintje", "--stdin"]).stdin());
+        assert!(Lint::parse_from(["lintje", "-"]).stdin());
+        assert!(Lint::try_parse_from(["lintje", "--stdin", "HEAD~1"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--stdin", "--all"]).is_err());
+
+        assert_eq!(
+            Lint::parse_from(["lintje", "--stdin"]).stdin_format,
+            StdinFormat::Message
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "--stdin", "--stdin-format=json"]).stdin_format,
+            StdinFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_repositories() {
+        assert!(!Lint::parse_from(["lintje"]).is_batch());
+
+        let lint = Lint::parse_from(["lintje", "--repo", "service-a", "--repo=/service-b"]);
+        assert!(lint.is_batch());
+        let repositories = lint.repositories().unwrap();
+        assert_eq!(repositories.len(), 2);
+        assert!(repositories[0].is_absolute());
+        assert!(repositories[0].ends_with("service-a"));
+        assert_eq!(repositories[1], Path::new("/service-b"));
+
+        assert!(Lint::parse_from(["lintje", "--repos-file", "repos.txt"]).is_batch());
+        assert!(Lint::try_parse_from(["lintje", "--repo=a", "--hook=pre-commit"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_repositories_file() {
+        let repositories = parse_repositories_file(
+            "service-a\n\n# Comment\n  service-b  \n/service-c\n",
+            Path::new("/repos"),
+        );
+        assert_eq!(
+            repositories,
+            vec![
+                Path::new("/repos/service-a"),
+                Path::new("/repos/service-b"),
+                Path::new("/service-c")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_repository_path() {
+        let root = std::env::current_dir()
+            .unwrap()
+            .join("tmp/tests/config/expand_repository_path");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("services/service-b/.git")).unwrap();
+        fs::create_dir_all(root.join("services/service-a/.git")).unwrap();
+        fs::create_dir_all(root.join("services/docs")).unwrap();
+        fs::create_dir_all(root.join("services/service-bare.git/refs")).unwrap();
+        fs::write(root.join("services/service-bare.git/HEAD"), "").unwrap();
+        fs::write(root.join("services/service-file"), "").unwrap();
+
+        assert_eq!(
+            expand_repository_path(&root.join("services/*")),
+            vec![
+                root.join("services/service-a"),
+                root.join("services/service-b"),
+                root.join("services/service-bare.git"),
+            ]
+        );
+        assert_eq!(
+            expand_repository_path(&root.join("*/service-?")),
+            vec![
+                root.join("services/service-a"),
+                root.join("services/service-b")
+            ]
+        );
+        assert!(expand_repository_path(&root.join("services/other-*")).is_empty());
+        // Paths without wildcards are not checked
+        assert_eq!(
+            expand_repository_path(&root.join("services/docs")),
+            vec![root.join("services/docs")]
+        );
+    }
+
+    #[test]
+    fn test_docs() {
+        assert_eq!(Lint::parse_from(["lintje"]).docs, None);
+        assert_eq!(Lint::parse_from(["lintje", "--docs"]).docs, Some(None));
+        assert_eq!(
+            Lint::parse_from(["lintje", "--docs", "SubjectLength"]).docs,
+            Some(Some("SubjectLength".to_string()))
+        );
+        // A ref named like the option is linted
+        let lint = Lint::parse_from(["lintje", "docs"]);
+        assert_eq!(lint.docs, None);
+        assert_eq!(lint.selection, Some("docs".to_string()));
+    }
+
+    #[test]
+    fn test_hook_print_command() {
+        assert_eq!(
+            Lint::parse_from(["lintje", "hook", "print", "commit-msg"]).command,
+            Some(Command::Hook {
+                command: HookCommand::Print {
+                    hook: HookScript::CommitMsg
+                }
+            })
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "hook", "print", "prepare-commit-msg"]).command,
+            Some(Command::Hook {
+                command: HookCommand::Print {
+                    hook: HookScript::PrepareCommitMsg
+                }
+            })
+        );
+        assert_eq!(
+            Lint::parse_from(["lintje", "hook", "print", "pre-push"]).command,
+            Some(Command::Hook {
+                command: HookCommand::Print {
+                    hook: HookScript::PrePush
+                }
+            })
+        );
+        assert!(Lint::try_parse_from(["lintje", "hook", "print", "post-commit"]).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(Lint::parse_from(["lintje"]).explain, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--explain", "SubjectLength"]).explain,
+            Some("SubjectLength".to_string())
+        );
+    }
+
+    #[test]
+    fn test_jira() {
+        let lint = Lint::parse_from(["lintje"]);
+        assert_eq!(lint.jira_url, None);
+        assert!(lint.rule_options().ticket_trackers.is_empty());
+
+        let lint = Lint::parse_from([
+            "lintje",
+            "--jira-url=https://example.atlassian.net",
+            "--jira-user=user@example.com",
+            "--jira-token=secret",
+            "--jira-project=JIRA",
+            "--require-open-tickets",
+        ]);
+        assert_eq!(lint.jira_user, Some("user@example.com".to_string()));
+        assert_eq!(lint.jira_token, Some("secret".to_string()));
+        assert_eq!(lint.jira_projects, vec!["JIRA"]);
+        let options = lint.rule_options();
+        assert_eq!(options.ticket_trackers.len(), 1);
+        assert_eq!(options.ticket_trackers[0].name(), "Jira");
+        assert!(options.require_open_tickets);
+
+        assert_eq!(
+            lint_with_git_config(&["lintje", "--jira-url=https://example.atlassian.net"], "")
+                .unwrap_err(),
+            "The Jira project keys are required to verify Jira tickets. \
+            Configure them with the `--jira-project` option."
+        );
+    }
+
+    #[test]
+    fn test_github() {
+        let lint = Lint::parse_from(["lintje"]);
+        assert!(!lint.verify_github_issues);
+        assert!(lint.rule_options().ticket_trackers.is_empty());
+
+        let lint = Lint::parse_from([
+            "lintje",
+            "--verify-github-issues",
+            "--github-repository=tombruijn/lintje",
+            "--github-token=secret",
+            "--github-api-url=https://github.example.com/api/v3",
+        ]);
+        assert_eq!(lint.github_repository, Some("tombruijn/lintje".to_string()));
+        assert_eq!(lint.github_token, Some("secret".to_string()));
+        assert_eq!(lint.github_api_url, "https://github.example.com/api/v3");
+        let options = lint.rule_options();
+        assert_eq!(options.ticket_trackers.len(), 1);
+        assert_eq!(options.ticket_trackers[0].name(), "GitHub");
+    }
+
+    #[test]
+    fn test_github_pr() {
+        assert_eq!(Lint::parse_from(["lintje"]).github_pr, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--github-pr", "tombruijn/lintje#12"]).github_pr,
+            Some(PullRequestReference {
+                repository: "tombruijn/lintje".to_string(),
+                number: 12
+            })
+        );
+        assert!(Lint::try_parse_from(["lintje", "--github-pr", "12"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--github-pr=a/b#1", "HEAD"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--github-pr=a/b#1", "--pr=1"]).is_err());
+    }
+
+    #[test]
+    fn test_gitlab_mr() {
+        let lint = Lint::parse_from(["lintje"]);
+        assert_eq!(lint.gitlab_mr, None);
+        assert_eq!(lint.gitlab_merge_request(), None);
+
+        let lint = Lint::parse_from(["lintje", "--gitlab-mr", "group/project!12"]);
+        assert_eq!(
+            lint.gitlab_merge_request(),
+            Some(Ok(MergeRequestReference {
+                project: "group/project".to_string(),
+                iid: 12
+            }))
+        );
+        let lint = Lint::parse_from(["lintje", "--gitlab-mr", "12"]);
+        assert!(matches!(lint.gitlab_merge_request(), Some(Err(_))));
+        assert_eq!(
+            Lint::parse_from(["lintje", "--gitlab-mr"]).gitlab_mr,
+            Some(None)
+        );
+        assert!(
+            Lint::try_parse_from(["lintje", "--gitlab-mr=a/b!1", "--github-pr=a/b#1"]).is_err()
+        );
+
+        let lint = Lint::parse_from(["lintje", "--gitlab-token=secret"]);
+        assert_eq!(
+            lint.gitlab_token(),
+            Some(GitLabToken::Private("secret".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_bitbucket_pr() {
+        let lint = Lint::parse_from(["lintje"]);
+        assert_eq!(lint.bitbucket_pr, None);
+        assert_eq!(lint.bitbucket_pull_request(), None);
+
+        let lint = Lint::parse_from(["lintje", "--bitbucket-pr", "workspace/repo#12"]);
+        assert_eq!(
+            lint.bitbucket_pull_request(),
+            Some(Ok(bitbucket::PullRequestReference {
+                repository: "workspace/repo".to_string(),
+                id: 12
+            }))
+        );
+        let lint = Lint::parse_from(["lintje", "--bitbucket-pr", "12"]);
+        assert!(matches!(lint.bitbucket_pull_request(), Some(Err(_))));
+        assert_eq!(
+            Lint::parse_from(["lintje", "--bitbucket-pr"]).bitbucket_pr,
+            Some(None)
+        );
+        assert!(Lint::try_parse_from(["lintje", "--bitbucket-pr=a/b#1", "--gitlab-mr"]).is_err());
+    }
+
+    fn lint_with_settings(args: &[&str], settings: &Settings) -> Result<Lint, String> {
+        let matches = Lint::command().get_matches_from(args);
+        let mut lint = Lint::from_arg_matches(&matches).unwrap();
+        lint.apply_settings(&matches, settings)?;
+        Ok(lint)
+    }
+
+    fn lint_with_git_config(args: &[&str], config: &str) -> Result<Lint, String> {
+        lint_with_settings(args, &Settings::from_git_config(config))
+    }
+
+    #[test]
+    fn test_git_config_parse() {
+        let config = Settings::from_git_config(
+            "lintje.hints\nfalse\0lintje.clichephrase\nBump version\0\
+            lintje.clichePhrase\nMore stuff\0lintje.color\0lintje.hints\ntrue\0",
+        );
+        assert_eq!(config.get("hints"), Some("true"));
+        assert_eq!(config.get_bool("hints"), Ok(Some(true)));
+        assert_eq!(config.get_bool("color"), Ok(Some(true)));
+        assert_eq!(config.get_bool("branch"), Ok(None));
+        assert_eq!(
+            config.get_all("clichePhrase"),
+            vec!["Bump version", "More stuff"]
+        );
+        assert_eq!(config.get_all("forbiddenBranchWord"), Vec::<String>::new());
+
+        let config =
+            Settings::from_git_config("lintje.hints\nmaybe\0lintje.maxsubjectlength\nabc\0");
+        assert!(config.get_bool("hints").is_err());
+        assert!(config.get_usize("maxSubjectLength").is_err());
+    }
+
+    #[test]
+    fn test_git_config() {
+        let config = "lintje.branch\nno\0lintje.hints\nfalse\0lintje.color\ntrue\0\
+            lintje.format\nplain\0lintje.maxsubjectlength\n72\0\
+            lintje.clichephrase\nBump version\0lintje.branchmaxdepth\n2\0\
+            lintje.jiraurl\nhttps://example.atlassian.net\0lintje.jiraproject\nJIRA\0";
+        let lint = lint_with_git_config(&["lintje"], config).unwrap();
+        assert!(!lint.branch_validation);
+        assert!(!lint.hints);
+        assert!(lint.color());
+        assert_eq!(lint.format, OutputFormat::Plain);
+        assert_eq!(lint.max_subject_length, Some(72));
+        assert_eq!(lint.cliche_phrases, vec!["Bump version"]);
+        assert_eq!(lint.branch_max_depth, Some(2));
+        assert_eq!(
+            lint.jira_url,
+            Some("https://example.atlassian.net".to_string())
+        );
+
+        // Command line arguments take precedence
+        let lint = lint_with_git_config(
+            &[
+                "lintje",
+                "--no-color",
+                "--max-subject-length=60",
+                "--cliche-phrase=Tweak",
+            ],
+            config,
+        )
+        .unwrap();
+        assert!(!lint.color());
+        assert_eq!(lint.max_subject_length, Some(60));
+        assert_eq!(lint.cliche_phrases, vec!["Tweak"]);
+        let lint = lint_with_git_config(&["lintje", "--color=auto"], config).unwrap();
+        assert_eq!(lint.color, Some(ColorWhen::Auto));
+        let lint = lint_with_git_config(&["lintje"], "lintje.color\nauto\0").unwrap();
+        assert_eq!(lint.color, Some(ColorWhen::Auto));
+
+        // Defaults without Git config
+        let lint = lint_with_git_config(&["lintje"], "").unwrap();
+        assert!(lint.branch_validation);
+        assert!(lint.hints);
+        assert_eq!(lint.max_subject_length, None);
+
+        assert!(lint_with_git_config(&["lintje"], "lintje.format\nunknown\0").is_err());
+    }
+
+    #[test]
+    fn test_config_file() {
+        let path = Path::new("/repo/.lintje.toml");
+        let mut settings = Settings::from_git_config(
+            "lintje.hints\nfalse\0lintje.clichephrase\nBump version\0lintje.branch\nfalse\0",
+        );
+        settings.merge(
+            Settings::from_toml(
+                "max-subject-length = 60\n\
+                hints = true\n\
+                cliche_phrase = [\"Minor fixes\"]\n\
+                branchMaxDepth = 2",
+                path,
+            )
+            .unwrap(),
+        );
+        let lint = lint_with_settings(&["lintje"], &settings).unwrap();
+        assert_eq!(lint.max_subject_length, Some(60));
+        assert!(lint.hints);
+        assert!(!lint.branch_validation);
+        assert_eq!(lint.cliche_phrases, vec!["Bump version", "Minor fixes"]);
+        assert_eq!(lint.branch_max_depth, Some(2));
+
+        let settings = Settings::from_toml("hints = \"maybe\"", path).unwrap();
+        assert_eq!(
+            lint_with_settings(&["lintje"], &settings).unwrap_err(),
+            "Invalid boolean value for `hints` in /repo/.lintje.toml: maybe"
+        );
+        assert_eq!(
+            Settings::from_toml("max-subject-lenght = 60", path).unwrap_err(),
+            "Unknown option `max-subject-lenght` in /repo/.lintje.toml"
+        );
+        // Secrets can't be set in config files
+        assert!(Settings::from_toml("jira-token = \"secret\"", path).is_err());
+        // The URLs tokens are sent to can't be set in config files
+        assert_eq!(
+            Settings::from_toml("jira-url = \"https://example.com\"", path).unwrap_err(),
+            "Unsupported option `jira-url` in /repo/.lintje.toml: service URLs can't be set in \
+            config files, so the config file can't change where API tokens are sent. Use the \
+            command line option, environment variable or Git config instead."
+        );
+        assert!(Settings::from_toml("github-api-url = \"https://example.com\"", path).is_err());
+        assert!(Settings::from_toml("gitlabApiUrl = \"https://example.com\"", path).is_err());
+        assert!(Settings::from_toml("hints = [[1]]", path).is_err());
+        assert!(Settings::from_toml("hints = ", path).is_err());
+    }
+
+    #[test]
+    fn test_config_file_extends() {
+        let dir = std::env::current_dir()
+            .unwrap()
+            .join("tmp/tests/config/config_file_extends");
+        fs::create_dir_all(dir.join("shared")).unwrap();
+        fs::write(
+            dir.join("shared/base.toml"),
+            "max-subject-length = 60\nhints = false\ncliche-phrase = \"Bump version\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join(".lintje.toml"),
+            "extends = \"shared/base.toml\"\nhints = true\ncliche-phrase = [\"Minor fixes\"]\n",
+        )
+        .unwrap();
+        let path = dir.join(".lintje.toml").to_string_lossy().to_string();
+        let settings = load_config_file(&path, &mut vec![]).unwrap();
+        let lint = lint_with_settings(&["lintje"], &settings).unwrap();
+        assert_eq!(lint.max_subject_length, Some(60));
+        assert!(lint.hints);
+        assert_eq!(lint.cliche_phrases, vec!["Bump version", "Minor fixes"]);
+
+        fs::write(
+            dir.join("shared/base.toml"),
+            "extends = [\"../.lintje.toml\"]\n",
+        )
+        .unwrap();
+        assert!(load_config_file(&path, &mut vec![])
+            .unwrap_err()
+            .starts_with("Config file extends itself: "));
+
+        fs::write(dir.join("shared/base.toml"), "extends = \"missing.toml\"\n").unwrap();
+        assert!(load_config_file(&path, &mut vec![])
+            .unwrap_err()
+            .starts_with("Unable to read config file: "));
+    }
+
+    #[test]
+    fn test_extended_config_location() {
+        assert_eq!(
+            extended_config_location("/repo/.lintje.toml", "shared/base.toml"),
+            "/repo/shared/base.toml"
+        );
+        assert_eq!(
+            extended_config_location("/repo/.lintje.toml", "/etc/lintje.toml"),
+            "/etc/lintje.toml"
+        );
+        assert_eq!(
+            extended_config_location("/repo/.lintje.toml", "https://example.com/lintje.toml"),
+            "https://example.com/lintje.toml"
+        );
+        assert_eq!(
+            extended_config_location("https://example.com/configs/strict.toml", "./base.toml"),
+            "https://example.com/configs/base.toml"
+        );
+    }
+
+    #[test]
+    fn test_fetch_config_file() {
+        let dir = std::env::current_dir()
+            .unwrap()
+            .join("tmp/tests/config/fetch_config_file");
+        let _ = fs::remove_dir_all(&dir);
+        let url = "https://127.0.0.1:9/lintje.toml";
+        assert_eq!(cache_file_name(url), "127.0.0.1_9_lintje.toml.toml");
+
+        // Without a cached copy, config files that can't be fetched are skipped with a warning
+        let warning = fetch_config_file(url, Some(&dir)).unwrap().unwrap_err();
+        assert!(warning.starts_with(
+            "Unable to fetch config file, skipping it: https://127.0.0.1:9/lintje.toml\n"
+        ));
+
+        // Recently cached config files are not fetched again
+        fs::create_dir_all(&dir).unwrap();
+        let cache_file = dir.join(cache_file_name(url));
+        fs::write(&cache_file, "hints = false\n").unwrap();
+        assert_eq!(
+            fetch_config_file(url, Some(&dir)).unwrap(),
+            Ok("hints = false\n".to_string())
+        );
+
+        // Outdated cached config files are used when the config file can't be fetched
+        let touched = std::process::Command::new("touch")
+            .args(["-t", "200001010000"])
+            .arg(&cache_file)
+            .status()
+            .unwrap();
+        assert!(touched.success());
+        assert_eq!(
+            fetch_config_file(url, Some(&dir)).unwrap(),
+            Ok("hints = false\n".to_string())
+        );
+
+        assert_eq!(
+            fetch_config_file("http://example.com/lintje.toml", Some(&dir)).unwrap_err(),
+            "Unable to fetch config file: http://example.com/lintje.toml\n\
+            Config files can only be fetched over HTTPS"
+        );
+    }
+
+    #[test]
+    fn test_find_config_files() {
+        let home = std::env::current_dir()
+            .unwrap()
+            .join("tmp/tests/config/find_config_files");
+        let root = home.join("repository");
+        let dir = root.join("src/nested");
+        fs::create_dir_all(&dir).unwrap();
+        for path in [&home, &root, &dir] {
+            let _ = fs::remove_file(path.join(".lintje.toml"));
+        }
+        assert!(find_config_files(&dir, Some(&root), Some(&home)).is_empty());
+
+        fs::write(root.join(".lintje.toml"), "").unwrap();
+        assert_eq!(
+            find_config_files(&dir, Some(&root), None),
+            vec![root.join(".lintje.toml")]
+        );
+        // Outside a repository, only the directory itself is searched
+        assert!(find_config_files(&dir, None, None).is_empty());
+        assert_eq!(
+            find_config_files(&root, None, None),
+            vec![root.join(".lintje.toml")]
+        );
+        // Not above the repository root
+        assert!(find_config_files(&dir, Some(&dir), None).is_empty());
+
+        // From least to most specific
+        fs::write(home.join(".lintje.toml"), "").unwrap();
+        fs::write(dir.join(".lintje.toml"), "").unwrap();
+        assert_eq!(
+            find_config_files(&dir, Some(&root), Some(&home)),
+            vec![
+                home.join(".lintje.toml"),
+                root.join(".lintje.toml"),
+                dir.join(".lintje.toml"),
+            ]
+        );
+        // Outside a repository, up to the home directory
+        assert_eq!(
+            find_config_files(&dir, None, Some(&home)),
+            vec![
+                home.join(".lintje.toml"),
+                root.join(".lintje.toml"),
+                dir.join(".lintje.toml"),
+            ]
+        );
+        assert_eq!(
+            find_config_files(&root, Some(&root), Some(&root)),
+            vec![root.join(".lintje.toml")]
+        );
+    }
+
+    #[test]
+    fn test_hook() {
+        assert_eq!(Lint::parse_from(["lintje"]).hook, None);
+        assert_eq!(
+            Lint::parse_from(["lintje", "--hook=pre-commit"]).hook,
+            Some(Hook::PreCommit)
+        );
+        assert!(Lint::try_parse_from(["lintje", "--hook=commit-msg"]).is_err());
+        assert!(
+            Lint::try_parse_from(["lintje", "--hook=pre-commit", "--hook-message-file=file"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_hook_commit_source() {
+        let args = Lint::parse_from(["lintje", "--hook-message-file=file"]);
+        assert_eq!(args.hook_commit_source, None);
+
+        let args = Lint::parse_from([
+            "lintje",
+            "--hook-message-file=file",
+            "--hook-commit-source=commit",
+        ]);
+        assert_eq!(args.hook_commit_source, Some(CommitSource::Commit));
+
+        // Only valid in combination with the hook message file
+        assert!(Lint::try_parse_from(["lintje", "--hook-commit-source=merge"]).is_err());
+    }
+
+    #[test]
+    fn test_cliche_phrases() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.cliche_phrases.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--cliche-phrase",
+            "Bump version",
+            "--cliche-phrase=Tweak stuff",
+        ])
+        .rule_options();
+        assert_eq!(options.cliche_phrases, vec!["Bump version", "Tweak stuff"]);
+    }
+
+    #[test]
+    fn test_cliche_words() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.cliche_words.is_empty());
+        assert!(!options.replace_cliche_words);
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--cliche-word",
+            "bump",
+            "--cliche-word=tweak",
+            "--replace-cliche-words",
+        ])
+        .rule_options();
+        assert_eq!(options.cliche_words, vec!["bump", "tweak"]);
+        assert!(options.replace_cliche_words);
+
+        let lint = lint_with_git_config(
+            &["lintje"],
+            "lintje.clicheword\nbump\0lintje.replaceclichewords\ntrue\0",
+        )
+        .unwrap();
+        assert_eq!(lint.cliche_words, vec!["bump"]);
+        assert!(lint.replace_cliche_words);
+    }
+
+    #[test]
+    fn test_mood_words() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.mood_words.is_empty());
+        assert!(!options.replace_mood_words);
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--mood-word",
+            "behoben",
+            "--mood-word=opgelost",
+            "--replace-mood-words",
+        ])
+        .rule_options();
+        assert_eq!(options.mood_words, vec!["behoben", "opgelost"]);
+        assert!(options.replace_mood_words);
+
+        let lint = lint_with_git_config(
+            &["lintje"],
+            "lintje.moodword\nbehoben\0lintje.replacemoodwords\ntrue\0",
+        )
+        .unwrap();
+        assert_eq!(lint.mood_words, vec!["behoben"]);
+        assert!(lint.replace_mood_words);
+    }
+
+    #[test]
+    fn test_allow_gitmoji() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.allow_gitmoji);
+
+        let options = Lint::parse_from(["lintje", "--allow-gitmoji"]).rule_options();
+        assert!(options.allow_gitmoji);
+
+        let options = lint_with_git_config(&["lintje"], "lintje.allowgitmoji\ntrue\0")
+            .unwrap()
+            .rule_options();
+        assert!(options.allow_gitmoji);
+    }
+
+    #[test]
+    fn test_forbidden_branch_words() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.forbidden_branch_words.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--forbidden-branch-word=master",
+            "--forbidden-branch-word=acme",
+        ])
+        .rule_options();
+        assert_eq!(options.forbidden_branch_words, vec!["master", "acme"]);
+    }
+
+    #[test]
+    fn test_branch_depth() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.branch_min_depth, None);
+        assert_eq!(options.branch_max_depth, None);
+
+        let options = Lint::parse_from(["lintje", "--branch-min-depth=2", "--branch-max-depth=3"])
+            .rule_options();
+        assert_eq!(options.branch_min_depth, Some(2));
+        assert_eq!(options.branch_max_depth, Some(3));
+    }
+
+    #[test]
+    fn test_branch_prefixes() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.branch_prefixes.is_empty());
+
+        let options =
+            Lint::parse_from(["lintje", "--branch-prefix=tom/", "--branch-prefix", "jane/"])
+                .rule_options();
+        assert_eq!(options.branch_prefixes, vec!["tom/", "jane/"]);
+
+        let options = lint_with_git_config(&["lintje"], "lintje.branchprefix\ntom/\0")
+            .unwrap()
+            .rule_options();
+        assert_eq!(options.branch_prefixes, vec!["tom/"]);
+    }
+
+    #[test]
+    fn test_require_branch_ticket() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.require_branch_ticket);
+        assert!(options.branch_ticket_patterns.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--require-branch-ticket",
+            r"--branch-ticket-pattern=^\d+-",
+        ])
+        .rule_options();
+        assert!(options.require_branch_ticket);
+        assert_eq!(options.branch_ticket_patterns[0].as_str(), r"^\d+-");
+        assert!(Lint::try_parse_from(["lintje", "--branch-ticket-pattern", "(unclosed"]).is_err());
+
+        let options = lint_with_git_config(
+            &["lintje"],
+            "lintje.requirebranchticket\ntrue\0lintje.branchticketpattern\nCASE-\\d+\0",
+        )
+        .unwrap()
+        .rule_options();
+        assert!(options.require_branch_ticket);
+        assert_eq!(options.branch_ticket_patterns[0].as_str(), r"CASE-\d+");
+        assert!(
+            lint_with_git_config(&["lintje"], "lintje.branchticketpattern\n(unclosed\0")
+                .unwrap_err()
+                .starts_with(
+                    "Invalid value for lintje.branchTicketPattern: Invalid regular expression"
+                )
+        );
+    }
+
+    #[test]
+    fn test_check_branch_case() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.check_branch_case);
+
+        let options = Lint::parse_from(["lintje", "--check-branch-case"]).rule_options();
+        assert!(options.check_branch_case);
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.checkbranchcase\ntrue\0").unwrap();
+        assert!(lint.rule_options().check_branch_case);
+    }
+
+    #[test]
+    fn test_branch_characters() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.check_branch_characters);
+        assert_eq!(options.branch_characters, "");
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--check-branch-characters",
+            "--branch-characters=.",
+            "--branch-characters",
+            "+",
+        ])
+        .rule_options();
+        assert!(options.check_branch_characters);
+        assert_eq!(options.branch_characters, ".+");
+
+        let options = lint_with_git_config(
+            &["lintje"],
+            "lintje.checkbranchcharacters\ntrue\0lintje.branchcharacters\n.\0",
+        )
+        .unwrap()
+        .rule_options();
+        assert!(options.check_branch_characters);
+        assert_eq!(options.branch_characters, ".");
+    }
+
+    #[test]
+    fn test_allow_generated_branch_names() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.allow_generated_branch_names);
+
+        let options = Lint::parse_from(["lintje", "--allow-generated-branch-names"]).rule_options();
+        assert!(options.allow_generated_branch_names);
+    }
+
+    #[test]
+    fn test_allow_fixup() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.allow_fixup);
+
+        let options = Lint::parse_from(["lintje", "--allow-fixup"]).rule_options();
+        assert!(options.allow_fixup);
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.allowfixup\ntrue\0").unwrap();
+        assert!(lint.rule_options().allow_fixup);
+    }
+
+    #[test]
+    fn test_line_length_exemptions() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.line_length_exemptions.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--line-length-exemption",
+            r"^\[\d+\]: ",
+            r"--line-length-exemption=^\s+at ",
+        ])
+        .rule_options();
+        let patterns = options
+            .line_length_exemptions
+            .iter()
+            .map(|pattern| pattern.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(patterns, vec![r"^\[\d+\]: ", r"^\s+at "]);
+        assert!(Lint::try_parse_from(["lintje", "--line-length-exemption", "(unclosed"]).is_err());
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.linelengthexemption\n^> \0").unwrap();
+        assert_eq!(lint.line_length_exemptions[0].as_str(), "^> ");
+        assert!(
+            lint_with_git_config(&["lintje"], "lintje.linelengthexemption\n(unclosed\0")
+                .unwrap_err()
+                .starts_with(
+                    "Invalid value for lintje.lineLengthExemption: Invalid regular expression"
+                )
+        );
+    }
+
+    #[test]
+    fn test_ticket_patterns() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.ticket_patterns.is_empty());
+        assert!(options.ticket_exemptions.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--ticket-pattern",
+            r"#\d+",
+            r"--ticket-pattern=\bPROJ\d+",
+            "--ticket-exemption=UTF-8",
+        ])
+        .rule_options();
+        let patterns = options
+            .ticket_patterns
+            .iter()
+            .map(|pattern| pattern.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(patterns, vec![r"#\d+", r"\bPROJ\d+"]);
+        assert_eq!(options.ticket_exemptions, vec!["UTF-8"]);
+        assert!(Lint::try_parse_from(["lintje", "--ticket-pattern", "(unclosed"]).is_err());
+
+        let lint = lint_with_git_config(
+            &["lintje"],
+            "lintje.ticketpattern\nCASE-\\d+\0lintje.ticketexemption\nISO-8601\0",
+        )
+        .unwrap();
+        assert_eq!(lint.ticket_patterns[0].as_str(), r"CASE-\d+");
+        assert_eq!(lint.ticket_exemptions, vec!["ISO-8601"]);
+        assert!(!lint.require_subject_ticket);
+        assert!(
+            lint_with_git_config(&["lintje"], "lintje.ticketpattern\n(unclosed\0")
+                .unwrap_err()
+                .starts_with("Invalid value for lintje.ticketPattern: Invalid regular expression")
+        );
+    }
+
+    #[test]
+    fn test_ticket_reference_patterns() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.ticket_reference_patterns.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--ticket-reference-pattern",
+            r"\bPROJ-\d+",
+            r"--ticket-reference-pattern=https://linear\.app/\S+",
+        ])
+        .rule_options();
+        let patterns = options
+            .ticket_reference_patterns
+            .iter()
+            .map(|pattern| pattern.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(patterns, vec![r"\bPROJ-\d+", r"https://linear\.app/\S+"]);
+        assert!(
+            Lint::try_parse_from(["lintje", "--ticket-reference-pattern", "(unclosed"]).is_err()
+        );
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.ticketreferencepattern\nPROJ-\\d+\0")
+            .unwrap();
+        assert_eq!(lint.ticket_reference_patterns[0].as_str(), r"PROJ-\d+");
+        assert!(
+            lint_with_git_config(&["lintje"], "lintje.ticketreferencepattern\n(unclosed\0")
+                .unwrap_err()
+                .starts_with(
+                    "Invalid value for lintje.ticketReferencePattern: Invalid regular expression"
+                )
+        );
+    }
+
+    #[test]
+    fn test_lint_squash_merges() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.lint_squash_merges);
+
+        let options = Lint::parse_from(["lintje", "--lint-squash-merges"]).rule_options();
+        assert!(options.lint_squash_merges);
+
+        let options = lint_with_git_config(&["lintje"], "lintje.lintsquashmerges\ntrue\0")
+            .unwrap()
+            .rule_options();
+        assert!(options.lint_squash_merges);
+    }
+
+    #[test]
+    fn test_require_subject_ticket() {
+        let options = Lint::parse_from(["lintje", "--require-subject-ticket"]).rule_options();
+        assert!(options.require_subject_ticket);
+
+        let options = lint_with_git_config(&["lintje"], "lintje.requiresubjectticket\ntrue\0")
+            .unwrap()
+            .rule_options();
+        assert!(options.require_subject_ticket);
+    }
+
+    #[test]
+    fn test_max_file_size() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.max_file_size, None);
+
+        let options = Lint::parse_from(["lintje", "--max-file-size", "5000"]).rule_options();
+        assert_eq!(options.max_file_size, Some(5000));
+        assert!(Lint::try_parse_from(["lintje", "--max-file-size", "5 MB"]).is_err());
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.maxfilesize\n2048\0").unwrap();
+        assert_eq!(lint.max_file_size, Some(2048));
+    }
+
+    #[test]
+    fn test_max_files_changed() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.max_files_changed, None);
+
+        let options = Lint::parse_from(["lintje", "--max-files-changed", "20"]).rule_options();
+        assert_eq!(options.max_files_changed, Some(20));
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.maxfileschanged\n10\0").unwrap();
+        assert_eq!(lint.max_files_changed, Some(10));
+    }
+
+    #[test]
+    fn test_max_directories() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.max_directories, None);
+
+        let options = Lint::parse_from(["lintje", "--max-directories", "3"]).rule_options();
+        assert_eq!(options.max_directories, Some(3));
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.maxdirectories\n2\0").unwrap();
+        assert_eq!(lint.max_directories, Some(2));
+    }
+
+    #[test]
+    fn test_lockfiles() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.lockfiles.is_empty());
+
+        let options =
+            Lint::parse_from(["lintje", "--lockfile", "uv.lock = pyproject.toml"]).rule_options();
+        assert_eq!(
+            options.lockfiles,
+            vec![Lockfile {
+                lockfile: "uv.lock".to_string(),
+                manifest: "pyproject.toml".to_string()
+            }]
+        );
+        assert!(Lint::try_parse_from(["lintje", "--lockfile", "uv.lock"]).is_err());
+        assert!(Lint::try_parse_from(["lintje", "--lockfile", "uv.lock="]).is_err());
+
+        let lint =
+            lint_with_git_config(&["lintje"], "lintje.lockfile\nmix.lock=mix.exs\0").unwrap();
+        assert_eq!(lint.lockfiles[0].manifest, "mix.exs");
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.lockfile\nmix.lock\0").unwrap_err(),
+            "Invalid value for lintje.lockfile: Invalid lockfile, expected the \
+            `lockfile=manifest` format: mix.lock"
+        );
+    }
+
+    #[test]
+    fn test_forbidden_paths() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.forbidden_paths.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--forbidden-path",
+            ".env",
+            "--forbidden-path=*.pem",
+        ])
+        .rule_options();
+        let patterns = options
+            .forbidden_paths
+            .iter()
+            .map(|pattern| pattern.pattern.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(patterns, vec![".env", "*.pem"]);
+        assert!(Lint::try_parse_from(["lintje", "--forbidden-path", "/"]).is_err());
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.forbiddenpath\nsecrets/\0").unwrap();
+        assert_eq!(lint.forbidden_paths[0].pattern, "secrets/");
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.forbiddenpath\n \0").unwrap_err(),
+            "Invalid value for lintje.forbiddenPath: Invalid path pattern:  "
+        );
+    }
+
+    #[test]
+    fn test_disabled_rules() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.disabled_rules.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--disable-rule",
+            "SubjectTicketNumber",
+            "--disable-rule=BranchNameLength",
+        ])
+        .rule_options();
+        assert_eq!(
+            options.disabled_rules,
+            vec![Rule::SubjectTicketNumber, Rule::BranchNameLength]
+        );
+        assert!(Lint::try_parse_from(["lintje", "--disable-rule", "Unknown"]).is_err());
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.disablerule\nSubjectMood\0").unwrap();
+        assert_eq!(lint.disabled_rules, vec![Rule::SubjectMood]);
+        let lint = lint_with_git_config(
+            &["lintje", "--disable-rule=SubjectLength"],
+            "lintje.disablerule\nSubjectMood\0",
+        )
+        .unwrap();
+        assert_eq!(lint.disabled_rules, vec![Rule::SubjectLength]);
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.disablerule\nUnknown\0").unwrap_err(),
+            "Invalid value for lintje.disableRule: Unknown rule: Unknown"
+        );
+    }
+
+    #[test]
+    fn test_hidden_hint_rules() {
+        let lint = Lint::parse_from(["lintje"]);
+        assert!(lint.hidden_hint_rules.is_empty());
+
+        let lint = Lint::parse_from([
+            "lintje",
+            "--hide-hint",
+            "MessageTicketNumber",
+            "--hide-hint=BranchNameGenerated",
+        ]);
+        assert_eq!(
+            lint.hidden_hint_rules,
+            vec![Rule::MessageTicketNumber, Rule::BranchNameGenerated]
+        );
+        assert!(Lint::try_parse_from(["lintje", "--hide-hint", "Unknown"]).is_err());
+
+        let lint =
+            lint_with_git_config(&["lintje"], "lintje.hidehint\nMessageTicketNumber\0").unwrap();
+        assert_eq!(lint.hidden_hint_rules, vec![Rule::MessageTicketNumber]);
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.hidehint\nUnknown\0").unwrap_err(),
+            "Invalid value for lintje.hideHint: Unknown rule: Unknown"
+        );
+    }
+
+    #[test]
+    fn test_forbidden_phrases() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.forbidden_phrases.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--forbidden-phrase",
+            "DO NOT MERGE",
+            "--forbidden-phrase",
+            "Falcon = Use the product name",
+            "--forbidden-phrase",
+            "WIP=",
+        ])
+        .rule_options();
+        assert_eq!(
+            options.forbidden_phrases,
+            vec![
+                ForbiddenPhrase::parse("DO NOT MERGE"),
+                ForbiddenPhrase::parse("Falcon=Use the product name"),
+                ForbiddenPhrase::parse("WIP"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subject_ascii() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.check_subject_ascii);
+        assert_eq!(options.subject_characters, "");
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--check-subject-ascii",
+            "--subject-characters=éü",
+            "--subject-characters",
+            "—",
+        ])
+        .rule_options();
+        assert!(options.check_subject_ascii);
+        assert_eq!(options.subject_characters, "éü—");
+
+        let options = lint_with_git_config(
+            &["lintje"],
+            "lintje.checksubjectascii\ntrue\0lintje.subjectcharacters\né\0",
+        )
+        .unwrap()
+        .rule_options();
+        assert!(options.check_subject_ascii);
+        assert_eq!(options.subject_characters, "é");
+    }
+
+    #[test]
+    fn test_subject_min_words() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.subject_min_words, None);
+
+        let options = Lint::parse_from(["lintje", "--subject-min-words"]).rule_options();
+        assert_eq!(options.subject_min_words, Some(2));
+        let options = Lint::parse_from(["lintje", "--subject-min-words=3"]).rule_options();
+        assert_eq!(options.subject_min_words, Some(3));
+        assert!(Lint::try_parse_from(["lintje", "--subject-min-words=many"]).is_err());
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.subjectminwords\n4\0").unwrap();
+        assert_eq!(lint.subject_min_words, Some(4));
+        assert_eq!(
+            lint_with_git_config(&["lintje"], "lintje.subjectminwords\nmany\0").unwrap_err(),
+            "Invalid number value for lintje.subjectMinWords: many"
+        );
+    }
+
+    #[test]
+    fn test_message_min_paragraphs() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert_eq!(options.message_min_paragraphs, None);
+
+        let options = Lint::parse_from(["lintje", "--message-min-paragraphs"]).rule_options();
+        assert_eq!(options.message_min_paragraphs, Some(2));
+        let options = Lint::parse_from(["lintje", "--message-min-paragraphs=3"]).rule_options();
+        assert_eq!(options.message_min_paragraphs, Some(3));
+
+        let lint = lint_with_git_config(&["lintje"], "lintje.messageminparagraphs\n3\0").unwrap();
+        assert_eq!(lint.message_min_paragraphs, Some(3));
+    }
+
+    #[test]
+    fn test_spelling() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(!options.check_spelling);
+        assert!(options.spelling_words.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--check-spelling",
+            "--spelling-word",
+            "Lintje",
+            "--spelling-word",
+            "wrok=work",
+        ])
+        .rule_options();
+        assert!(options.check_spelling);
+        assert_eq!(
+            options.spelling_words,
+            vec![
+                SpellingWord::Allowed("lintje".to_string()),
+                SpellingWord::Misspelling("wrok".to_string(), "work".to_string()),
+            ]
+        );
+
+        let options = lint_with_git_config(
+            &["lintje"],
+            "lintje.checkspelling\ntrue\0lintje.spellingword\nteh\0",
+        )
+        .unwrap()
+        .rule_options();
+        assert!(options.check_spelling);
+        assert_eq!(
+            options.spelling_words,
+            vec![SpellingWord::Allowed("teh".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_required_trailers() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.required_trailers.is_empty());
+
+        let options = Lint::parse_from([
+            "lintje",
+            "--require-trailer",
+            "Signed-off-by",
+            "--require-trailer",
+            "Reviewed-by",
+        ])
+        .rule_options();
+        assert_eq!(
+            options.required_trailers,
+            vec!["Signed-off-by".to_string(), "Reviewed-by".to_string()]
+        );
+
+        let options = lint_with_git_config(&["lintje"], "lintje.requiretrailer\nSigned-off-by\0")
+            .unwrap()
+            .rule_options();
+        assert_eq!(options.required_trailers, vec!["Signed-off-by".to_string()]);
+    }
+
+    #[test]
+    fn test_require_dco() {
+        assert!(!Lint::parse_from(["lintje"]).rule_options().require_dco);
+        assert!(
+            Lint::parse_from(["lintje", "--require-dco"])
+                .rule_options()
+                .require_dco
+        );
+        let options = lint_with_git_config(&["lintje"], "lintje.requiredco\ntrue\0")
+            .unwrap()
+            .rule_options();
+        assert!(options.require_dco);
+    }
+
+    #[test]
+    fn test_require_signature() {
+        assert!(
+            !Lint::parse_from(["lintje"])
+                .rule_options()
+                .require_signature
+        );
+        assert!(
+            Lint::parse_from(["lintje", "--require-signature"])
+                .rule_options()
+                .require_signature
+        );
+        let options = lint_with_git_config(&["lintje"], "lintje.requiresignature\ntrue\0")
+            .unwrap()
+            .rule_options();
+        assert!(options.require_signature);
+    }
+
+    #[test]
+    fn test_protected_branches() {
+        let options = Lint::parse_from(["lintje"]).rule_options();
+        assert!(options.protected_branches.is_empty());
+
+        let options = Lint::parse_from(["lintje", "--check-protected-branch"]).rule_options();
+        assert_eq!(
+            options.protected_branches,
+            vec!["main", "master", "develop"]
+        );
+
+        let options = Lint::parse_from(["lintje", "--protected-branch", "trunk"]).rule_options();
+        assert_eq!(options.protected_branches, vec!["trunk"]);
+
+        let options = lint_with_git_config(
+            &["lintje"],
+            "lintje.checkprotectedbranch\ntrue\0lintje.protectedbranch\nrelease\0",
+        )
+        .unwrap()
+        .rule_options();
+        assert_eq!(options.protected_branches, vec!["release"]);
     }
 }
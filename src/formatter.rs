@@ -1,10 +1,12 @@
+use serde_json::{json, Value};
 use std::io;
 use termcolor::{Color, ColorSpec, WriteColor};
 
 use crate::branch::Branch;
-use crate::commit::Commit;
-use crate::issue::{ContextType, Issue, IssueType, Position};
-use crate::utils::display_width;
+use crate::commit::{mask_secrets, Commit};
+use crate::issue::{Context, ContextType, Issue, IssueType, Position};
+use crate::rule::{Rule, RULES};
+use crate::utils::{display_width, pluralize};
 
 pub fn red_color() -> ColorSpec {
     let mut cs = ColorSpec::new();
@@ -84,13 +86,119 @@ pub fn formatted_commit_issue(
     formatted_position(out, &issue.position)?;
     write!(out, ":")?;
     out.reset()?;
-    write!(out, " {}", commit.subject)?;
+    write!(out, " {}", mask_secrets(&commit.subject))?;
     writeln!(out)?;
     formatted_context(out, issue)?;
 
     Ok(())
 }
 
+/// Print the header line of the commit's issues, when the issues are grouped by commit. The issues
+/// are printed indented beneath it with an [`IndentedWriter`].
+pub fn formatted_commit_header(
+    out: &mut impl WriteColor,
+    commit: &Commit,
+    issue_count: usize,
+) -> io::Result<()> {
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    write!(
+        out,
+        "commit {}: {}",
+        commit.short_sha.as_deref().unwrap_or("0000000"),
+        mask_secrets(&commit.subject)
+    )?;
+    out.reset()?;
+    writeln!(
+        out,
+        " — {} {}",
+        issue_count,
+        pluralize("issue", issue_count)
+    )
+}
+
+/// Print the header line of the branch's issues, when the issues are grouped by commit.
+pub fn formatted_branch_header(
+    out: &mut impl WriteColor,
+    branch: &Branch,
+    issue_count: usize,
+) -> io::Result<()> {
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    write!(out, "branch {}", branch.name)?;
+    out.reset()?;
+    writeln!(
+        out,
+        " — {} {}",
+        issue_count,
+        pluralize("issue", issue_count)
+    )
+}
+
+/// Print the header line of the rule's issues, when the issues are grouped by rule.
+pub fn formatted_rule_header(
+    out: &mut impl WriteColor,
+    rule: Rule,
+    issue_count: usize,
+) -> io::Result<()> {
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    write!(out, "rule {}", rule)?;
+    out.reset()?;
+    writeln!(
+        out,
+        " — {} {}",
+        issue_count,
+        pluralize("issue", issue_count)
+    )
+}
+
+/// Writer that indents every line written to it, to print issues beneath a group header. Empty
+/// lines are not indented.
+pub struct IndentedWriter<'a, W> {
+    out: &'a mut W,
+    indent: usize,
+    line_start: bool,
+}
+
+impl<'a, W: WriteColor> IndentedWriter<'a, W> {
+    pub fn new(out: &'a mut W, indent: usize) -> Self {
+        Self {
+            out,
+            indent,
+            line_start: true,
+        }
+    }
+}
+
+impl<W: WriteColor> io::Write for IndentedWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|byte| *byte == b'\n') {
+            if self.line_start && line != b"\n" {
+                write!(self.out, "{:indent$}", "", indent = self.indent)?;
+            }
+            self.out.write_all(line)?;
+            self.line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+impl<W: WriteColor> WriteColor for IndentedWriter<'_, W> {
+    fn supports_color(&self) -> bool {
+        self.out.supports_color()
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> io::Result<()> {
+        self.out.set_color(spec)
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.out.reset()
+    }
+}
+
 pub fn formatted_branch_issue(
     out: &mut impl WriteColor,
     branch: &Branch,
@@ -111,6 +219,388 @@ pub fn formatted_branch_issue(
     Ok(())
 }
 
+/// Print the commit issue on one line, like `1234567:1:10 Error SubjectLength The message`, for
+/// tools that process the output line by line, like quickfix lists of editors.
+pub fn compact_commit_issue(
+    out: &mut impl WriteColor,
+    commit: &Commit,
+    issue: &Issue,
+) -> io::Result<()> {
+    let sha = match &commit.short_sha {
+        Some(sha) => sha,
+        None => "0000000",
+    };
+    write!(out, "{}", sha)?;
+    formatted_position(out, &issue.position)?;
+    writeln!(out, " {} {} {}", issue.r#type, issue.rule, issue.message)
+}
+
+/// Print the branch issue on one line, like `Branch:1 Error BranchNameLength The message`.
+pub fn compact_branch_issue(out: &mut impl WriteColor, issue: &Issue) -> io::Result<()> {
+    write!(out, "Branch")?;
+    formatted_position(out, &issue.position)?;
+    writeln!(out, " {} {} {}", issue.r#type, issue.rule, issue.message)
+}
+
+/// Describe the commit issue in full sentences, without gutters and underlines.
+pub fn plain_commit_issue(
+    out: &mut impl WriteColor,
+    commit: &Commit,
+    issue: &Issue,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "{} {}: {}",
+        issue.r#type,
+        issue.rule,
+        sentence(&issue.message)
+    )?;
+    let sha = match &commit.short_sha {
+        Some(sha) => sha,
+        None => "0000000",
+    };
+    write!(
+        out,
+        "  In commit {} \"{}\"",
+        sha,
+        mask_secrets(&commit.subject)
+    )?;
+    match issue.position {
+        Position::Subject { line, column } | Position::MessageLine { line, column } => {
+            writeln!(out, ", on line {}, column {}.", line, column)?;
+        }
+        Position::Branch { column } => writeln!(out, ", on column {}.", column)?,
+        Position::Diff => writeln!(out, ", in the changes.")?,
+    }
+    plain_context(out, issue)
+}
+
+/// Describe the branch issue in full sentences, without gutters and underlines.
+pub fn plain_branch_issue(
+    out: &mut impl WriteColor,
+    branch: &Branch,
+    issue: &Issue,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "{} {}: {}",
+        issue.r#type,
+        issue.rule,
+        sentence(&issue.message)
+    )?;
+    write!(out, "  In branch \"{}\"", branch.name)?;
+    match issue.position {
+        Position::Branch { column } => writeln!(out, ", on column {}.", column)?,
+        _ => writeln!(out, ".")?,
+    }
+    plain_context(out, issue)
+}
+
+fn plain_context(out: &mut impl WriteColor, issue: &Issue) -> io::Result<()> {
+    for context in &issue.context {
+        let message = match &context.message {
+            Some(message) => message,
+            None => continue,
+        };
+        let label = match context.r#type {
+            ContextType::Addition => "Suggestion",
+            ContextType::Error | ContextType::Plain => "Problem",
+        };
+        write!(out, "  {} on ", label)?;
+        if let Some(line) = context.line {
+            write!(out, "line {}", line)?;
+            if context.range.is_some() {
+                write!(out, ", ")?;
+            }
+        }
+        if let Some((start, end)) = context_columns(context) {
+            let text = context_text(context);
+            if start == end {
+                write!(out, "column {}", start)?;
+            } else {
+                write!(out, "columns {} to {}", start, end)?;
+            }
+            if !text.is_empty() && context.r#type != ContextType::Addition {
+                write!(out, " (\"{}\")", text)?;
+            }
+        }
+        writeln!(out, ": {}", sentence(message))?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Returns the first and last column of the context's range, counted in characters from 1.
+/// Whitespace around the highlighted text is not included.
+fn context_columns(context: &Context) -> Option<(usize, usize)> {
+    let range = context.range.as_ref()?;
+    let start = context
+        .content
+        .get(..range.start)
+        .map_or(range.start, |v| v.chars().count())
+        + 1;
+    let end = start + context_text(context).chars().count().max(1) - 1;
+    Some((start, end))
+}
+
+/// Returns the text highlighted by the context's range.
+fn context_text(context: &Context) -> &str {
+    context
+        .range
+        .as_ref()
+        .and_then(|range| context.content.get(range.clone()))
+        .unwrap_or("")
+        .trim()
+}
+
+/// Describe the commit issue as a JSON object, for the `json` output format.
+pub fn json_commit_issue(commit: &Commit, issue: &Issue) -> Value {
+    let mut value = json_issue(issue);
+    value["commit"] = json!({
+        "sha": commit.long_sha,
+        "short_sha": commit.short_sha,
+        "subject": mask_secrets(&commit.subject),
+    });
+    value
+}
+
+/// Describe the branch issue as a JSON object, for the `json` output format.
+pub fn json_branch_issue(branch: &Branch, issue: &Issue) -> Value {
+    let mut value = json_issue(issue);
+    value["branch"] = json!({ "name": branch.name });
+    value
+}
+
+fn json_issue(issue: &Issue) -> Value {
+    let issue_type = match issue.r#type {
+        IssueType::Error => "error",
+        IssueType::Hint => "hint",
+    };
+    let position = json_position(&issue.position);
+    let context = issue
+        .context
+        .iter()
+        .map(|context| {
+            let context_type = match context.r#type {
+                ContextType::Plain => "plain",
+                ContextType::Error => "error",
+                ContextType::Addition => "addition",
+            };
+            let columns =
+                context_columns(context).map(|(start, end)| json!({ "start": start, "end": end }));
+            json!({
+                "type": context_type,
+                "line": context.line,
+                "content": context.content,
+                "columns": columns,
+                "message": context.message,
+            })
+        })
+        .collect::<Vec<Value>>();
+    json!({
+        "type": issue_type,
+        "rule": issue.rule.to_string(),
+        "message": issue.message,
+        "position": position,
+        "context": context,
+    })
+}
+
+/// Describe the commit issue as a SARIF result, for the `sarif` output format. The result is
+/// located in the commit message file, `.git/COMMIT_EDITMSG`, at the line and column of the
+/// issue's position, and in the commit, identified by its SHA.
+pub fn sarif_commit_result(commit: &Commit, issue: &Issue) -> Value {
+    let sha = commit.long_sha.as_deref().unwrap_or("0000000");
+    let short_sha = commit.short_sha.as_deref().unwrap_or(sha);
+    let location = json!({
+        "physicalLocation": sarif_physical_location(".git/COMMIT_EDITMSG", &issue.position),
+        "logicalLocations": [{ "name": short_sha, "fullyQualifiedName": sha, "kind": "commit" }],
+        "message": { "text": format!("Commit {}: {}", short_sha, mask_secrets(&commit.subject)) },
+    });
+    sarif_result(issue, &location)
+}
+
+/// Describe the branch issue as a SARIF result, for the `sarif` output format. The result is
+/// located on the first line of `.git/HEAD`, the file that names the checked out branch, and in
+/// the branch, identified by its ref name.
+pub fn sarif_branch_result(branch: &Branch, issue: &Issue) -> Value {
+    let location = json!({
+        "physicalLocation": sarif_physical_location(".git/HEAD", &issue.position),
+        "logicalLocations": [{
+            "name": branch.name,
+            "fullyQualifiedName": format!("refs/heads/{}", branch.name),
+            "kind": "branch",
+        }],
+        "message": { "text": format!("Branch: {}", branch.name) },
+    });
+    sarif_result(issue, &location)
+}
+
+fn json_position(position: &Position) -> Value {
+    match *position {
+        Position::Subject { line, column } => {
+            json!({ "type": "subject", "line": line, "column": column })
+        }
+        Position::MessageLine { line, column } => {
+            json!({ "type": "message", "line": line, "column": column })
+        }
+        Position::Diff => json!({ "type": "diff" }),
+        Position::Branch { column } => json!({ "type": "branch", "column": column }),
+    }
+}
+
+/// SARIF requires the lines and columns of a region to start at 1. Diff issues have no line and
+/// are located on the first line of the file.
+fn sarif_physical_location(uri: &str, position: &Position) -> Value {
+    let region = match *position {
+        Position::Subject { line, column } | Position::MessageLine { line, column } => {
+            json!({ "startLine": line.max(1), "startColumn": column.max(1) })
+        }
+        Position::Diff => json!({ "startLine": 1 }),
+        Position::Branch { column } => json!({ "startLine": 1, "startColumn": column.max(1) }),
+    };
+    json!({ "artifactLocation": { "uri": uri }, "region": region })
+}
+
+fn sarif_result(issue: &Issue, location: &Value) -> Value {
+    let level = match issue.r#type {
+        IssueType::Error => "error",
+        IssueType::Hint => "note",
+    };
+    json!({
+        "ruleId": issue.rule.to_string(),
+        "ruleIndex": RULES.iter().position(|rule| *rule == issue.rule),
+        "level": level,
+        "message": { "text": issue.message },
+        "locations": [location],
+        "properties": { "position": json_position(&issue.position) },
+    })
+}
+
+/// Returns the SARIF log of one Lintje run, with a rule descriptor for every rule. Errors that
+/// prevented Lintje from validating the commits or branch are reported as tool execution
+/// notifications.
+pub fn sarif_document(results: &[Value], errors: &[String]) -> Value {
+    let rules = RULES
+        .iter()
+        .map(|rule| {
+            let metadata = rule.metadata();
+            json!({
+                "id": rule.to_string(),
+                "shortDescription": { "text": metadata.description },
+                "fullDescription": { "text": metadata.rationale },
+                "help": { "text": format!("{}\n\n{}", metadata.description, metadata.rationale) },
+                "helpUri": rule.doc_url(),
+                "properties": { "category": metadata.category.title() },
+            })
+        })
+        .collect::<Vec<Value>>();
+    let notifications = errors
+        .iter()
+        .map(|error| json!({ "level": "error", "message": { "text": error } }))
+        .collect::<Vec<Value>>();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Lintje",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://lintje.dev",
+                    "rules": rules,
+                },
+            },
+            "invocations": [{
+                "executionSuccessful": errors.is_empty(),
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": results,
+        }],
+    })
+}
+
+/// Ends the message with a period, unless it already ends with punctuation.
+fn sentence(message: &str) -> String {
+    if message.ends_with(&['.', '?', '!'][..]) {
+        message.to_string()
+    } else {
+        format!("{}.", message)
+    }
+}
+
+/// Print the documentation of the rules, grouped by rule category.
+pub fn formatted_rule_docs(out: &mut impl WriteColor, rules: &[Rule]) -> io::Result<()> {
+    let mut category = None;
+    for (index, rule) in rules.iter().enumerate() {
+        if index > 0 {
+            writeln!(out)?;
+        }
+        let rule_category = rule.metadata().category;
+        if rules.len() > 1 && category != Some(rule_category) {
+            category = Some(rule_category);
+            let title = format!("{} rules", rule_category.title());
+            out.set_color(ColorSpec::new().set_bold(true).set_underline(true))?;
+            write!(out, "{}", title)?;
+            out.reset()?;
+            writeln!(out, "\n")?;
+        }
+        formatted_rule_explanation(out, *rule)?;
+    }
+    Ok(())
+}
+
+pub fn formatted_rule_explanation(out: &mut impl WriteColor, rule: Rule) -> io::Result<()> {
+    let metadata = rule.metadata();
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    write!(out, "{}", rule)?;
+    out.reset()?;
+    writeln!(out, ": {}", metadata.description)?;
+    writeln!(out, "\n{}", metadata.rationale)?;
+    formatted_rule_examples(
+        out,
+        "Valid examples",
+        metadata.valid_examples,
+        &green_color(),
+    )?;
+    formatted_rule_examples(
+        out,
+        "Invalid examples",
+        metadata.invalid_examples,
+        &red_color(),
+    )?;
+    if metadata.auto_fixable {
+        writeln!(out, "\nIssues of this rule can be fixed automatically.")?;
+    }
+    out.set_color(&muted_color())?;
+    write!(out, "\nDocumentation: {}", rule.doc_url())?;
+    out.reset()?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn formatted_rule_examples(
+    out: &mut impl WriteColor,
+    label: &str,
+    examples: &[&str],
+    color: &ColorSpec,
+) -> io::Result<()> {
+    writeln!(out, "\n{}:", label)?;
+    for example in examples {
+        for (index, line) in example.lines().enumerate() {
+            if !line.is_empty() {
+                let prefix = if index == 0 { "-" } else { " " };
+                write!(out, "{} ", prefix)?;
+                out.set_color(color)?;
+                write!(out, "{}", line)?;
+                out.reset()?;
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn formatted_context(out: &mut impl WriteColor, issue: &Issue) -> io::Result<()> {
     let mut first_line = true;
     let mut last_line_number = None;
@@ -210,13 +700,20 @@ pub fn formatted_context(out: &mut impl WriteColor, issue: &Issue) -> io::Result
 
 #[cfg(test)]
 pub mod tests {
-    use super::{formatted_branch_issue, formatted_commit_issue};
+    use super::{
+        compact_branch_issue, compact_commit_issue, formatted_branch_header,
+        formatted_branch_issue, formatted_commit_header, formatted_commit_issue,
+        formatted_rule_header, json_branch_issue, json_commit_issue, sarif_branch_result,
+        sarif_commit_result, sarif_document, IndentedWriter,
+    };
     use crate::branch::Branch;
     use crate::commit::Commit;
     use crate::issue::{Context, Issue, Position};
-    use crate::rule::Rule;
+    use crate::rule::{Rule, RULES};
     use crate::utils::test::formatted_context;
     use core::ops::Range;
+    use serde_json::json;
+    use std::io::Write;
     use termcolor::{BufferWriter, ColorChoice};
 
     fn commit<S: AsRef<str>>(sha: Option<String>, subject: S, message: S) -> Commit {
@@ -518,6 +1015,37 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_formatted_headers() {
+        let bufwtr = BufferWriter::stdout(ColorChoice::Never);
+        let mut out = bufwtr.buffer();
+        let commit = commit(Some("1234567890".to_string()), "Subject", "Message");
+        formatted_commit_header(&mut out, &commit, 3).unwrap();
+        let branch = Branch::new("branch-name".to_string());
+        formatted_branch_header(&mut out, &branch, 1).unwrap();
+        formatted_rule_header(&mut out, Rule::SubjectMood, 2).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(out.as_slice()),
+            "commit 1234567: Subject — 3 issues\n\
+            branch branch-name — 1 issue\n\
+            rule SubjectMood — 2 issues\n"
+        );
+    }
+
+    #[test]
+    fn test_indented_writer() {
+        let bufwtr = BufferWriter::stdout(ColorChoice::Never);
+        let mut out = bufwtr.buffer();
+        let mut indented = IndentedWriter::new(&mut out, 2);
+        write!(indented, "Error: ").unwrap();
+        writeln!(indented, "Message\n\nContext").unwrap();
+        writeln!(indented, "More context").unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(out.as_slice()),
+            "  Error: Message\n\n  Context\n  More context\n"
+        );
+    }
+
     #[test]
     fn test_formatted_branch_issue_branch_error() {
         let branch = Branch::new("branch-name".to_string());
@@ -816,4 +1344,211 @@ pub mod tests {
              \x20\x20|                                                   ^^ Mark double width character\n"
         );
     }
+
+    #[test]
+    fn test_compact_issues() {
+        let bufwtr = BufferWriter::stdout(ColorChoice::Always);
+        let mut out = bufwtr.buffer();
+        let commit = commit(Some("1234567890".to_string()), "Fix JIRA-123", "Message");
+        let issue = Issue::error(
+            Rule::SubjectTicketNumber,
+            "The subject contains a ticket number".to_string(),
+            Position::Subject { line: 1, column: 5 },
+            vec![],
+        );
+        compact_commit_issue(&mut out, &commit, &issue).unwrap();
+        let issue = Issue::hint(
+            Rule::DiffPresence,
+            "No file changes found".to_string(),
+            Position::Diff,
+            vec![],
+        );
+        compact_commit_issue(&mut out, &commit, &issue).unwrap();
+        let issue = Issue::error(
+            Rule::BranchNameLength,
+            "Branch name of 3 characters is too short".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        );
+        compact_branch_issue(&mut out, &issue).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(out.as_slice()),
+            "1234567:1:5 Error SubjectTicketNumber The subject contains a ticket number\n\
+            1234567 Hint DiffPresence No file changes found\n\
+            Branch:1 Error BranchNameLength Branch name of 3 characters is too short\n"
+        );
+    }
+
+    #[test]
+    fn test_json_commit_issue() {
+        let commit = commit(Some("1234567890".to_string()), "Subject ✨ Fix", "Message");
+        let issue = Issue::hint(
+            Rule::SubjectLength,
+            "The hint message".to_string(),
+            Position::Subject { line: 1, column: 9 },
+            vec![Context::subject_hint(
+                "Subject ✨ Fix".to_string(),
+                Range { start: 8, end: 15 },
+                "The hint".to_string(),
+            )],
+        );
+        assert_eq!(
+            json_commit_issue(&commit, &issue),
+            json!({
+                "type": "hint",
+                "rule": "SubjectLength",
+                "message": "The hint message",
+                "position": { "type": "subject", "line": 1, "column": 9 },
+                "context": [{
+                    "type": "addition",
+                    "line": 1,
+                    "content": "Subject ✨ Fix",
+                    "columns": { "start": 9, "end": 13 },
+                    "message": "The hint",
+                }],
+                "commit": {
+                    "sha": "1234567890",
+                    "short_sha": "1234567",
+                    "subject": "Subject ✨ Fix",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_branch_issue() {
+        let branch = Branch::new("fix-123".to_string());
+        let issue = Issue::error(
+            Rule::BranchNameTicketNumber,
+            "The error message".to_string(),
+            Position::Branch { column: 1 },
+            vec![Context::branch_error(
+                "fix-123".to_string(),
+                Range { start: 0, end: 3 },
+                "The error".to_string(),
+            )],
+        );
+        assert_eq!(
+            json_branch_issue(&branch, &issue),
+            json!({
+                "type": "error",
+                "rule": "BranchNameTicketNumber",
+                "message": "The error message",
+                "position": { "type": "branch", "column": 1 },
+                "context": [{
+                    "type": "error",
+                    "line": null,
+                    "content": "fix-123",
+                    "columns": { "start": 1, "end": 3 },
+                    "message": "The error",
+                }],
+                "branch": { "name": "fix-123" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_sarif_commit_result() {
+        let commit = commit(Some("1234567890".to_string()), "Subject", "Message");
+        let issue = Issue::error(
+            Rule::MessageLineLength,
+            "The error message".to_string(),
+            Position::MessageLine {
+                line: 3,
+                column: 73,
+            },
+            vec![],
+        );
+        assert_eq!(
+            sarif_commit_result(&commit, &issue),
+            json!({
+                "ruleId": "MessageLineLength",
+                "ruleIndex": RULES.iter().position(|rule| *rule == Rule::MessageLineLength),
+                "level": "error",
+                "message": { "text": "The error message" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": ".git/COMMIT_EDITMSG" },
+                        "region": { "startLine": 3, "startColumn": 73 },
+                    },
+                    "logicalLocations": [{
+                        "name": "1234567",
+                        "fullyQualifiedName": "1234567890",
+                        "kind": "commit",
+                    }],
+                    "message": { "text": "Commit 1234567: Subject" },
+                }],
+                "properties": {
+                    "position": { "type": "message", "line": 3, "column": 73 },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_sarif_branch_result() {
+        let branch = Branch::new("fix-123".to_string());
+        let issue = Issue::hint(
+            Rule::BranchNameGenerated,
+            "The hint message".to_string(),
+            Position::Branch { column: 1 },
+            vec![],
+        );
+        let result = sarif_branch_result(&branch, &issue);
+        assert_eq!(result["level"], "note");
+        assert_eq!(
+            result["locations"][0],
+            json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": ".git/HEAD" },
+                    "region": { "startLine": 1, "startColumn": 1 },
+                },
+                "logicalLocations": [{
+                    "name": "fix-123",
+                    "fullyQualifiedName": "refs/heads/fix-123",
+                    "kind": "branch",
+                }],
+                "message": { "text": "Branch: fix-123" },
+            })
+        );
+        assert_eq!(
+            result["properties"]["position"],
+            json!({ "type": "branch", "column": 1 })
+        );
+    }
+
+    #[test]
+    fn test_sarif_document() {
+        let document = sarif_document(&[], &["Something went wrong".to_string()]);
+        assert_eq!(document["version"], "2.1.0");
+        let run = &document["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), crate::rule::RULES.len());
+        assert_eq!(rules[0]["id"], crate::rule::RULES[0].to_string());
+        assert_eq!(
+            rules[0]["helpUri"],
+            crate::rule::RULES[0].doc_url().as_str()
+        );
+        assert_eq!(run["invocations"][0]["executionSuccessful"], false);
+        assert_eq!(
+            run["invocations"][0]["toolExecutionNotifications"][0]["message"]["text"],
+            "Something went wrong"
+        );
+        assert_eq!(run["results"], json!([]));
+    }
+
+    #[test]
+    fn test_sarif_diff_result_region() {
+        let commit = commit(Some("1234567890".to_string()), "Subject", "Message");
+        let issue = Issue::error(
+            Rule::DiffPresence,
+            "The error message".to_string(),
+            Position::Diff,
+            vec![],
+        );
+        assert_eq!(
+            sarif_commit_result(&commit, &issue)["locations"][0]["physicalLocation"]["region"],
+            json!({ "startLine": 1 })
+        );
+    }
 }
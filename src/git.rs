@@ -1,15 +1,32 @@
 use regex::Regex;
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::branch::Branch;
-use crate::command::run_command;
-use crate::commit::{Commit, SUBJECT_WITH_MERGE_REMOTE_BRANCH};
+use crate::command::{run_command, run_command_streaming, run_command_with_input};
+use crate::commit::{
+    validate_commit_range, validate_commits, ChangedFile, Commit, SignatureStatus,
+    SUBJECT_WITH_MERGE_REMOTE_BRANCH,
+};
+use crate::options::{FetchOptions, RuleOptions};
+use crate::rule::Rule;
+use crate::stats;
 
 const SCISSORS: &str = "------------------------ >8 ------------------------";
 const COMMIT_DELIMITER: &str = "------------------------ COMMIT >! ------------------------";
 const COMMIT_BODY_DELIMITER: &str = "------------------------ BODY >! ------------------------";
+// The characters Git picks the comment character from with the `core.commentChar=auto` config.
+const AUTO_COMMENT_CHARS: [char; 10] = ['#', ';', '@', '!', '$', '%', '^', '&', '|', ':'];
+// The notices Git adds below the scissors line. Older Git versions use the second notice.
+const SCISSORS_NOTICES: [&str; 2] = [
+    "Do not modify or remove the line above.",
+    "Do not touch the line above.",
+];
 
 lazy_static! {
-    static ref SUBJECT_WITH_SQUASH_PR: Regex = Regex::new(r".+ \(#\d+\)$").unwrap();
     static ref MESSAGE_CONTAINS_MERGE_REQUEST_REFERENCE: Regex =
         Regex::new(r"^See merge request .+/.+!\d+$").unwrap();
 }
@@ -23,78 +40,665 @@ pub enum CleanupMode {
     Default,
 }
 
-pub fn fetch_and_parse_branch() -> Result<Branch, String> {
+/// The source of the commit message as passed to the prepare-commit-msg hook by Git.
+#[cfg_attr(feature = "cli", derive(clap::ArgEnum))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommitSource {
+    /// The message was given with the `-m` or `-F` option.
+    Message,
+    /// The message was given with the `-t` option or the `commit.template` config.
+    Template,
+    /// The commit is a merge commit or a `.git/MERGE_MSG` file exists.
+    Merge,
+    /// A `.git/SQUASH_MSG` file exists, created by `git merge --squash`.
+    Squash,
+    /// The message was reused with the `-c`, `-C` or `--amend` option.
+    Commit,
+}
+
+pub fn fetch_and_parse_branch(options: &RuleOptions) -> Result<Branch, String> {
     let name = match run_command("git", &["rev-parse", "--abbrev-ref", "HEAD"]) {
         Ok(output) => output.trim().to_string(),
         Err(e) => return Err(e.message),
     };
     let mut branch = Branch::new(name);
-    branch.validate();
+    branch.validate(options);
     Ok(branch)
 }
 
-pub fn fetch_and_parse_commits(selector: Option<String>) -> Result<Vec<Commit>, String> {
+/// Lint the selected commit, or commit range. The Git commands are run in the repository of the
+/// directory, or in the current working directory without a directory.
+pub fn fetch_and_parse_commits(
+    directory: Option<&Path>,
+    selector: Option<String>,
+    fetch_options: &FetchOptions,
+    options: &RuleOptions,
+) -> Result<Vec<Commit>, String> {
     let mut commits = Vec::<Commit>::new();
-    // Format definition per commit
-    // Line 1: Commit SHA in long form
-    // Line 2: Commit author email address
-    // Line 3 to second to last: Commit subject and message
-    // Line last: Delimiter to tell commits apart
-    let format = "%n%H%n%ae%n%B%n";
-    let mut args = vec![
-        "log".to_string(),
-        format!(
-            "--pretty={}{}{}",
-            COMMIT_DELIMITER, format, COMMIT_BODY_DELIMITER
-        ),
-        "--shortstat".to_string(),
-    ];
+    let mut args = log_args(fetch_options, options);
+    // Select all commits since the date rather than one commit if no commit range was selected
+    let single_commit = fetch_options.since.is_none();
     match selector {
         Some(selection) => {
             let selection = selection.trim().to_string();
-            if !selection.contains("..") {
+            if single_commit && !selection.contains("..") {
                 // Only select one commit if no commit range was selected
                 args.push("-n 1".to_string());
             }
             args.push(selection);
         }
         None => {
-            args.push("-n 1".to_string());
+            if single_commit {
+                args.push("-n 1".to_string());
+            }
             args.push("HEAD".to_string());
         }
     };
 
-    let output = match run_command("git", &args) {
+    let output = match run_command("git", &git_args(directory, &args)) {
         Ok(out) => out,
         Err(e) => return Err(e.message),
     };
     let messages = output.split(COMMIT_DELIMITER);
     for message in messages {
-        let trimmed_message = message.trim();
-        if !trimmed_message.is_empty() {
-            match parse_commit(trimmed_message) {
-                Some(commit) => commits.push(commit),
-                None => debug!("Commit ignored: {:?}", message),
+        parse_log_message(message, &mut commits, options);
+    }
+    fetch_file_sizes(directory, &mut commits, options);
+    validate_commits(&mut commits, options);
+    validate_commit_range(&mut commits, options);
+    Ok(commits)
+}
+
+// The arguments to run the Git command with in the repository of the directory, using Git's `-C`
+// option, rather than changing the working directory of the process.
+fn git_args<S: AsRef<OsStr>>(directory: Option<&Path>, args: &[S]) -> Vec<OsString> {
+    let mut git_args = vec![];
+    if let Some(directory) = directory {
+        git_args.push(OsString::from("-C"));
+        git_args.push(directory.as_os_str().to_os_string());
+    }
+    git_args.extend(args.iter().map(|arg| arg.as_ref().to_os_string()));
+    git_args
+}
+
+/// Lint all commits reachable from HEAD, including the root commit.
+///
+/// The Git log output is parsed while it's read, so the output of the entire history isn't kept
+/// in memory. The progress callback is called with the number of commits parsed so far.
+///
+/// The commit range rules, like `SubjectDuplicate`, are not validated. They are meant for commits
+/// that can still be squashed before they are merged, not for the entire history.
+pub fn fetch_and_parse_all_commits<F: FnMut(usize)>(
+    fetch_options: &FetchOptions,
+    options: &RuleOptions,
+    mut progress: F,
+) -> Result<Vec<Commit>, String> {
+    let mut commits = Vec::<Commit>::new();
+    let mut message = String::new();
+    let mut count = 0;
+    let mut args = log_args(fetch_options, options);
+    args.push("HEAD".to_string());
+    let result = run_command_streaming("git", &args, |line| {
+        if line == COMMIT_DELIMITER {
+            if !message.trim().is_empty() {
+                parse_log_message(&message, &mut commits, options);
+                count += 1;
+                progress(count);
+            }
+            message.clear();
+        } else {
+            message.push_str(line);
+            message.push('\n');
+        }
+    });
+    if let Err(e) = result {
+        return Err(e.message);
+    }
+    parse_log_message(&message, &mut commits, options);
+    fetch_file_sizes(None, &mut commits, options);
+    validate_commits(&mut commits, options);
+    Ok(commits)
+}
+
+/// A commit fetched from outside the local repository, like from the GitHub API.
+#[derive(Debug, Default, PartialEq)]
+pub struct RemoteCommit {
+    pub sha: Option<String>,
+    pub email: Option<String>,
+    pub author_name: Option<String>,
+    /// The full commit message, with the subject on the first line.
+    pub message: String,
+    pub has_changes: bool,
+}
+
+impl RemoteCommit {
+    /// A commit from the API of a Git host, like GitHub. The changes of the commits are not part
+    /// of the API responses, so every commit is assumed to have changes.
+    pub fn from_api(
+        sha: Option<String>,
+        email: Option<String>,
+        author_name: Option<String>,
+        message: String,
+    ) -> Self {
+        Self {
+            sha,
+            email,
+            author_name,
+            message,
+            has_changes: true,
+        }
+    }
+}
+
+/// A pull or merge request fetched from outside the local repository, with the name of its branch
+/// and its commits.
+#[derive(Debug)]
+pub struct PullRequest {
+    pub branch: String,
+    /// The commits of the pull request, the newest commit first like Git logs them.
+    pub commits: Vec<RemoteCommit>,
+}
+
+/// Parse and validate the commits fetched from outside the local repository, like the commits of
+/// a pull request fetched from the GitHub API.
+///
+/// The commits are expected in the order Git logs them: the newest commit first. The files
+/// changed in the commits are not known, so the rules about the changed files are not validated.
+pub fn parse_remote_commits(
+    remote_commits: Vec<RemoteCommit>,
+    options: &RuleOptions,
+) -> Vec<Commit> {
+    let mut commits = remote_commits
+        .into_iter()
+        .map(|remote_commit| parse_remote_commit(remote_commit, options))
+        .collect::<Vec<Commit>>();
+    validate_commits(&mut commits, options);
+    validate_commit_range(&mut commits, options);
+    commits
+}
+
+/// Parse the commits from a JSON array of commit objects, for tools that fetch the commits
+/// themselves. Every object has a `subject` and a `message` body, or only a `message` with the
+/// subject on the first line. The `sha`, `has_changes` and `author` fields are optional.
+///
+/// ```json
+/// [{
+///   "sha": "1a2b3c4d5e6f...",
+///   "subject": "Add the login form",
+///   "message": "Users can now log in.",
+///   "has_changes": true,
+///   "author": { "name": "Jane Doe", "email": "jane@example.com" }
+/// }]
+/// ```
+pub fn parse_commits_json(input: &str) -> Result<Vec<RemoteCommit>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(input).map_err(|e| format!("Unable to parse commits JSON: {}", e))?;
+    let commits = json
+        .as_array()
+        .ok_or_else(|| "The commits JSON is not an array of commits".to_string())?;
+    commits
+        .iter()
+        .enumerate()
+        .map(|(index, commit)| {
+            let invalid = |field: &str| {
+                format!(
+                    "Invalid `{}` field of commit {} in the commits JSON",
+                    field,
+                    index + 1
+                )
+            };
+            let string_field = |field: &str| match &commit[field] {
+                serde_json::Value::Null => Ok(None),
+                serde_json::Value::String(value) => Ok(Some(value.to_string())),
+                _ => Err(invalid(field)),
+            };
+            if !commit.is_object() {
+                return Err(format!(
+                    "Commit {} in the commits JSON is not an object",
+                    index + 1
+                ));
+            }
+            let message = match (string_field("subject")?, string_field("message")?) {
+                (Some(subject), Some(message)) => format!("{}\n\n{}", subject, message),
+                (Some(subject), None) => subject,
+                (None, Some(message)) => message,
+                (None, None) => return Err(invalid("subject")),
+            };
+            let has_changes = match &commit["has_changes"] {
+                serde_json::Value::Null => true,
+                serde_json::Value::Bool(has_changes) => *has_changes,
+                _ => return Err(invalid("has_changes")),
+            };
+            let author = &commit["author"];
+            if !author.is_null() && !author.is_object() {
+                return Err(invalid("author"));
             }
+            Ok(RemoteCommit {
+                sha: string_field("sha")?,
+                email: author["email"].as_str().map(String::from),
+                author_name: author["name"].as_str().map(String::from),
+                message,
+                has_changes,
+            })
+        })
+        .collect()
+}
+
+fn parse_remote_commit(remote_commit: RemoteCommit, options: &RuleOptions) -> Commit {
+    let message = normalize_line_endings(remote_commit.message.trim_end());
+    let mut lines = message.lines();
+    let subject = lines.next().unwrap_or_default();
+    let mut commit = commit_for(
+        remote_commit.sha,
+        remote_commit.email,
+        subject,
+        lines.map(String::from).collect(),
+        remote_commit.has_changes,
+        options,
+    );
+    commit.author_name = remote_commit.author_name;
+    commit
+}
+
+/// The arguments of the `git log` command to fetch commits in the format the commit parser
+/// expects.
+fn log_args(fetch_options: &FetchOptions, options: &RuleOptions) -> Vec<String> {
+    // Format definition per commit
+    // Line 1: Commit SHA in long form, followed by the signature status if signatures are
+    //         required. Verifying signatures is slow, so it's only done when needed.
+    // Line 2: Commit author email address
+    // Line 3: Commit author name
+    // Line 4 to second to last: Commit subject and message
+    // Line last: Delimiter to tell commits apart
+    // Followed by the changed files and the stats of the commit
+    let sha_format = if options.require_signature {
+        "%H %G?"
+    } else {
+        "%H"
+    };
+    let format = format!("%n{}%n%ae%n%an%n%B%n", sha_format);
+    let mut args = vec![
+        "log".to_string(),
+        format!(
+            "--pretty={}{}{}",
+            COMMIT_DELIMITER, format, COMMIT_BODY_DELIMITER
+        ),
+        "--raw".to_string(),
+        "--no-abbrev".to_string(),
+        "--shortstat".to_string(),
+    ];
+    if let Some(author) = &fetch_options.author {
+        args.push(format!("--author={}", author));
+    }
+    if let Some(since) = &fetch_options.since {
+        args.push(format!("--since={}", since));
+    }
+    args
+}
+
+/// Parse the commit from the Git log output. The commit is validated separately, so all commits
+/// can be validated in parallel.
+fn parse_log_message(message: &str, commits: &mut Vec<Commit>, options: &RuleOptions) {
+    let trimmed_message = message.trim();
+    if !trimmed_message.is_empty() {
+        match stats::measure("Parsing commits", || parse_commit(trimmed_message, options)) {
+            Some(commit) => commits.push(commit),
+            None => debug!("Commit ignored: {:?}", message),
         }
     }
+}
+
+/// Lint the staged changes before a commit is made, like in the pre-commit hook.
+///
+/// There's no commit message yet at this point, so only the rules about the commit's changes are
+/// validated.
+pub fn fetch_and_parse_staged_changes(options: &RuleOptions) -> Result<Vec<Commit>, String> {
+    let stats = match run_command(
+        "git",
+        &["diff", "--cached", "--raw", "--no-abbrev", "--shortstat"],
+    ) {
+        Ok(stdout) => stdout,
+        Err(e) => return Err(e.message),
+    };
+    let has_changes = !stats.trim().is_empty();
+    let mut commit = Commit::new(None, None, "", "".to_string(), has_changes);
+    commit.changed_files = stats.lines().filter_map(ChangedFile::parse).collect();
+    commit.files_changed = parse_files_changed(&stats);
+    let mut commits = vec![commit];
+    fetch_file_sizes(None, &mut commits, options);
+    for commit in commits.iter_mut() {
+        commit.validate_diff(options);
+    }
     Ok(commits)
 }
 
-fn parse_commit(message: &str) -> Option<Commit> {
+// Parse the number of changed files from the `--shortstat` line, like
+// ` 3 files changed, 116 insertions(+), 11 deletions(-)`.
+fn parse_files_changed(stats: &str) -> usize {
+    stats
+        .lines()
+        .find_map(|line| {
+            let (count, rest) = line.trim().split_once(' ')?;
+            if rest.starts_with("file") {
+                count.parse().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0)
+}
+
+/// Look up the sizes of the files added in the commits, for the `DiffLargeFile` rule. The sizes
+/// of the files of all commits are looked up with one Git command.
+fn fetch_file_sizes(directory: Option<&Path>, commits: &mut [Commit], options: &RuleOptions) {
+    if options.rule_disabled(Rule::DiffLargeFile) {
+        return;
+    }
+    let blobs = commits
+        .iter()
+        .flat_map(|commit| commit.changed_files.iter())
+        .filter(|file| file.status == 'A')
+        .map(|file| file.blob.as_str())
+        .collect::<Vec<&str>>();
+    if blobs.is_empty() {
+        return;
+    }
+    let input = format!("{}\n", blobs.join("\n"));
+    let output = match run_command_with_input(
+        "git",
+        &git_args(
+            directory,
+            &["cat-file", "--batch-check=%(objectname) %(objectsize)"],
+        ),
+        Some(&input),
+    ) {
+        Ok(stdout) => stdout,
+        Err(e) => {
+            error!(
+                "Unable to determine the size of added files.\nError: {}",
+                e.message
+            );
+            return;
+        }
+    };
+    // Objects that can't be found, like the commits of submodules, are reported as
+    // `<object> missing`, and don't have a size.
+    let sizes = output
+        .lines()
+        .filter_map(|line| {
+            let (blob, size) = line.split_once(' ')?;
+            Some((blob, size.parse::<usize>().ok()?))
+        })
+        .collect::<HashMap<&str, usize>>();
+    for commit in commits.iter_mut() {
+        for file in commit.changed_files.iter_mut() {
+            if file.status == 'A' {
+                file.size = sizes.get(file.blob.as_str()).copied();
+            }
+        }
+    }
+}
+
+/// Returns true if there are any changes staged to be committed.
+pub fn has_staged_changes() -> Result<bool, String> {
+    match run_command("git", &["diff", "--cached", "--shortstat"]) {
+        Ok(stdout) => Ok(!stdout.trim().is_empty()),
+        Err(e) => Err(e.message),
+    }
+}
+
+/// Use the Git directory at the path for all Git commands, like a bare repository on a server.
+/// Relative paths are relative to the working directory.
+pub fn use_git_dir(path: &Path) -> Result<(), String> {
+    let current_dir = env::current_dir()
+        .map_err(|e| format!("Unable to determine the working directory: {}", e))?;
+    let git_dir = current_dir.join(path);
+    if !git_dir.is_dir() {
+        return Err(format!(
+            "The Git directory does not exist: {}",
+            git_dir.to_string_lossy()
+        ));
+    }
+    debug!("Using GIT_DIR: {}", git_dir.to_string_lossy());
+    env::set_var("GIT_DIR", git_dir);
+    Ok(())
+}
+
+/// Change the working directory to the root of the repository's working tree, so all Git
+/// commands target the repository the hook was run for.
+///
+/// Hooks from a shared `core.hooksPath` directory or a Git template can be run from another
+/// working directory. Git sets the `GIT_DIR` and `GIT_WORK_TREE` environment variables for these
+/// hooks, which can be relative to the directory Git was run in. These are made absolute before
+/// the working directory is changed.
+pub fn enter_repository_root() -> Result<PathBuf, String> {
+    let current_dir = env::current_dir()
+        .map_err(|e| format!("Unable to determine the working directory: {}", e))?;
+    for variable in ["GIT_DIR", "GIT_WORK_TREE"] {
+        if let Some(path) = env::var_os(variable) {
+            let path = current_dir.join(path);
+            debug!("Using {}: {}", variable, path.to_string_lossy());
+            env::set_var(variable, path);
+        }
+    }
+    let root = repository_root()?;
+    debug!("Repository root: {}", root.to_string_lossy());
+    env::set_current_dir(&root).map_err(|e| {
+        format!(
+            "Unable to change the working directory to the repository root: {}\n{}",
+            root.to_string_lossy(),
+            e
+        )
+    })?;
+    Ok(root)
+}
+
+/// Returns the root directory of the repository's working tree.
+pub fn repository_root() -> Result<PathBuf, String> {
+    // Without `GIT_WORK_TREE`, Git considers the working directory the root of the working tree
+    // when `GIT_DIR` is set. Look up the working tree from the Git directory instead.
+    if env::var_os("GIT_WORK_TREE").is_none() {
+        if let Some(git_dir) = env::var_os("GIT_DIR") {
+            if let Some(root) = work_tree_for_git_dir(Path::new(&git_dir)) {
+                return Ok(root);
+            }
+        }
+    }
+    match run_command("git", &["rev-parse", "--show-toplevel"]) {
+        Ok(stdout) => Ok(PathBuf::from(stdout.trim())),
+        Err(e) => Err(format!(
+            "Unable to determine the repository root.\nError: {}",
+            e.message
+        )),
+    }
+}
+
+/// Returns the absolute path of the working tree of the Git directory: the parent directory of a
+/// `.git` directory, or the working tree of a linked worktree, as stored in its `gitdir` file.
+fn work_tree_for_git_dir(git_dir: &Path) -> Option<PathBuf> {
+    // A relative Git directory, like `.git`, has no parent directory to return
+    let git_dir = git_dir.canonicalize().ok()?;
+    let work_tree = if git_dir.file_name() == Some(OsStr::new(".git")) {
+        git_dir.parent()?.to_path_buf()
+    } else {
+        let gitdir_file = fs::read_to_string(git_dir.join("gitdir")).ok()?;
+        Path::new(gitdir_file.trim()).parent()?.to_path_buf()
+    };
+    work_tree.canonicalize().ok()
+}
+
+/// Returns the commit range of commits that have not been pushed yet: all commits since the
+/// remote tracking branch the current branch pushes to. Commits that have been amended or rebased
+/// since the last push are also included, as they are different commits than those on the remote.
+pub fn unpushed_commits_selection() -> Result<String, String> {
+    for reference in ["@{push}", "@{upstream}"] {
+        match run_command(
+            "git",
+            &[
+                "rev-parse",
+                "--abbrev-ref",
+                "--symbolic-full-name",
+                reference,
+            ],
+        ) {
+            Ok(stdout) => {
+                let remote_branch = stdout.trim();
+                if !remote_branch.is_empty() {
+                    debug!("Using remote tracking branch: {}", remote_branch);
+                    return Ok(format!("{}..HEAD", remote_branch));
+                }
+            }
+            Err(e) => debug!(
+                "Unable to determine remote tracking branch for {}.\nError: {}",
+                reference, e.message
+            ),
+        }
+    }
+    Err("Unable to determine the last pushed commit. \
+        The current branch has no remote tracking branch. \
+        Push the branch first or specify a commit range."
+        .to_string())
+}
+
+/// Returns the commit range of the pull request with the given number: all commits of the pull
+/// request that are not on the default branch of the `origin` remote.
+///
+/// The pull request's commits are looked up in the local refs first, like `refs/pull/<number>/head`
+/// and `refs/remotes/origin/pr/<number>`. If the pull request isn't found locally, it's fetched from
+/// the `origin` remote.
+pub fn pull_request_selection(number: u32) -> Result<String, String> {
+    let head = pull_request_head(number)?;
+    let base = default_branch()?;
+    debug!("Pull request #{} head: {}, base: {}", number, head, base);
+    Ok(format!("{}..{}", base, head))
+}
+
+/// Returns the commit range of the commits on the current branch that are not on the default branch
+/// of the `origin` remote: all commits since the merge-base of the current branch and the default
+/// branch.
+pub fn branch_range_selection() -> Result<String, String> {
+    let base = default_branch()?;
+    match run_command("git", &["merge-base", &base, "HEAD"]) {
+        Ok(stdout) => {
+            let merge_base = stdout.trim();
+            debug!("Merge-base with {}: {}", base, merge_base);
+            Ok(format!("{}..HEAD", merge_base))
+        }
+        Err(e) => Err(format!(
+            "Unable to determine the merge-base of the current branch and {}.\nError: {}",
+            base, e.message
+        )),
+    }
+}
+
+fn pull_request_head(number: u32) -> Result<String, String> {
+    let local_refs = [
+        format!("refs/pull/{}/head", number),
+        format!("refs/remotes/origin/pull/{}/head", number),
+        format!("refs/remotes/origin/pr/{}", number),
+    ];
+    for reference in &local_refs {
+        if let Some(sha) = commit_sha(reference) {
+            return Ok(sha);
+        }
+    }
+    debug!("Pull request #{} not found locally. Fetching it.", number);
+    let fetch_ref = format!("refs/pull/{}/head", number);
+    if let Err(e) = run_command("git", &["fetch", "--quiet", "origin", &fetch_ref]) {
+        return Err(format!(
+            "Unable to find pull request #{} locally or on the origin remote.\nError: {}",
+            number, e.message
+        ));
+    }
+    commit_sha("FETCH_HEAD")
+        .ok_or_else(|| format!("Unable to find the fetched pull request #{}.", number))
+}
+
+/// Returns the default branch of the `origin` remote, like `origin/main`.
+fn default_branch() -> Result<String, String> {
+    if let Ok(stdout) = run_command("git", &["rev-parse", "--abbrev-ref", "origin/HEAD"]) {
+        let branch = stdout.trim();
+        if !branch.is_empty() && branch != "origin/HEAD" {
+            return Ok(branch.to_string());
+        }
+    }
+    for branch in ["origin/main", "origin/master"] {
+        if commit_sha(branch).is_some() {
+            return Ok(branch.to_string());
+        }
+    }
+    Err(
+        "Unable to determine the default branch of the origin remote. \
+        Set it with `git remote set-head origin --auto`."
+            .to_string(),
+    )
+}
+
+fn commit_sha(reference: &str) -> Option<String> {
+    run_command(
+        "git",
+        &[
+            "rev-parse",
+            "--quiet",
+            "--verify",
+            &format!("{}^{{commit}}", reference),
+        ],
+    )
+    .ok()
+    .map(|stdout| stdout.trim().to_string())
+}
+
+/// Returns the `lintje.*` keys and values from the Git config of all scopes, in the format of
+/// `git config --null --get-regexp`.
+pub fn lintje_config() -> String {
+    match run_command("git", &["config", "--null", "--get-regexp", r"^lintje\."]) {
+        Ok(stdout) => stdout,
+        // Git exits with status code 1 when no keys are found
+        Err(e) if e.code == Some(1) => String::new(),
+        Err(e) => {
+            error!("Unable to read Git config.\nError: {}", e.message);
+            String::new()
+        }
+    }
+}
+
+/// Returns the GitHub repository name, like `tombruijn/lintje`, of the `origin` remote.
+pub fn github_repository() -> Option<String> {
+    match run_command("git", &["remote", "get-url", "origin"]) {
+        Ok(stdout) => crate::github::repository_from_remote_url(&stdout),
+        Err(e) => {
+            debug!("Unable to fetch origin remote URL.\nError: {}", e.message);
+            None
+        }
+    }
+}
+
+fn parse_commit(message: &str, options: &RuleOptions) -> Option<Commit> {
+    let message = &normalize_line_endings(message);
     let mut long_sha = None;
+    let mut signature = None;
     let mut email = None;
+    let mut author_name = None;
     let mut subject = None;
     let mut message_lines = vec![];
     let mut has_changes = false;
+    let mut changed_files = vec![];
+    let mut files_changed = 0;
     let mut message_parts = message.split(COMMIT_BODY_DELIMITER);
     match message_parts.next() {
         Some(body) => {
             for (index, line) in body.lines().enumerate() {
                 match index {
-                    0 => long_sha = Some(line),
+                    0 => match line.split_once(' ') {
+                        Some((sha, status)) => {
+                            long_sha = Some(sha);
+                            signature = SignatureStatus::parse(status);
+                        }
+                        None => long_sha = Some(line),
+                    },
                     1 => email = Some(line.to_string()),
-                    2 => subject = Some(line),
+                    2 => author_name = Some(line.to_string()),
+                    3 => subject = Some(line),
                     _ => message_lines.push(line.to_string()),
                 }
             }
@@ -109,6 +713,11 @@ fn parse_commit(message: &str) -> Option<Commit> {
             } else {
                 debug!("Stats line found: {}", has_changes_str.to_string());
                 has_changes = true;
+                changed_files = has_changes_str
+                    .lines()
+                    .filter_map(ChangedFile::parse)
+                    .collect();
+                files_changed = parse_files_changed(has_changes_str);
             }
         }
         None => debug!("Commit has no stats"),
@@ -119,13 +728,19 @@ fn parse_commit(message: &str) -> Option<Commit> {
                 debug!("Commit subject not present in message: {:?}", message);
                 ""
             });
-            Some(commit_for(
+            let mut commit = commit_for(
                 Some(long_sha.to_string()),
                 email,
                 used_subject,
                 message_lines,
                 has_changes,
-            ))
+                options,
+            );
+            commit.author_name = author_name;
+            commit.signature = signature;
+            commit.changed_files = changed_files;
+            commit.files_changed = files_changed;
+            Some(commit)
         }
         _ => {
             debug!("Commit ignored: SHA was not present: {}", message);
@@ -139,26 +754,39 @@ pub fn parse_commit_hook_format(
     cleanup_mode: &CleanupMode,
     comment_char: &str,
     has_changes: bool,
+    options: &RuleOptions,
 ) -> Commit {
+    let message = &normalize_line_endings(message);
     let mut subject = None;
     let mut message_lines = vec![];
-    let scissor_line = format!("{} {}", comment_char, SCISSORS);
     debug!("Using clean up mode: {:?}", cleanup_mode);
     debug!("Using config core.commentChar: {:?}", comment_char);
-    for line in message.lines() {
+    let detected_comment_char;
+    let comment_char = if comment_char == "auto" {
+        detected_comment_char = detect_comment_char(message);
+        debug!("Detected comment character: {:?}", detected_comment_char);
+        detected_comment_char.as_str()
+    } else {
+        comment_char
+    };
+    let scissor_line = format!("{} {}", comment_char, SCISSORS);
+    let mut lines = message.lines().peekable();
+    while let Some(line) = lines.next() {
         // A scissor line has been detected.
         //
         // A couple reasons why this could happen:
         //
         // - A scissor line was found in cleanup mode "scissors". All content after this line is
         //   ignored.
-        // - A scissor line was found in a different cleanup mode with the `--verbose` option.
-        //   Lintje cannot detect this verbose mode so it assumes it's for the verbose mode
-        //   and ignores all content after this line.
+        // - A scissor line was found in a different cleanup mode with the `--verbose` option or
+        //   `commit.verbose` config. Lintje cannot detect this verbose mode so it assumes it's
+        //   for the verbose mode and ignores all content after this line. The diff below the
+        //   line is also detected when the comment character is different than configured, like
+        //   with the `core.commentChar=auto` config, by the notice Git adds below the line.
         // - The commit message is entirely empty, leaving only the comments added to the file by
         //   Git. Unless `--allow-empty-message` is specified this is the user telling Git it stop
         //   the commit process.
-        if line == scissor_line {
+        if line == scissor_line || is_verbose_scissors_line(line, lines.peek().copied()) {
             debug!("Found scissors line. Stop parsing message.");
             break;
         }
@@ -190,7 +818,89 @@ pub fn parse_commit_hook_format(
         "".to_string()
     });
 
-    commit_for(None, None, &used_subject, message_lines, has_changes)
+    let mut commit = commit_for(
+        None,
+        None,
+        &used_subject,
+        message_lines,
+        has_changes,
+        options,
+    );
+    // The commit isn't made yet, so the author is the author Git will use for the commit
+    if options.require_dco {
+        if let Some((name, email)) = author_ident() {
+            commit.author_name = Some(name);
+            commit.email = Some(email);
+        }
+    }
+    if !commit.ignored {
+        commit.validate(options);
+    }
+    commit
+}
+
+// Commit messages written on Windows can have `\r\n` line endings, and older macOS editors end
+// lines with `\r`. Use `\n` line endings everywhere, so the carriage returns don't end up in the
+// subject and message lines.
+fn normalize_line_endings(message: &str) -> String {
+    message.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// The name and email address of the author of the commit that is being made.
+fn author_ident() -> Option<(String, String)> {
+    match run_command("git", &["var", "GIT_AUTHOR_IDENT"]) {
+        Ok(stdout) => parse_author_ident(&stdout),
+        Err(e) => {
+            debug!("Unable to determine commit author.\nError: {}", e.message);
+            None
+        }
+    }
+}
+
+// Parse the "Jane Doe <jane@example.com> 1600000000 +0200" format
+fn parse_author_ident(ident: &str) -> Option<(String, String)> {
+    let (name, rest) = ident.split_once(" <")?;
+    let (email, _timestamp) = rest.split_once('>')?;
+    Some((name.trim().to_string(), email.to_string()))
+}
+
+/// Detect the comment character Git picked with the `core.commentChar=auto` config.
+fn detect_comment_char(message: &str) -> String {
+    // Git adds its instructions and the scissors line to the message file as comments
+    for line in message.lines() {
+        let mut chars = line.chars();
+        if let Some(character) = chars.next() {
+            let rest = chars.as_str();
+            if AUTO_COMMENT_CHARS.contains(&character)
+                && (rest.starts_with(" Please enter the commit message")
+                    || rest == format!(" {}", SCISSORS))
+            {
+                return character.to_string();
+            }
+        }
+    }
+    // Git adds no comments when the commit message is given with the `--message` option, pick the
+    // first character no line starts with like Git does.
+    AUTO_COMMENT_CHARS
+        .iter()
+        .find(|character| !message.lines().any(|line| line.starts_with(**character)))
+        .map_or_else(|| "#".to_string(), char::to_string)
+}
+
+/// Returns true if the line is a scissors line followed by Git's notice to not remove it, with any
+/// comment character.
+fn is_verbose_scissors_line(line: &str, next_line: Option<&str>) -> bool {
+    let prefix = match line.strip_suffix(SCISSORS) {
+        Some(prefix) => prefix.trim_end(),
+        None => return false,
+    };
+    if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+        return false;
+    }
+    match next_line.and_then(|next_line| next_line.strip_prefix(prefix)) {
+        Some(notice) => SCISSORS_NOTICES.contains(&notice.trim()),
+        None => false,
+    }
 }
 
 fn cleanup_line(line: &str, cleanup_mode: &CleanupMode, comment_char: &str) -> Option<String> {
@@ -213,19 +923,26 @@ fn commit_for(
     subject: &str,
     message: Vec<String>,
     has_changes: bool,
+    options: &RuleOptions,
 ) -> Commit {
     let mut commit = Commit::new(sha, email, subject, message.join("\n"), has_changes);
-    if ignored(&commit) {
-        commit.ignored = true;
-    } else {
-        commit.validate();
-    }
+    commit.ignored = ignored(&commit, options);
     commit
 }
 
-fn ignored(commit: &Commit) -> bool {
+fn ignored(commit: &Commit, options: &RuleOptions) -> bool {
     let subject = &commit.subject;
     let message = &commit.message;
+    if message
+        .lines()
+        .any(|line| line.trim_end() == "lintje:disable-all")
+    {
+        debug!(
+            "Ignoring commit because it disables all rules with `lintje:disable-all`: {}",
+            subject
+        );
+        return true;
+    }
     if let Some(email) = &commit.email {
         if email.ends_with("[bot]@users.noreply.github.com") {
             debug!(
@@ -258,7 +975,7 @@ fn ignored(commit: &Commit) -> bool {
         );
         return true;
     }
-    if SUBJECT_WITH_SQUASH_PR.is_match(subject) {
+    if commit.is_squash_merge() && !options.lint_squash_merges {
         // Subject ends with a GitHub squash PR marker: ` (#123)`
         debug!(
             "Ignoring commit because it's a 'merge pull request' squash commit: {}",
@@ -277,8 +994,45 @@ fn ignored(commit: &Commit) -> bool {
     false
 }
 
-pub fn cleanup_mode() -> CleanupMode {
-    match run_command("git", &["config", "commit.cleanup"]) {
+/// Detect the commit source from the state of the repository. Git only passes the commit source
+/// to the prepare-commit-msg hook, not to the commit-msg hook.
+///
+/// Only merge and squash commits can be detected this way. Amended commits leave no trace in the
+/// repository before the commit is made.
+pub fn detect_commit_source() -> Option<CommitSource> {
+    if run_command("git", &["rev-parse", "--quiet", "--verify", "MERGE_HEAD"]).is_ok() {
+        debug!("MERGE_HEAD found. Commit source is merge.");
+        return Some(CommitSource::Merge);
+    }
+    match run_command("git", &["rev-parse", "--git-path", "SQUASH_MSG"]) {
+        Ok(stdout) => {
+            if Path::new(stdout.trim()).exists() {
+                debug!("SQUASH_MSG found. Commit source is squash.");
+                return Some(CommitSource::Squash);
+            }
+        }
+        Err(e) => debug!("Unable to determine SQUASH_MSG path.\nError: {}", e.message),
+    }
+    None
+}
+
+/// Returns the path of the commit message template configured with the `commit.template` config.
+pub fn commit_template() -> Result<PathBuf, String> {
+    match run_command("git", &["config", "--path", "commit.template"]) {
+        Ok(stdout) if !stdout.trim().is_empty() => Ok(PathBuf::from(stdout.trim())),
+        Ok(_) => Err("No commit message template configured with commit.template".to_string()),
+        Err(e) if e.code == Some(1) => {
+            Err("No commit message template configured with commit.template".to_string())
+        }
+        Err(e) => Err(format!(
+            "Unable to determine Git's commit.template config: {}",
+            e.message
+        )),
+    }
+}
+
+pub fn cleanup_mode(directory: Option<&Path>) -> CleanupMode {
+    match run_command("git", &git_args(directory, &["config", "commit.cleanup"])) {
         Ok(stdout) => match stdout.trim() {
             "default" | "" => CleanupMode::Default,
             "scissors" => CleanupMode::Scissors,
@@ -311,10 +1065,32 @@ pub fn cleanup_mode() -> CleanupMode {
     }
 }
 
-pub fn comment_char() -> String {
-    match run_command("git", &["config", "core.commentChar"]) {
+/// Returns the comment character configured with the `core.commentChar` or `core.commentString`
+/// config. Newer Git versions support comment strings of multiple characters. Both options are
+/// aliases of each other, Git uses the value of the option that is set last.
+///
+/// With the `auto` value, Git picks a comment character that's not used in the commit message.
+/// The comment character is then detected in the commit message file.
+pub fn comment_char(directory: Option<&Path>) -> String {
+    match run_command(
+        "git",
+        &git_args(
+            directory,
+            &[
+                "config",
+                "--null",
+                "--get-regexp",
+                r"^core\.comment(char|string)$",
+            ],
+        ),
+    ) {
         Ok(stdout) => {
-            let character = stdout.trim().to_string();
+            let character = stdout
+                .split('\0')
+                .rfind(|entry| !entry.is_empty())
+                .and_then(|entry| entry.split_once('\n'))
+                .map(|(_key, value)| value.trim().to_string())
+                .unwrap_or_default();
             if character.is_empty() {
                 debug!("No Git core.commentChar config found. Using default `#` character.");
                 "#".to_string()
@@ -342,9 +1118,42 @@ pub fn comment_char() -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::Commit;
-    use super::{parse_commit, parse_commit_hook_format, CleanupMode, COMMIT_BODY_DELIMITER};
+    use super::{
+        parse_author_ident, parse_commit_hook_format as parse_commit_hook_format_with_options,
+        parse_commits_json, parse_remote_commits, CleanupMode, RemoteCommit, COMMIT_BODY_DELIMITER,
+    };
+    use super::{Commit, SignatureStatus};
+    use crate::commit::validate_commits;
     use crate::issue::{Issue, IssueType};
+    use crate::options::RuleOptions;
+    use crate::rule::Rule;
+
+    fn parse_commit(message: &str) -> Option<Commit> {
+        parse_commit_with_options(message, &RuleOptions::default())
+    }
+
+    fn parse_commit_with_options(message: &str, options: &RuleOptions) -> Option<Commit> {
+        let mut commits = super::parse_commit(message, options)
+            .into_iter()
+            .collect::<Vec<Commit>>();
+        validate_commits(&mut commits, options);
+        commits.pop()
+    }
+
+    fn parse_commit_hook_format(
+        message: &str,
+        cleanup_mode: &CleanupMode,
+        comment_char: &str,
+        has_changes: bool,
+    ) -> Commit {
+        parse_commit_hook_format_with_options(
+            message,
+            cleanup_mode,
+            comment_char,
+            has_changes,
+            &RuleOptions::default(),
+        )
+    }
 
     fn assert_commit_is_ignored(result: &Option<Commit>) {
         match result {
@@ -377,11 +1186,29 @@ mod tests {
         format!("{}\n{}\n{}", message, COMMIT_BODY_DELIMITER, "\n")
     }
 
+    #[test]
+    fn test_parse_commit_with_signature() {
+        let result = parse_commit(&commit_with_file_changes(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa N\n\
+        test@example.com\n\
+        Test User\n\
+        This is a subject",
+        ));
+        let commit = result.unwrap();
+        assert_eq!(
+            commit.long_sha,
+            Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string())
+        );
+        assert_eq!(commit.signature, Some(SignatureStatus::Unsigned));
+        assert_eq!(commit.subject, "This is a subject");
+    }
+
     #[test]
     fn test_parse_commit() {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         This is a subject\n\
         \n\
         This is my multi line message.\n\
@@ -396,9 +1223,12 @@ mod tests {
         );
         assert_eq!(commit.short_sha, Some("aaaaaaa".to_string()));
         assert_eq!(commit.email, Some("test@example.com".to_string()));
+        assert_eq!(commit.author_name, Some("Test User".to_string()));
+        assert_eq!(commit.signature, None);
         assert_eq!(commit.subject, "This is a subject");
         assert_eq!(commit.message, "\nThis is my multi line message.\nLine 2.");
         assert!(commit.has_changes);
+        assert_eq!(commit.files_changed, 3);
         assert!(commit
             .issues
             .into_iter()
@@ -412,6 +1242,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         This is a subject",
         ));
 
@@ -429,6 +1260,30 @@ mod tests {
         assert!(!commit.issues.is_empty());
     }
 
+    #[test]
+    fn test_parse_commit_with_changed_files() {
+        let result = parse_commit(&format!(
+            "{}\n{}\n{}",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
+            test@example.com\n\
+            Test User\n\
+            This is a subject",
+            COMMIT_BODY_DELIMITER,
+            "\n:000000 100644 0000000 e69de29 A\tsrc/new.rs\n\
+            :100644 100644 e69de29 d00491f M\tREADME.md\n\n\
+            \x202 files changed, 1 insertion(+)"
+        ));
+        let commit = result.unwrap();
+        assert!(commit.has_changes);
+        assert_eq!(commit.files_changed, 2);
+        let files = commit
+            .changed_files
+            .iter()
+            .map(|file| (file.status, file.path.as_str()))
+            .collect::<Vec<(char, &str)>>();
+        assert_eq!(files, vec![('A', "src/new.rs"), ('M', "README.md")]);
+    }
+
     #[test]
     fn test_parse_commit_empty() {
         let result = parse_commit("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n");
@@ -452,6 +1307,7 @@ mod tests {
         let result = parse_commit(&commit_without_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
             test@example.com\n\
+            Test User\n\
             This is a subject\n\
             \n\
             This is a message.",
@@ -482,11 +1338,36 @@ mod tests {
         assert_commit_is_ignored(&result);
     }
 
+    #[test]
+    fn test_parse_commit_ignore_disable_all() {
+        let result = parse_commit(&commit_with_file_changes(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
+        test@example.com\n\
+        Test User\n\
+        import vendored library\n\
+        \n\
+        lintje:disable-all",
+        ));
+        assert_commit_is_ignored(&result);
+
+        // Only the directive on its own line
+        let result = parse_commit(&commit_with_file_changes(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
+        test@example.com\n\
+        Test User\n\
+        Document the directives\n\
+        \n\
+        Add `lintje:disable-all` to the message to skip all rules.",
+        ));
+        assert_commit_is_not_ignored(&result);
+    }
+
     #[test]
     fn test_parse_commit_ignore_tag_merge_commit() {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Merge tag 'v1.2.3' into main",
         ));
 
@@ -498,6 +1379,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Merge pull request #123 from tombruijn/repo\n\
         \n\
         This is my multi line message.\n\
@@ -512,6 +1394,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Fix some issue that's squashed (#123)\n\
         \n\
         This is my multi line message.\n\
@@ -521,11 +1404,40 @@ mod tests {
         assert_commit_is_ignored(&result);
     }
 
+    #[test]
+    fn test_parse_commit_lint_squash_merge_commit_pull_request() {
+        let options = RuleOptions {
+            lint_squash_merges: true,
+            ..RuleOptions::default()
+        };
+        let result = parse_commit_with_options(
+            &commit_with_file_changes(
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
+        test@example.com\n\
+        Test User\n\
+        Fixed some issue that's squashed (#123)\n\
+        \n\
+        * Fix issue\n\
+        * Fix typo",
+            ),
+            &options,
+        );
+
+        assert_commit_is_not_ignored(&result);
+        let commit = result.unwrap();
+        assert!(commit.is_squash_merge());
+        assert!(commit
+            .issues
+            .iter()
+            .any(|issue| issue.rule == Rule::SubjectMood));
+    }
+
     #[test]
     fn test_parse_commit_ignore_merge_commits_merge_request() {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Merge branch 'branch' into main\n\
         \n\
         This is my multi line message.\n\
@@ -541,6 +1453,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Fix some issue\n\
         \n\
         This is my multi line message.\n\
@@ -554,6 +1467,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Fix some issue\n\
         \n\
         This is my multi line message.\n\
@@ -567,6 +1481,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Fix some issue\n\
         \n\
         This is my multi line message.\n\
@@ -581,6 +1496,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Merge branch 'branch'",
         ));
 
@@ -592,6 +1508,7 @@ mod tests {
         let result = parse_commit(&commit_with_file_changes(
             "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n\
         test@example.com\n\
+        Test User\n\
         Merge branch 'branch' of github.com/org/repo into branch",
         ));
 
@@ -654,6 +1571,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_commit_hook_format_with_crlf_line_endings() {
+        for cleanup_mode in [
+            CleanupMode::Default,
+            CleanupMode::Strip,
+            CleanupMode::Whitespace,
+            CleanupMode::Scissors,
+            CleanupMode::Verbatim,
+        ] {
+            let commit = parse_commit_hook_format(
+                "This is a subject\r\n\
+                \r\n\
+                This is the message body.\r\n\
+                Another line.\r",
+                &cleanup_mode,
+                "#",
+                true,
+            );
+            assert_eq!(commit.subject, "This is a subject");
+            assert_eq!(commit.message, "\nThis is the message body.\nAnother line.");
+        }
+
+        let commit = parse_commit_hook_format(
+            "This is a subject\r\n\
+            # A comment\r\n\
+            # ------------------------ >8 ------------------------\r\n\
+            diff --git a/file b/file\r\n",
+            &CleanupMode::Scissors,
+            "#",
+            true,
+        );
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "# A comment");
+
+        let commit = parse_commit_hook_format(
+            "This is a subject\r\rThis is the message body.\r",
+            &CleanupMode::Verbatim,
+            "#",
+            true,
+        );
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "\nThis is the message body.");
+    }
+
+    #[test]
+    fn test_parse_commit_with_crlf_line_endings() {
+        let result = parse_commit(&commit_with_file_changes(
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n\
+            test@example.com\r\n\
+            Test User\r\n\
+            This is a subject\r\n\
+            \r\n\
+            This is the message body.\r",
+        ));
+        let commit = result.unwrap();
+        assert_eq!(commit.email, Some("test@example.com".to_string()));
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "\nThis is the message body.");
+    }
+
     #[test]
     fn test_parse_commit_hook_format_with_leading_empty_lines() {
         let commit = parse_commit_hook_format(
@@ -737,6 +1714,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_commit_hook_format_with_strip_comment_string() {
+        let commit = parse_commit_hook_format(
+            "This is a subject\n\
+            \n\
+            This is the message body.\n\
+            // This is a commented line.\n\
+            / This is not a commented line.\n\
+            \n\
+            // ------------------------ >8 ------------------------\n\
+            Other things that are not part of the message.\n\
+            ",
+            &CleanupMode::Default,
+            "//",
+            true,
+        );
+
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(
+            commit.message,
+            "\nThis is the message body.\n/ This is not a commented line.\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_hook_format_with_auto_comment_char() {
+        let commit = parse_commit_hook_format(
+            "This is a subject\n\
+            \n\
+            # This line starts with a hash.\n\
+            \n\
+            ; Please enter the commit message for your changes. Lines starting\n\
+            ; with ';' will be ignored, and an empty message aborts the commit.\n\
+            ",
+            &CleanupMode::Default,
+            "auto",
+            true,
+        );
+
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "\n# This line starts with a hash.\n");
+    }
+
+    #[test]
+    fn test_parse_commit_hook_format_with_auto_comment_char_without_comments() {
+        let commit = parse_commit_hook_format(
+            "This is a subject\n\
+            \n\
+            # This line starts with a hash.\n\
+            ",
+            &CleanupMode::Default,
+            "auto",
+            true,
+        );
+
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(commit.message, "\n# This line starts with a hash.");
+    }
+
     #[test]
     fn test_parse_commit_hook_format_with_scissors() {
         let commit = parse_commit_hook_format(
@@ -781,6 +1817,64 @@ mod tests {
         assert_eq!(commit.message, "");
     }
 
+    #[test]
+    fn test_parse_commit_hook_format_with_verbose_diff() {
+        // The comment character is different than configured, like with
+        // `core.commentChar=auto`
+        let commit = parse_commit_hook_format(
+            "This is a subject\n\
+            \n\
+            This is the message body.\n\
+            ; Please enter the commit message for your changes.\n\
+            ; ------------------------ >8 ------------------------\n\
+            ; Do not modify or remove the line above.\n\
+            ; Everything below it will be ignored.\n\
+            diff --git a/file.rs b/file.rs\n\
+            index 9daeafb..dbd3a6b 100644\n\
+            --- a/file.rs\n\
+            +++ b/file.rs\n\
+            @@ -1 +1 @@\n\
+            -fn main() { println!(\"This line is longer than the maximum message line length\"); }\n\
+            ",
+            &CleanupMode::Verbatim,
+            "#",
+            true,
+        );
+
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(
+            commit.message,
+            "\nThis is the message body.\n; Please enter the commit message for your changes."
+        );
+        assert!(!commit
+            .issues
+            .iter()
+            .any(|issue| issue.rule == Rule::MessageLineLength));
+    }
+
+    #[test]
+    fn test_parse_commit_hook_format_with_scissors_line_without_notice() {
+        let commit = parse_commit_hook_format(
+            "This is a subject\n\
+            \n\
+            This is the message body.\n\
+            ; ------------------------ >8 ------------------------\n\
+            This is line 2.\n\
+            ",
+            &CleanupMode::Default,
+            "#",
+            true,
+        );
+
+        assert_eq!(commit.subject, "This is a subject");
+        assert_eq!(
+            commit.message,
+            "\nThis is the message body.\n\
+            ; ------------------------ >8 ------------------------\n\
+            This is line 2."
+        );
+    }
+
     #[test]
     fn test_parse_commit_hook_format_with_verbatim() {
         let commit = parse_commit_hook_format(
@@ -916,4 +2010,98 @@ mod tests {
         assert_eq!(commit.subject, "This is a subject");
         assert_eq!(commit.message, "\nThis is the message body.");
     }
+
+    #[test]
+    fn test_parse_author_ident() {
+        assert_eq!(
+            parse_author_ident("Jane Doe <jane@example.com> 1600000000 +0200\n"),
+            Some(("Jane Doe".to_string(), "jane@example.com".to_string()))
+        );
+        assert_eq!(parse_author_ident("Jane Doe"), None);
+    }
+
+    #[test]
+    fn test_parse_remote_commits() {
+        let commits = parse_remote_commits(
+            vec![
+                RemoteCommit {
+                    sha: Some("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string()),
+                    email: Some("jane@example.com".to_string()),
+                    author_name: Some("Jane Doe".to_string()),
+                    message: "Fixing tests\r\n\r\nThe tests failed.\n".to_string(),
+                    has_changes: true,
+                },
+                RemoteCommit {
+                    sha: Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                    message: "Merge pull request #1 from tombruijn/login".to_string(),
+                    ..RemoteCommit::default()
+                },
+            ],
+            &RuleOptions::default(),
+        );
+        assert_eq!(commits.len(), 2);
+        let commit = &commits[0];
+        assert_eq!(commit.short_sha, Some("bbbbbbb".to_string()));
+        assert_eq!(commit.email, Some("jane@example.com".to_string()));
+        assert_eq!(commit.author_name, Some("Jane Doe".to_string()));
+        assert_eq!(commit.subject, "Fixing tests");
+        assert_eq!(commit.message, "\nThe tests failed.");
+        assert!(commit
+            .issues
+            .iter()
+            .any(|issue| issue.rule == Rule::SubjectMood));
+        assert!(commits[1].ignored);
+    }
+
+    #[test]
+    fn test_parse_commits_json() {
+        assert_eq!(
+            parse_commits_json(
+                r#"[
+                    {
+                        "sha": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                        "subject": "Add the login form",
+                        "message": "Users can now log in.",
+                        "has_changes": false,
+                        "author": {"name": "Jane Doe", "email": "jane@example.com"}
+                    },
+                    {"subject": "Fix the login form"},
+                    {"message": "Update the login form\n\nUsers can log in again."}
+                ]"#
+            ),
+            Ok(vec![
+                RemoteCommit {
+                    sha: Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                    email: Some("jane@example.com".to_string()),
+                    author_name: Some("Jane Doe".to_string()),
+                    message: "Add the login form\n\nUsers can now log in.".to_string(),
+                    has_changes: false,
+                },
+                RemoteCommit {
+                    message: "Fix the login form".to_string(),
+                    has_changes: true,
+                    ..RemoteCommit::default()
+                },
+                RemoteCommit {
+                    message: "Update the login form\n\nUsers can log in again.".to_string(),
+                    has_changes: true,
+                    ..RemoteCommit::default()
+                },
+            ])
+        );
+        assert_eq!(parse_commits_json("[]"), Ok(vec![]));
+        assert!(parse_commits_json("").is_err());
+        assert!(parse_commits_json("{}").is_err());
+        assert!(parse_commits_json("[1]").is_err());
+        assert_eq!(
+            parse_commits_json(r#"[{"sha": "a"}]"#),
+            Err("Invalid `subject` field of commit 1 in the commits JSON".to_string())
+        );
+        assert_eq!(
+            parse_commits_json(r#"[{"subject": "Fix"}, {"subject": 1}]"#),
+            Err("Invalid `subject` field of commit 2 in the commits JSON".to_string())
+        );
+        assert!(parse_commits_json(r#"[{"subject": "Fix", "has_changes": "yes"}]"#).is_err());
+        assert!(parse_commits_json(r#"[{"subject": "Fix", "author": "Jane"}]"#).is_err());
+    }
 }
@@ -0,0 +1,343 @@
+use crate::git::{PullRequest, RemoteCommit};
+use crate::http::{fetch_all_pages, http_get, parse_json, unexpected_response, HttpResponse};
+use crate::tickets::{TicketStatus, TicketTracker};
+use core::ops::Range;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    // Issue references in the current repository and in other repositories.
+    // #123
+    // tombruijn/lintje#123
+    // References that are part of a word, URL or HTML entity, like `&#123;`, are not matched.
+    static ref GITHUB_ISSUE: Regex =
+        Regex::new(r"(?:^|[^\w&/#.:-])((?:[\w.-]+/[\w.-]+)?#\d+)\b").unwrap();
+    // Repository name from the remote URL.
+    // https://github.com/tombruijn/lintje.git
+    // git@github.com:tombruijn/lintje.git
+    static ref GITHUB_REMOTE_URL: Regex =
+        Regex::new(r"github\.com[:/]([\w.-]+/[\w.-]+?)(?:\.git)?/?$").unwrap();
+    // Pull request reference, like `tombruijn/lintje#123`.
+    static ref GITHUB_PULL_REQUEST: Regex =
+        Regex::new(r"^([\w.-]+/[\w.-]+)#(\d+)$").unwrap();
+}
+
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+// The maximum number of commits the GitHub API returns per page.
+const COMMITS_PER_PAGE: usize = 100;
+
+/// Looks up issues and pull requests in GitHub using the GitHub REST API.
+#[derive(Debug)]
+pub struct GitHub {
+    api_url: String,
+    repository: Option<String>,
+    token: Option<String>,
+    cache: Mutex<HashMap<String, TicketStatus>>,
+}
+
+impl GitHub {
+    pub fn new(api_url: &str, repository: Option<String>, token: Option<String>) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            repository,
+            token,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// A pull request in a GitHub repository, like `tombruijn/lintje#123`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestReference {
+    pub repository: String,
+    pub number: u32,
+}
+
+impl PullRequestReference {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid pull request: {}. Use the `owner/repo#number` format.",
+                value
+            )
+        };
+        let captures = GITHUB_PULL_REQUEST
+            .captures(value.trim())
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            repository: captures[1].to_string(),
+            number: captures[2].parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl GitHub {
+    /// Fetch the branch name and commits of the pull request with the GitHub API, so the commits
+    /// don't need to be available in the local repository.
+    ///
+    /// The files changed in the commits are not fetched, to avoid a request per commit.
+    pub fn fetch_pull_request(
+        &self,
+        reference: &PullRequestReference,
+    ) -> Result<PullRequest, String> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.api_url, reference.repository, reference.number
+        );
+        let response = self.get(&url)?;
+        let branch = match response.status {
+            200 => parse_pull_request_branch(&response.body)?,
+            404 => {
+                return Err(format!(
+                    "Pull request not found on GitHub: {}#{}",
+                    reference.repository, reference.number
+                ))
+            }
+            _ => return Err(unexpected_response("GitHub", "token", &response)),
+        };
+        let mut commits = fetch_all_pages(|page| {
+            let response = self.get(&format!(
+                "{}/commits?per_page={}&page={}",
+                url, COMMITS_PER_PAGE, page
+            ))?;
+            if response.status != 200 {
+                return Err(unexpected_response("GitHub", "token", &response));
+            }
+            let commits = parse_pull_request_commits(&response.body)?;
+            // A page with less commits than requested is the last page
+            let next_page = commits.len() == COMMITS_PER_PAGE;
+            Ok((commits, next_page))
+        })?;
+        // GitHub lists the oldest commit first
+        commits.reverse();
+        Ok(PullRequest { branch, commits })
+    }
+
+    fn get(&self, url: &str) -> Result<HttpResponse, String> {
+        let authorization = self
+            .token
+            .as_ref()
+            .map(|token| format!("Authorization: Bearer {}", token));
+        let mut curl_options = vec![("header", "Accept: application/vnd.github+json")];
+        if let Some(authorization) = &authorization {
+            curl_options.push(("header", authorization));
+        }
+        http_get(url, &curl_options)
+    }
+}
+
+impl TicketTracker for GitHub {
+    fn name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn ticket_references(&self, text: &str) -> Vec<Range<usize>> {
+        GITHUB_ISSUE
+            .captures_iter(text)
+            .filter_map(|captures| captures.get(1))
+            .map(|reference| reference.range())
+            .collect()
+    }
+
+    fn fetch_ticket_status(&self, reference: &str) -> Result<TicketStatus, String> {
+        let (repository, number) = match reference.split_once('#') {
+            Some(("", number)) => match &self.repository {
+                Some(repository) => (repository.as_str(), number),
+                None => {
+                    return Err("Unable to determine the GitHub repository. \
+                        Configure it with the `--github-repository` option."
+                        .to_string())
+                }
+            },
+            Some((repository, number)) => (repository, number),
+            None => return Err(format!("Invalid issue reference: {}", reference)),
+        };
+        let url = format!("{}/repos/{}/issues/{}", self.api_url, repository, number);
+        let response = self.get(&url)?;
+        match response.status {
+            200 => parse_issue_status(&response.body),
+            // Deleted issues return the 410 Gone status
+            404 | 410 => Ok(TicketStatus::Missing),
+            _ => Err(unexpected_response("GitHub", "token", &response)),
+        }
+    }
+
+    fn cache(&self) -> &Mutex<HashMap<String, TicketStatus>> {
+        &self.cache
+    }
+}
+
+/// Returns the GitHub repository name, like `tombruijn/lintje`, from a Git remote URL.
+pub fn repository_from_remote_url(url: &str) -> Option<String> {
+    GITHUB_REMOTE_URL
+        .captures(url.trim())
+        .and_then(|captures| captures.get(1))
+        .map(|repository| repository.as_str().to_string())
+}
+
+fn parse_pull_request_branch(body: &str) -> Result<String, String> {
+    match parse_json("GitHub", body)?["head"]["ref"].as_str() {
+        Some(branch) => Ok(branch.to_string()),
+        None => Err(format!(
+            "Unable to find pull request branch in GitHub response: {}",
+            body
+        )),
+    }
+}
+
+fn parse_pull_request_commits(body: &str) -> Result<Vec<RemoteCommit>, String> {
+    let json = parse_json("GitHub", body)?;
+    let commits = match json.as_array() {
+        Some(commits) => commits,
+        None => {
+            return Err(format!(
+                "Unable to find commits in GitHub response: {}",
+                body
+            ))
+        }
+    };
+    commits
+        .iter()
+        .map(|commit| {
+            let message = commit["commit"]["message"].as_str().ok_or_else(|| {
+                format!("Unable to find commit message in GitHub response: {}", body)
+            })?;
+            let author = &commit["commit"]["author"];
+            Ok(RemoteCommit::from_api(
+                commit["sha"].as_str().map(String::from),
+                author["email"].as_str().map(String::from),
+                author["name"].as_str().map(String::from),
+                message.to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn parse_issue_status(body: &str) -> Result<TicketStatus, String> {
+    match parse_json("GitHub", body)?["state"].as_str() {
+        Some("closed") => Ok(TicketStatus::Closed),
+        Some(_) => Ok(TicketStatus::Open),
+        None => Err(format!(
+            "Unable to find issue state in GitHub response: {}",
+            body
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_issue_status, parse_pull_request_branch, parse_pull_request_commits,
+        repository_from_remote_url, GitHub, PullRequestReference, DEFAULT_API_URL,
+    };
+    use crate::git::RemoteCommit;
+    use crate::tickets::{TicketStatus, TicketTracker};
+
+    fn references(text: &str) -> Vec<String> {
+        GitHub::new(DEFAULT_API_URL, None, None)
+            .ticket_references(text)
+            .into_iter()
+            .map(|range| text[range].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_ticket_references() {
+        assert_eq!(
+            references("#1 Fixes #123, part of tombruijn/lintje#45 (#6)"),
+            vec!["#1", "#123", "tombruijn/lintje#45", "#6"]
+        );
+        assert_eq!(
+            references("Not issue#1, &#123; or https://example.com/page#2 or ##3"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_repository_from_remote_url() {
+        assert_eq!(
+            repository_from_remote_url("https://github.com/tombruijn/lintje.git\n"),
+            Some("tombruijn/lintje".to_string())
+        );
+        assert_eq!(
+            repository_from_remote_url("git@github.com:tombruijn/lintje.git"),
+            Some("tombruijn/lintje".to_string())
+        );
+        assert_eq!(
+            repository_from_remote_url("https://github.com/tombruijn/lintje"),
+            Some("tombruijn/lintje".to_string())
+        );
+        assert_eq!(
+            repository_from_remote_url("https://gitlab.com/tombruijn/lintje.git"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_issue_status() {
+        assert_eq!(
+            parse_issue_status(r#"{"number":1,"state":"open"}"#),
+            Ok(TicketStatus::Open)
+        );
+        assert_eq!(
+            parse_issue_status(r#"{"number":1,"state":"closed"}"#),
+            Ok(TicketStatus::Closed)
+        );
+        assert!(parse_issue_status("{}").is_err());
+    }
+
+    #[test]
+    fn test_pull_request_reference() {
+        assert_eq!(
+            PullRequestReference::parse("tombruijn/lintje#123"),
+            Ok(PullRequestReference {
+                repository: "tombruijn/lintje".to_string(),
+                number: 123
+            })
+        );
+        assert!(PullRequestReference::parse("tombruijn/lintje").is_err());
+        assert!(PullRequestReference::parse("#123").is_err());
+        assert!(PullRequestReference::parse("tombruijn/lintje#abc").is_err());
+        assert!(PullRequestReference::parse("tombruijn/lintje#99999999999").is_err());
+    }
+
+    #[test]
+    fn test_parse_pull_request_branch() {
+        assert_eq!(
+            parse_pull_request_branch(r#"{"number":1,"head":{"ref":"login-form"}}"#),
+            Ok("login-form".to_string())
+        );
+        assert!(parse_pull_request_branch(r#"{"number":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_pull_request_commits() {
+        let body = r#"[{
+            "sha": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "commit": {
+                "author": {"name": "Tom", "email": "tom@example.com"},
+                "message": "Add the login form\n\nUsers can now log in."
+            }
+        }]"#;
+        assert_eq!(
+            parse_pull_request_commits(body),
+            Ok(vec![RemoteCommit {
+                sha: Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                email: Some("tom@example.com".to_string()),
+                author_name: Some("Tom".to_string()),
+                message: "Add the login form\n\nUsers can now log in.".to_string(),
+                has_changes: true,
+            }])
+        );
+        assert_eq!(parse_pull_request_commits("[]"), Ok(vec![]));
+        assert!(parse_pull_request_commits(r#"{"message":"Not Found"}"#).is_err());
+        assert!(parse_pull_request_commits(r#"[{"sha":"a"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_fetch_without_repository() {
+        let github = GitHub::new(DEFAULT_API_URL, None, None);
+        assert!(github.fetch_ticket_status("#1").is_err());
+    }
+}
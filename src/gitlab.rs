@@ -0,0 +1,222 @@
+use crate::git::{PullRequest, RemoteCommit};
+use crate::http::{fetch_all_pages, http_get, parse_json, unexpected_response, HttpResponse};
+use regex::Regex;
+
+lazy_static! {
+    // Merge request reference, like `tombruijn/lintje!123`. The project can be in a subgroup, or
+    // be the numeric project ID.
+    static ref GITLAB_MERGE_REQUEST: Regex = Regex::new(r"^([\w./-]+)!(\d+)$").unwrap();
+}
+
+pub const DEFAULT_API_URL: &str = "https://gitlab.com/api/v4";
+// The maximum number of commits the GitLab API returns per page.
+const COMMITS_PER_PAGE: usize = 100;
+
+/// The token to authenticate with the GitLab API.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitLabToken {
+    /// A personal, project or group access token.
+    Private(String),
+    /// The `CI_JOB_TOKEN` of a GitLab CI job.
+    Job(String),
+}
+
+/// A merge request in a GitLab project, like `tombruijn/lintje!123`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeRequestReference {
+    pub project: String,
+    pub iid: u32,
+}
+
+impl MergeRequestReference {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid merge request: {}. Use the `group/project!number` format.",
+                value
+            )
+        };
+        let captures = GITLAB_MERGE_REQUEST
+            .captures(value.trim())
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            project: captures[1].to_string(),
+            iid: captures[2].parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Fetches merge requests using the GitLab REST API.
+#[derive(Debug)]
+pub struct GitLab {
+    api_url: String,
+    token: Option<GitLabToken>,
+}
+
+impl GitLab {
+    pub fn new(api_url: &str, token: Option<GitLabToken>) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// Fetch the branch name and commits of the merge request with the GitLab API, so the commits
+    /// don't need to be available in the local repository.
+    ///
+    /// The files changed in the commits are not fetched, to avoid a request per commit.
+    pub fn fetch_merge_request(
+        &self,
+        reference: &MergeRequestReference,
+    ) -> Result<PullRequest, String> {
+        let url = format!(
+            "{}/projects/{}/merge_requests/{}",
+            self.api_url,
+            reference.project.replace('/', "%2F"),
+            reference.iid
+        );
+        let response = self.get(&url)?;
+        let branch = match response.status {
+            200 => parse_merge_request_branch(&response.body)?,
+            404 => {
+                return Err(format!(
+                    "Merge request not found on GitLab: {}!{}",
+                    reference.project, reference.iid
+                ))
+            }
+            _ => return Err(unexpected_response("GitLab", "token", &response)),
+        };
+        let commits = fetch_all_pages(|page| {
+            let response = self.get(&format!(
+                "{}/commits?per_page={}&page={}",
+                url, COMMITS_PER_PAGE, page
+            ))?;
+            if response.status != 200 {
+                return Err(unexpected_response("GitLab", "token", &response));
+            }
+            let commits = parse_merge_request_commits(&response.body)?;
+            // A page with less commits than requested is the last page
+            let next_page = commits.len() == COMMITS_PER_PAGE;
+            Ok((commits, next_page))
+        })?;
+        // GitLab lists the newest commit first, like Git
+        Ok(PullRequest { branch, commits })
+    }
+
+    fn get(&self, url: &str) -> Result<HttpResponse, String> {
+        let header = match &self.token {
+            Some(GitLabToken::Private(token)) => Some(format!("PRIVATE-TOKEN: {}", token)),
+            Some(GitLabToken::Job(token)) => Some(format!("JOB-TOKEN: {}", token)),
+            None => None,
+        };
+        let mut curl_options = vec![];
+        if let Some(header) = &header {
+            curl_options.push(("header", header.as_str()));
+        }
+        http_get(url, &curl_options)
+    }
+}
+
+fn parse_merge_request_branch(body: &str) -> Result<String, String> {
+    match parse_json("GitLab", body)?["source_branch"].as_str() {
+        Some(branch) => Ok(branch.to_string()),
+        None => Err(format!(
+            "Unable to find merge request branch in GitLab response: {}",
+            body
+        )),
+    }
+}
+
+fn parse_merge_request_commits(body: &str) -> Result<Vec<RemoteCommit>, String> {
+    let json = parse_json("GitLab", body)?;
+    let commits = match json.as_array() {
+        Some(commits) => commits,
+        None => {
+            return Err(format!(
+                "Unable to find commits in GitLab response: {}",
+                body
+            ))
+        }
+    };
+    commits
+        .iter()
+        .map(|commit| {
+            let message = commit["message"].as_str().ok_or_else(|| {
+                format!("Unable to find commit message in GitLab response: {}", body)
+            })?;
+            Ok(RemoteCommit::from_api(
+                commit["id"].as_str().map(String::from),
+                commit["author_email"].as_str().map(String::from),
+                commit["author_name"].as_str().map(String::from),
+                message.to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_merge_request_branch, parse_merge_request_commits, MergeRequestReference};
+    use crate::git::RemoteCommit;
+
+    #[test]
+    fn test_merge_request_reference() {
+        assert_eq!(
+            MergeRequestReference::parse("tombruijn/lintje!123"),
+            Ok(MergeRequestReference {
+                project: "tombruijn/lintje".to_string(),
+                iid: 123
+            })
+        );
+        assert_eq!(
+            MergeRequestReference::parse("group/subgroup/project!1"),
+            Ok(MergeRequestReference {
+                project: "group/subgroup/project".to_string(),
+                iid: 1
+            })
+        );
+        assert_eq!(
+            MergeRequestReference::parse("42!1"),
+            Ok(MergeRequestReference {
+                project: "42".to_string(),
+                iid: 1
+            })
+        );
+        assert!(MergeRequestReference::parse("tombruijn/lintje").is_err());
+        assert!(MergeRequestReference::parse("!123").is_err());
+        assert!(MergeRequestReference::parse("tombruijn/lintje#123").is_err());
+    }
+
+    #[test]
+    fn test_parse_merge_request_branch() {
+        assert_eq!(
+            parse_merge_request_branch(r#"{"iid":1,"source_branch":"login-form"}"#),
+            Ok("login-form".to_string())
+        );
+        assert!(parse_merge_request_branch(r#"{"iid":1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_merge_request_commits() {
+        let body = r#"[{
+            "id": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "title": "Add the login form",
+            "message": "Add the login form\n\nUsers can now log in.",
+            "author_name": "Tom",
+            "author_email": "tom@example.com"
+        }]"#;
+        assert_eq!(
+            parse_merge_request_commits(body),
+            Ok(vec![RemoteCommit {
+                sha: Some("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                email: Some("tom@example.com".to_string()),
+                author_name: Some("Tom".to_string()),
+                message: "Add the login form\n\nUsers can now log in.".to_string(),
+                has_changes: true,
+            }])
+        );
+        assert_eq!(parse_merge_request_commits("[]"), Ok(vec![]));
+        assert!(parse_merge_request_commits(r#"{"message":"404 Not found"}"#).is_err());
+        assert!(parse_merge_request_commits(r#"[{"id":"a"}]"#).is_err());
+    }
+}
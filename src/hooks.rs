@@ -0,0 +1,68 @@
+use crate::config::HookScript;
+
+const COMMIT_MSG: &str = r#"#!/bin/sh
+# Lint the commit message with Lintje, after the message is written.
+lintje --hook-message-file="$1"
+"#;
+
+const PREPARE_COMMIT_MSG: &str = r#"#!/bin/sh
+# Lint the commit message with Lintje, before the message editor is opened.
+lintje --hook-message-file="$1" ${2:+--hook-commit-source="$2"}
+"#;
+
+const PRE_PUSH: &str = r#"#!/bin/sh
+# Lint the commits that are about to be pushed with Lintje.
+zero=$(git hash-object --stdin </dev/null | tr "0-9a-f" "0")
+while read -r local_ref local_sha remote_ref remote_sha; do
+  if [ "$local_sha" = "$zero" ]; then
+    # The remote branch is deleted, there are no commits to lint
+    continue
+  fi
+  if [ "$remote_sha" = "$zero" ]; then
+    # New remote branch, lint the commits that are not on the default branch
+    base=$(git merge-base origin/HEAD "$local_sha" 2>/dev/null)
+    if [ -n "$base" ]; then
+      range="$base..$local_sha"
+    else
+      range="$local_sha"
+    fi
+  else
+    range="$remote_sha..$local_sha"
+  fi
+  lintje "$range" || exit 1
+done
+"#;
+
+/// Returns the script of the Git hook that runs Lintje, to install in the `.git/hooks` directory
+/// or add to the config of a hook manager.
+pub fn hook_script(hook: HookScript) -> &'static str {
+    match hook {
+        HookScript::CommitMsg => COMMIT_MSG,
+        HookScript::PrepareCommitMsg => PREPARE_COMMIT_MSG,
+        HookScript::PrePush => PRE_PUSH,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hook_script;
+    use crate::config::HookScript;
+    use std::process::Command;
+
+    #[test]
+    fn test_hook_script_syntax() {
+        for hook in [
+            HookScript::CommitMsg,
+            HookScript::PrepareCommitMsg,
+            HookScript::PrePush,
+        ] {
+            let script = hook_script(hook);
+            assert!(script.starts_with("#!/bin/sh\n"));
+            let output = Command::new("sh")
+                .args(["-n", "-c", script])
+                .output()
+                .expect("Could not check hook script syntax");
+            assert!(output.status.success(), "Invalid script: {:?}", hook);
+        }
+    }
+}
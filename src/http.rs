@@ -0,0 +1,154 @@
+use crate::command::run_command_with_input;
+
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Perform a HTTP GET request using curl.
+///
+/// The curl options, like `user` and `header`, are passed to curl as a config file on STDIN, so
+/// that credentials don't show up in the process list.
+pub fn http_get(url: &str, curl_options: &[(&str, &str)]) -> Result<HttpResponse, String> {
+    let config = curl_options
+        .iter()
+        .map(|(name, value)| format!("{} = \"{}\"\n", name, escape_curl_config(value)))
+        .collect::<String>();
+    let output = run_command_with_input(
+        "curl",
+        &[
+            "--silent",
+            "--show-error",
+            "--location",
+            "--max-time",
+            "10",
+            "--config",
+            "-",
+            "--write-out",
+            "\n%{http_code}",
+            url,
+        ],
+        Some(&config),
+    )
+    .map_err(|e| e.message)?;
+    parse_http_response(&output)
+}
+
+/// Returns the error for a response of an API, like the GitHub API, with an unexpected status.
+///
+/// The credentials are the credentials the service was configured with, like `token`.
+pub fn unexpected_response(service: &str, credentials: &str, response: &HttpResponse) -> String {
+    match response.status {
+        401 | 403 => format!(
+            "Unable to authenticate with {} (HTTP status {}). Check the {} {}.",
+            service, response.status, service, credentials
+        ),
+        status => format!(
+            "Unexpected response from {} (HTTP status {}): {}",
+            service, status, response.body
+        ),
+    }
+}
+
+pub fn parse_json(service: &str, body: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(body).map_err(|e| format!("Unable to parse {} response: {}", service, e))
+}
+
+/// Fetch every page of a paginated API, starting at page 1.
+///
+/// The page is fetched with the closure, which returns the items on the page and if there is a
+/// next page.
+pub fn fetch_all_pages<T, F>(mut fetch_page: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(usize) -> Result<(Vec<T>, bool), String>,
+{
+    let mut items = vec![];
+    let mut page = 1;
+    loop {
+        let (page_items, next_page) = fetch_page(page)?;
+        items.extend(page_items);
+        if !next_page {
+            return Ok(items);
+        }
+        page += 1;
+    }
+}
+
+fn escape_curl_config(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// The response body is followed by the HTTP status code on the last line, as configured with the
+// `--write-out` option.
+fn parse_http_response(output: &str) -> Result<HttpResponse, String> {
+    let (body, status) = match output.rsplit_once('\n') {
+        Some(parts) => parts,
+        None => ("", output),
+    };
+    match status.trim().parse::<u16>() {
+        Ok(status) => Ok(HttpResponse {
+            status,
+            body: body.to_string(),
+        }),
+        Err(_) => Err(format!("Unable to parse HTTP response status: {}", status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        escape_curl_config, fetch_all_pages, parse_http_response, unexpected_response, HttpResponse,
+    };
+
+    #[test]
+    fn test_parse_http_response() {
+        let response = parse_http_response("{\"key\":\n\"value\"}\n200").unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{\"key\":\n\"value\"}");
+
+        let response = parse_http_response("404").unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "");
+
+        assert!(parse_http_response("body\n000x").is_err());
+    }
+
+    #[test]
+    fn test_unexpected_response() {
+        let response = |status| HttpResponse {
+            status,
+            body: "Oops".to_string(),
+        };
+        assert_eq!(
+            unexpected_response("GitHub", "token", &response(401)),
+            "Unable to authenticate with GitHub (HTTP status 401). Check the GitHub token."
+        );
+        assert_eq!(
+            unexpected_response("GitHub", "token", &response(500)),
+            "Unexpected response from GitHub (HTTP status 500): Oops"
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_pages() {
+        let mut pages = vec![];
+        let items = fetch_all_pages(|page| {
+            pages.push(page);
+            Ok((vec![page * 10, page * 10 + 1], page < 3))
+        });
+        assert_eq!(items, Ok(vec![10, 11, 20, 21, 30, 31]));
+        assert_eq!(pages, vec![1, 2, 3]);
+
+        let items: Result<Vec<usize>, String> = fetch_all_pages(|_| Err("Oops".to_string()));
+        assert_eq!(items, Err("Oops".to_string()));
+    }
+
+    #[test]
+    fn test_escape_curl_config() {
+        assert_eq!(
+            escape_curl_config("Authorization: Basic \"a\\b\""),
+            "Authorization: Basic \\\"a\\\\b\\\""
+        );
+    }
+}
@@ -0,0 +1,692 @@
+//! Translations of the issue messages and the hints in their context. Rules report their issues
+//! in English, which are translated before they are printed. The rule names are not translated,
+//! so that tools processing the output can rely on them.
+//!
+//! ```
+//! use lintje::i18n::{translate, Language};
+//!
+//! assert_eq!(
+//!     translate("The subject contains a ticket number", Language::Nl),
+//!     "Het onderwerp bevat een ticketnummer"
+//! );
+//! ```
+use crate::issue::Issue;
+use regex::Regex;
+
+/// The language issue messages are printed in.
+#[cfg_attr(feature = "cli", derive(clap::ArgEnum))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    /// English, the language the rules report issues in.
+    En,
+    /// Dutch.
+    Nl,
+}
+
+// The English messages and their Dutch translation. The `{}` placeholders match any text, which
+// is inserted in the translation in the same order. More specific messages are listed before
+// messages they overlap with.
+const DUTCH: &[(&str, &str)] = &[
+    // Commit type rules
+    (
+        "A remote merge commit was found",
+        "Er is een merge commit van de remote gevonden",
+    ),
+    (
+        "Rebase on the remote branch, rather than merging the remote branch into the local branch",
+        "Rebase op de remote branch, in plaats van de remote branch in de lokale branch te mergen",
+    ),
+    (
+        "A fixup commit was found",
+        "Er is een fixup commit gevonden",
+    ),
+    (
+        "A squash commit was found",
+        "Er is een squash commit gevonden",
+    ),
+    (
+        "Rebase fixup commits before pushing or merging",
+        "Rebase fixup commits voor het pushen of mergen",
+    ),
+    (
+        "Rebase squash commits before pushing or merging",
+        "Rebase squash commits voor het pushen of mergen",
+    ),
+    (
+        "The target commit of the {} commit was not found",
+        "De doelcommit van de {} commit is niet gevonden",
+    ),
+    (
+        "No older commit with this subject was found to squash this commit into",
+        "Er is geen oudere commit met dit onderwerp gevonden om deze commit mee samen te voegen",
+    ),
+    (
+        "The revert commit does not explain why the change was reverted",
+        "De revert commit legt niet uit waarom de wijziging is teruggedraaid",
+    ),
+    (
+        "Explain why the change was reverted below this line",
+        "Leg onder deze regel uit waarom de wijziging is teruggedraaid",
+    ),
+    (
+        "Explain why the change was reverted",
+        "Leg uit waarom de wijziging is teruggedraaid",
+    ),
+    ("The commit is not signed", "De commit is niet ondertekend"),
+    (
+        "The commit has a bad signature",
+        "De commit heeft een ongeldige handtekening",
+    ),
+    (
+        "Sign the commit with `git commit --amend --gpg-sign`",
+        "Onderteken de commit met `git commit --amend --gpg-sign`",
+    ),
+    (
+        "Sign the commit again with `git commit --amend --gpg-sign`",
+        "Onderteken de commit opnieuw met `git commit --amend --gpg-sign`",
+    ),
+    // Commit subject rules
+    (
+        "The commit has no subject",
+        "De commit heeft geen onderwerp",
+    ),
+    (
+        "Add a subject to describe the change",
+        "Voeg een onderwerp toe dat de wijziging beschrijft",
+    ),
+    (
+        "The subject of `{}` characters wide is too long",
+        "Het onderwerp van `{}` tekens breed is te lang",
+    ),
+    (
+        "The subject of `{}` characters wide is too short",
+        "Het onderwerp van `{}` tekens breed is te kort",
+    ),
+    (
+        "Shorten the subject to a maximum width of {} characters",
+        "Kort het onderwerp in tot maximaal {} tekens breed",
+    ),
+    (
+        "The subject does not use the imperative grammatical mood",
+        "Het onderwerp staat niet in de gebiedende wijs",
+    ),
+    (
+        "Use the imperative mood for the subject",
+        "Gebruik de gebiedende wijs voor het onderwerp",
+    ),
+    (
+        "The subject starts with a whitespace character such as a space or a tab",
+        "Het onderwerp begint met witruimte, zoals een spatie of een tab",
+    ),
+    (
+        "Remove the leading whitespace from the subject",
+        "Verwijder de witruimte aan het begin van het onderwerp",
+    ),
+    (
+        "Remove the `{}` prefix from the subject",
+        "Verwijder het voorvoegsel `{}` uit het onderwerp",
+    ),
+    (
+        "Remove the prefix from the subject",
+        "Verwijder het voorvoegsel uit het onderwerp",
+    ),
+    (
+        "The subject does not start with a capital letter",
+        "Het onderwerp begint niet met een hoofdletter",
+    ),
+    (
+        "Start the subject with a capital letter",
+        "Begin het onderwerp met een hoofdletter",
+    ),
+    (
+        "The `{}` build tag was found in the subject",
+        "De build tag `{}` is gevonden in het onderwerp",
+    ),
+    (
+        "Remove the build tag from the subject",
+        "Verwijder de build tag uit het onderwerp",
+    ),
+    (
+        "Move build tag to message body",
+        "Verplaats de build tag naar de berichttekst",
+    ),
+    (
+        "The subject starts with a punctuation character: `{}`",
+        "Het onderwerp begint met een leesteken: `{}`",
+    ),
+    (
+        "The subject ends with a punctuation character: `{}`",
+        "Het onderwerp eindigt met een leesteken: `{}`",
+    ),
+    (
+        "Remove punctuation from the start of the subject",
+        "Verwijder het leesteken aan het begin van het onderwerp",
+    ),
+    (
+        "Remove punctuation from the end of the subject",
+        "Verwijder het leesteken aan het eind van het onderwerp",
+    ),
+    (
+        "The subject starts with an emoji",
+        "Het onderwerp begint met een emoji",
+    ),
+    (
+        "Remove emoji from the start of the subject",
+        "Verwijder de emoji aan het begin van het onderwerp",
+    ),
+    (
+        "The subject contains a ticket number",
+        "Het onderwerp bevat een ticketnummer",
+    ),
+    (
+        "Remove the ticket number from the subject",
+        "Verwijder het ticketnummer uit het onderwerp",
+    ),
+    (
+        "Move the ticket number to the message body",
+        "Verplaats het ticketnummer naar de berichttekst",
+    ),
+    (
+        "The subject does not contain a ticket number",
+        "Het onderwerp bevat geen ticketnummer",
+    ),
+    (
+        "Add a ticket number to the subject",
+        "Voeg een ticketnummer toe aan het onderwerp",
+    ),
+    (
+        "The subject does not explain the change in much detail",
+        "Het onderwerp beschrijft de wijziging niet in detail",
+    ),
+    (
+        "Describe the change in more detail",
+        "Beschrijf de wijziging in meer detail",
+    ),
+    (
+        "The subject contains the misspelled word `{}`",
+        "Het onderwerp bevat het verkeerd gespelde woord `{}`",
+    ),
+    ("Did you mean `{}`?", "Bedoelde je `{}`?"),
+    (
+        "The subject is similar to the subject of commit {}",
+        "Het onderwerp lijkt op het onderwerp van commit {}",
+    ),
+    (
+        "Consider squashing this commit into commit {}: {}",
+        "Overweeg om deze commit samen te voegen met commit {}: {}",
+    ),
+    (
+        "The subject contains the confusable character `{}` ({})",
+        "Het onderwerp bevat het verwarrende teken `{}` ({})",
+    ),
+    (
+        "The subject contains the invisible character {}",
+        "Het onderwerp bevat het onzichtbare teken {}",
+    ),
+    (
+        "The subject contains the forbidden phrase `{}`",
+        "Het onderwerp bevat de verboden zin `{}`",
+    ),
+    (
+        "The subject contains the non-ASCII character `{}`",
+        "Het onderwerp bevat het niet-ASCII-teken `{}`",
+    ),
+    (
+        "The subject contains the non-ASCII characters `{}`",
+        "Het onderwerp bevat de niet-ASCII-tekens `{}`",
+    ),
+    ("Replace with ASCII characters", "Vervang door ASCII-tekens"),
+    (
+        "The subject contains the repeated word `{}`",
+        "Het onderwerp bevat het herhaalde woord `{}`",
+    ),
+    ("Remove the repeated word", "Verwijder het herhaalde woord"),
+    (
+        "The subject is written in all caps",
+        "Het onderwerp is in hoofdletters geschreven",
+    ),
+    (
+        "Write the subject in sentence case, like `Fix login bug`",
+        "Schrijf het onderwerp met alleen een hoofdletter aan het begin, zoals `Fix login bug`",
+    ),
+    (
+        "The subject contains fewer than {} words",
+        "Het onderwerp bevat minder dan {} woorden",
+    ),
+    (
+        "Describe the change in more words",
+        "Beschrijf de wijziging in meer woorden",
+    ),
+    ("The subject is truncated", "Het onderwerp is afgekapt"),
+    (
+        "Restore the full subject, or move the rest of the sentence to the message body",
+        "Herstel het volledige onderwerp, of verplaats de rest van de zin naar de berichttekst",
+    ),
+    // Commit message rules
+    (
+        "No empty line found below the subject",
+        "Er is geen lege regel onder het onderwerp gevonden",
+    ),
+    (
+        "Add an empty line below the subject line",
+        "Voeg een lege regel toe onder het onderwerp",
+    ),
+    (
+        "No message body was found",
+        "Er is geen berichttekst gevonden",
+    ),
+    (
+        "Add a message body with context about the change and why it was made",
+        "Voeg een berichttekst toe met context over de wijziging en waarom die is gemaakt",
+    ),
+    (
+        "The message body is too short",
+        "De berichttekst is te kort",
+    ),
+    (
+        "Add a longer message with context about the change and why it was made",
+        "Voeg een langere berichttekst toe met context over de wijziging en waarom die is gemaakt",
+    ),
+    (
+        "The message body contains fewer than {} paragraphs",
+        "De berichttekst bevat minder dan {} alinea's",
+    ),
+    (
+        "Add a paragraph that explains why the change was made",
+        "Voeg een alinea toe die uitlegt waarom de wijziging is gemaakt",
+    ),
+    (
+        "Line {} in the message body is longer than 72 characters",
+        "Regel {} in de berichttekst is langer dan 72 tekens",
+    ),
+    (
+        "Lines {} in the message body are longer than 72 characters",
+        "Regels {} in de berichttekst zijn langer dan 72 tekens",
+    ),
+    (
+        "Shorten line to maximum 72 characters",
+        "Kort de regel in tot maximaal 72 tekens",
+    ),
+    (
+        "The message body does not contain a ticket or issue number",
+        "De berichttekst bevat geen ticket- of issuenummer",
+    ),
+    (
+        "Consider adding a reference to a ticket or issue",
+        "Overweeg een verwijzing naar een ticket of issue toe te voegen",
+    ),
+    (
+        "The message body does not contain the `{}` trailer",
+        "De berichttekst bevat de `{}` trailer niet",
+    ),
+    ("Add the `{}` trailer", "Voeg de `{}` trailer toe"),
+    (
+        "The message body does not contain a Signed-off-by trailer from the commit author",
+        "De berichttekst bevat geen Signed-off-by trailer van de auteur van de commit",
+    ),
+    (
+        "The Signed-off-by trailer does not match the commit author",
+        "De Signed-off-by trailer komt niet overeen met de auteur van de commit",
+    ),
+    (
+        "Sign off the commit with `git commit --signoff`",
+        "Onderteken de commit met `git commit --signoff`",
+    ),
+    ("Sign off as `{}`", "Onderteken als `{}`"),
+    (
+        "The Co-authored-by trailer is not in the `Name <email>` format",
+        "De Co-authored-by trailer heeft niet het formaat `Naam <e-mail>`",
+    ),
+    (
+        "Use the `Name <email>` format",
+        "Gebruik het formaat `Naam <e-mail>`",
+    ),
+    (
+        "The message body contains the invisible character {}",
+        "De berichttekst bevat het onzichtbare teken {}",
+    ),
+    (
+        "The message body contains the forbidden phrase `{}`",
+        "De berichttekst bevat de verboden zin `{}`",
+    ),
+    (
+        "Remove the invisible character",
+        "Verwijder het onzichtbare teken",
+    ),
+    ("Remove the forbidden phrase", "Verwijder de verboden zin"),
+    (
+        "The message body contains diff output",
+        "De berichttekst bevat diff-uitvoer",
+    ),
+    (
+        "Remove the diff from the message body",
+        "Verwijder de diff uit de berichttekst",
+    ),
+    (
+        "The subject contains a possible secret: {}",
+        "Het onderwerp bevat mogelijk een geheim: {}",
+    ),
+    (
+        "The message body contains a possible secret: {}",
+        "De berichttekst bevat mogelijk een geheim: {}",
+    ),
+    (
+        "Remove the secret and revoke it",
+        "Verwijder het geheim en trek het in",
+    ),
+    ("Replace with `{}`", "Vervang door `{}`"),
+    // Ticket reference rules
+    (
+        "The ticket {} does not exist in {}",
+        "Het ticket {} bestaat niet in {}",
+    ),
+    (
+        "The ticket {} is closed in {}",
+        "Het ticket {} is gesloten in {}",
+    ),
+    (
+        "Check the ticket reference for typos",
+        "Controleer de verwijzing naar het ticket op typefouten",
+    ),
+    ("Reference an open ticket", "Verwijs naar een open ticket"),
+    // Commit changes rules
+    (
+        "No file changes found",
+        "Er zijn geen gewijzigde bestanden gevonden",
+    ),
+    (
+        "Add changes to the commit or remove the commit",
+        "Voeg wijzigingen toe aan de commit of verwijder de commit",
+    ),
+    (
+        "The added file `{}` is {}, larger than the maximum of {}",
+        "Het toegevoegde bestand `{}` is {}, groter dan het maximum van {}",
+    ),
+    (
+        "Remove the file from the commit, or store it outside the repository",
+        "Verwijder het bestand uit de commit, of sla het op buiten de repository",
+    ),
+    (
+        "The lockfile `{}` changed without changes to `{}`",
+        "Het lockbestand `{}` is gewijzigd zonder wijzigingen aan `{}`",
+    ),
+    (
+        "Consider committing the lockfile changes with the manifest changes",
+        "Overweeg de wijzigingen aan het lockbestand samen met de wijzigingen aan het manifest te committen",
+    ),
+    (
+        "The file `{}` matches the forbidden path `{}`",
+        "Het bestand `{}` komt overeen met het verboden pad `{}`",
+    ),
+    (
+        "Remove the file from the commit",
+        "Verwijder het bestand uit de commit",
+    ),
+    (
+        "The commit changes {} files, more than the maximum of {}",
+        "De commit wijzigt {} bestanden, meer dan het maximum van {}",
+    ),
+    (
+        "Consider splitting the commit into smaller commits",
+        "Overweeg de commit op te splitsen in kleinere commits",
+    ),
+    (
+        "The commit changes files in {} top-level directories, more than the maximum of {}",
+        "De commit wijzigt bestanden in {} hoofdmappen, meer dan het maximum van {}",
+    ),
+    (
+        "Consider splitting the commit into a commit per change",
+        "Overweeg de commit op te splitsen in een commit per wijziging",
+    ),
+    // Branch rules
+    (
+        "Branch name of {} characters is too short",
+        "De branchnaam van {} tekens is te kort",
+    ),
+    (
+        "A ticket number was detected in the branch name",
+        "Er is een ticketnummer gevonden in de branchnaam",
+    ),
+    (
+        "Remove the ticket number from the branch name or expand the branch name with more details",
+        "Verwijder het ticketnummer uit de branchnaam of beschrijf de wijziging in de branchnaam",
+    ),
+    (
+        "The branch name does not contain a ticket number",
+        "De branchnaam bevat geen ticketnummer",
+    ),
+    (
+        "Add a ticket number to the branch name",
+        "Voeg een ticketnummer toe aan de branchnaam",
+    ),
+    (
+        "The branch name starts with a punctuation character",
+        "De branchnaam begint met een leesteken",
+    ),
+    (
+        "The branch name ends with a punctuation character",
+        "De branchnaam eindigt met een leesteken",
+    ),
+    (
+        "Remove punctuation from the start of the branch name",
+        "Verwijder het leesteken aan het begin van de branchnaam",
+    ),
+    (
+        "Remove punctuation from the end of the branch name",
+        "Verwijder het leesteken aan het eind van de branchnaam",
+    ),
+    (
+        "The branch name contains the uppercase character `{}`",
+        "De branchnaam bevat de hoofdletter `{}`",
+    ),
+    (
+        "The branch name contains the uppercase characters `{}`",
+        "De branchnaam bevat de hoofdletters `{}`",
+    ),
+    (
+        "The branch name contains the disallowed character `{}`",
+        "De branchnaam bevat het niet-toegestane teken `{}`",
+    ),
+    (
+        "The branch name contains the disallowed characters `{}`",
+        "De branchnaam bevat de niet-toegestane tekens `{}`",
+    ),
+    (
+        "Use letters, numbers, `-`, `/` or `_` instead",
+        "Gebruik in plaats daarvan letters, cijfers, `-`, `/` of `_`",
+    ),
+    (
+        "The branch name does not explain the change in much detail",
+        "De branchnaam beschrijft de wijziging niet in detail",
+    ),
+    (
+        "The branch name contains the forbidden word `{}`",
+        "De branchnaam bevat het verboden woord `{}`",
+    ),
+    (
+        "Remove the forbidden word from the branch name",
+        "Verwijder het verboden woord uit de branchnaam",
+    ),
+    (
+        "The branch name has {} segments, more than the maximum of {}",
+        "De branchnaam heeft {} segmenten, meer dan het maximum van {}",
+    ),
+    (
+        "The branch name has {} segment, fewer than the minimum of {}",
+        "De branchnaam heeft {} segment, minder dan het minimum van {}",
+    ),
+    (
+        "The branch name has {} segments, fewer than the minimum of {}",
+        "De branchnaam heeft {} segmenten, minder dan het minimum van {}",
+    ),
+    (
+        "Use at most {} `/` separated segment in the branch name",
+        "Gebruik maximaal {} met `/` gescheiden segment in de branchnaam",
+    ),
+    (
+        "Use at most {} `/` separated segments in the branch name",
+        "Gebruik maximaal {} met `/` gescheiden segmenten in de branchnaam",
+    ),
+    (
+        "Use at least {} `/` separated segment in the branch name",
+        "Gebruik minimaal {} met `/` gescheiden segment in de branchnaam",
+    ),
+    (
+        "Use at least {} `/` separated segments in the branch name",
+        "Gebruik minimaal {} met `/` gescheiden segmenten in de branchnaam",
+    ),
+    (
+        "The branch name looks automatically generated",
+        "De branchnaam lijkt automatisch gegenereerd",
+    ),
+    (
+        "Rename the branch to describe the change before opening a pull request",
+        "Hernoem de branch zodat die de wijziging beschrijft, voordat je een pull request opent",
+    ),
+    (
+        "The branch `{}` is a protected branch",
+        "De branch `{}` is een beschermde branch",
+    ),
+    (
+        "Move the commits to a new branch and open a pull request",
+        "Verplaats de commits naar een nieuwe branch en open een pull request",
+    ),
+    (
+        "The branch name contains the confusable character `{}` ({})",
+        "De branchnaam bevat het verwarrende teken `{}` ({})",
+    ),
+    (
+        "The branch name contains the invisible character {}",
+        "De branchnaam bevat het onzichtbare teken {}",
+    ),
+];
+
+lazy_static! {
+    static ref DUTCH_MESSAGES: Vec<(Regex, &'static str)> = compile(DUTCH);
+}
+
+// Turn the English messages into patterns that capture the text of the placeholders.
+fn compile(translations: &[(&str, &'static str)]) -> Vec<(Regex, &'static str)> {
+    translations
+        .iter()
+        .map(|(message, translation)| {
+            let pattern = regex::escape(message).replace(r"\{\}", "(.+?)");
+            (Regex::new(&format!("^{}$", pattern)).unwrap(), *translation)
+        })
+        .collect()
+}
+
+/// Translate the English message to the given language. Messages without a translation are
+/// returned as is.
+pub fn translate(message: &str, language: Language) -> String {
+    let translations = match language {
+        Language::En => return message.to_string(),
+        Language::Nl => &*DUTCH_MESSAGES,
+    };
+    for (pattern, translation) in translations {
+        if let Some(captures) = pattern.captures(message) {
+            let mut translated = String::new();
+            for (index, part) in translation.split("{}").enumerate() {
+                if index > 0 {
+                    if let Some(value) = captures.get(index) {
+                        translated.push_str(value.as_str());
+                    }
+                }
+                translated.push_str(part);
+            }
+            return translated;
+        }
+    }
+    debug!("No translation found for message: {}", message);
+    message.to_string()
+}
+
+/// Translate the message of the issue and the hints in its context to the given language.
+pub fn translate_issue(issue: &mut Issue, language: Language) {
+    if language == Language::En {
+        return;
+    }
+    issue.message = translate(&issue.message, language);
+    for context in &mut issue.context {
+        if let Some(message) = &context.message {
+            context.message = Some(translate(message, language));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compile, translate, translate_issue, Language, DUTCH};
+    use crate::issue::{Context, Issue, Position};
+    use crate::rule::Rule;
+    use core::ops::Range;
+
+    #[test]
+    fn test_translations_placeholders() {
+        for (message, translation) in DUTCH {
+            assert_eq!(
+                message.matches("{}").count(),
+                translation.matches("{}").count(),
+                "Placeholder count differs: {}",
+                message
+            );
+        }
+        assert_eq!(compile(DUTCH).len(), DUTCH.len());
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(
+            translate("The subject contains a ticket number", Language::En),
+            "The subject contains a ticket number"
+        );
+        assert_eq!(
+            translate(
+                "The subject of `72` characters wide is too long",
+                Language::Nl
+            ),
+            "Het onderwerp van `72` tekens breed is te lang"
+        );
+        assert_eq!(
+            translate("The ticket #123 is closed in GitHub", Language::Nl),
+            "Het ticket #123 is gesloten in GitHub"
+        );
+        assert_eq!(
+            translate(
+                "The branch name has 1 segment, fewer than the minimum of 2",
+                Language::Nl
+            ),
+            "De branchnaam heeft 1 segment, minder dan het minimum van 2"
+        );
+        // Messages without translation, like the advice of a forbidden phrase
+        assert_eq!(
+            translate("Use the word allowlist", Language::Nl),
+            "Use the word allowlist"
+        );
+    }
+
+    #[test]
+    fn test_translate_issue() {
+        let mut issue = Issue::error(
+            Rule::SubjectMood,
+            "The subject does not use the imperative grammatical mood".to_string(),
+            Position::Subject { line: 1, column: 1 },
+            vec![Context::subject_error(
+                "Fixed bug".to_string(),
+                Range { start: 0, end: 5 },
+                "Use the imperative mood for the subject".to_string(),
+            )],
+        );
+        translate_issue(&mut issue, Language::Nl);
+        assert_eq!(issue.rule, Rule::SubjectMood);
+        assert_eq!(
+            issue.message,
+            "Het onderwerp staat niet in de gebiedende wijs"
+        );
+        assert_eq!(issue.context[0].content, "Fixed bug");
+        assert_eq!(
+            issue.context[0].message,
+            Some("Gebruik de gebiedende wijs voor het onderwerp".to_string())
+        );
+    }
+}
@@ -94,6 +94,16 @@ impl Context {
         }
     }
 
+    pub fn subject_hint(content: String, range: Range<usize>, message: String) -> Self {
+        Self {
+            r#type: ContextType::Addition,
+            line: Some(1),
+            content,
+            range: Some(range),
+            message: Some(message),
+        }
+    }
+
     pub fn message_line(line: usize, content: String) -> Self {
         Self {
             r#type: ContextType::Plain,
@@ -144,6 +154,16 @@ impl Context {
         }
     }
 
+    pub fn diff_hint(content: String, range: Range<usize>, message: String) -> Self {
+        Self {
+            r#type: ContextType::Addition,
+            line: None,
+            content,
+            range: Some(range),
+            message: Some(message),
+        }
+    }
+
     pub fn branch_error(content: String, range: Range<usize>, message: String) -> Self {
         Self {
             r#type: ContextType::Error,
@@ -153,4 +173,14 @@ impl Context {
             message: Some(message),
         }
     }
+
+    pub fn branch_hint(content: String, range: Range<usize>, message: String) -> Self {
+        Self {
+            r#type: ContextType::Addition,
+            line: None,
+            content,
+            range: Some(range),
+            message: Some(message),
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use crate::http::{http_get, parse_json, unexpected_response};
+use crate::tickets::{TicketStatus, TicketTracker};
+use core::ops::Range;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    // Jira project keys start with an uppercase letter, followed by uppercase letters, digits or
+    // underscores.
+    // AB-123
+    // JIRA-123
+    static ref JIRA_TICKET: Regex = Regex::new(r"\b[A-Z][A-Z0-9_]+-\d+\b").unwrap();
+}
+
+/// Looks up tickets in Jira using the Jira REST API.
+#[derive(Debug)]
+pub struct Jira {
+    url: String,
+    user: Option<String>,
+    token: Option<String>,
+    projects: Vec<String>,
+    cache: Mutex<HashMap<String, TicketStatus>>,
+}
+
+impl Jira {
+    pub fn new(
+        url: &str,
+        user: Option<String>,
+        token: Option<String>,
+        projects: Vec<String>,
+    ) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            user,
+            token,
+            projects,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl TicketTracker for Jira {
+    fn name(&self) -> &str {
+        "Jira"
+    }
+
+    // Only tickets of the configured projects are references. Other text that looks like a
+    // ticket, like `UTF-8` and `SHA-256`, would otherwise be looked up as a ticket.
+    fn ticket_references(&self, text: &str) -> Vec<Range<usize>> {
+        JIRA_TICKET
+            .find_iter(text)
+            .filter(|ticket| {
+                self.projects
+                    .iter()
+                    .any(|project| ticket.as_str().starts_with(&format!("{}-", project)))
+            })
+            .map(|ticket| ticket.range())
+            .collect()
+    }
+
+    fn fetch_ticket_status(&self, reference: &str) -> Result<TicketStatus, String> {
+        let url = format!("{}/rest/api/2/issue/{}?fields=status", self.url, reference);
+        let credentials = match (&self.user, &self.token) {
+            (Some(user), Some(token)) => Some(format!("{}:{}", user, token)),
+            _ => None,
+        };
+        let curl_options = match &credentials {
+            Some(credentials) => vec![("user", credentials.as_str())],
+            None => vec![],
+        };
+        let response = http_get(&url, &curl_options)?;
+        match response.status {
+            200 => parse_ticket_status(&response.body),
+            404 => Ok(TicketStatus::Missing),
+            _ => Err(unexpected_response("Jira", "user and token", &response)),
+        }
+    }
+
+    fn cache(&self) -> &Mutex<HashMap<String, TicketStatus>> {
+        &self.cache
+    }
+}
+
+// Jira groups all workflow statuses in three categories: "new", "indeterminate" and "done".
+fn parse_ticket_status(body: &str) -> Result<TicketStatus, String> {
+    match parse_json("Jira", body)?["fields"]["status"]["statusCategory"]["key"].as_str() {
+        Some("done") => Ok(TicketStatus::Closed),
+        Some(_) => Ok(TicketStatus::Open),
+        None => Err(format!(
+            "Unable to find ticket status in Jira response: {}",
+            body
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ticket_status, Jira};
+    use crate::tickets::{TicketStatus, TicketTracker};
+
+    fn jira(projects: &[&str]) -> Jira {
+        Jira::new(
+            "https://example.atlassian.net/",
+            None,
+            None,
+            projects.iter().map(|p| p.to_string()).collect(),
+        )
+    }
+
+    fn references(jira: &Jira, text: &str) -> Vec<String> {
+        jira.ticket_references(text)
+            .into_iter()
+            .map(|range| text[range].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_ticket_references() {
+        let tracker = jira(&["JIRA", "AB"]);
+        assert_eq!(
+            references(&tracker, "Fixes JIRA-123 and AB-1, not ab-2 or AJIRA-3x"),
+            vec!["JIRA-123", "AB-1"]
+        );
+        assert_eq!(tracker.url, "https://example.atlassian.net");
+
+        let tracker = jira(&[]);
+        assert!(references(&tracker, "Fixes JIRA-123 and UTF-8").is_empty());
+
+        let tracker = jira(&["JIRA"]);
+        assert_eq!(
+            references(&tracker, "Fixes JIRA-123 and UTF-8"),
+            vec!["JIRA-123"]
+        );
+    }
+
+    #[test]
+    fn test_parse_ticket_status() {
+        let body = r#"{"key":"JIRA-1","fields":{"status":{"statusCategory":{"key":"done"}}}}"#;
+        assert_eq!(parse_ticket_status(body), Ok(TicketStatus::Closed));
+        let body =
+            r#"{"key":"JIRA-1","fields":{"status":{"statusCategory":{"key":"indeterminate"}}}}"#;
+        assert_eq!(parse_ticket_status(body), Ok(TicketStatus::Open));
+        assert!(parse_ticket_status("{}").is_err());
+        assert!(parse_ticket_status("not json").is_err());
+    }
+}
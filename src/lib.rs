@@ -0,0 +1,77 @@
+//! Lintje is an opinionated linter for Git. This library contains the rules Lintje validates
+//! commits and branches with, for tools that want to validate commits without running the
+//! `lintje` executable.
+//!
+//! ```
+//! use lintje::commit::Commit;
+//! use lintje::options::RuleOptions;
+//! use lintje::rule::Rule;
+//!
+//! let mut commit = Commit::new(
+//!     None,
+//!     None,
+//!     "Fixing tests",
+//!     "The tests were broken by a typo.".to_string(),
+//!     true,
+//! );
+//! commit.validate(&RuleOptions::default());
+//! assert!(commit.issues.iter().any(|issue| issue.rule == Rule::SubjectMood));
+//! ```
+//!
+//! Fetching commits and branches with Git or the GitHub, GitLab and Bitbucket APIs, and verifying
+//! ticket references with the Jira and GitHub APIs, requires the `git` feature.
+#![deny(unused_crate_dependencies)]
+#![deny(unused_extern_crates)]
+#![deny(unused_import_braces)]
+#![deny(non_ascii_idents)]
+#![warn(clippy::semicolon_if_nothing_returned)]
+#![warn(clippy::if_not_else)]
+#![warn(clippy::match_same_arms)]
+#![warn(clippy::needless_pass_by_value)]
+#![warn(clippy::trivially_copy_pass_by_ref)]
+#![warn(clippy::doc_markdown)]
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate lazy_static;
+
+// Dependencies of the `lintje` executable only
+#[cfg(feature = "cli")]
+use atty as _;
+#[cfg(feature = "cli")]
+use toml as _;
+// Dependencies of the tests of the `lintje` executable only
+#[cfg(test)]
+use assert_cmd as _;
+#[cfg(test)]
+use predicates as _;
+
+pub mod branch;
+mod characters;
+pub mod commit;
+#[cfg(feature = "color")]
+pub mod formatter;
+pub mod i18n;
+pub mod issue;
+pub mod options;
+pub mod rule;
+mod spelling;
+pub mod stats;
+pub mod tickets;
+pub mod utils;
+
+#[cfg(feature = "git")]
+pub mod bitbucket;
+#[cfg(feature = "git")]
+pub mod command;
+#[cfg(feature = "git")]
+pub mod git;
+#[cfg(feature = "git")]
+pub mod github;
+#[cfg(feature = "git")]
+pub mod gitlab;
+#[cfg(feature = "git")]
+pub mod http;
+#[cfg(feature = "git")]
+pub mod jira;
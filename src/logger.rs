@@ -2,11 +2,17 @@ use log::{Level, Metadata, Record};
 
 pub struct Logger {
     level: log::Level,
+    // Log to STDERR rather than STDOUT, when STDOUT is used for other output, like the messages
+    // of the Language Server Protocol.
+    stderr: bool,
 }
 
 impl Logger {
-    pub fn new() -> Self {
-        Self { level: Level::Warn }
+    pub fn new(stderr: bool) -> Self {
+        Self {
+            level: Level::Warn,
+            stderr,
+        }
     }
 }
 
@@ -16,7 +22,11 @@ impl log::Log for Logger {
     }
 
     fn log(&self, record: &Record) {
-        println!("[{}] {}", record.level(), record.args());
+        if self.stderr {
+            eprintln!("[{}] {}", record.level(), record.args());
+        } else {
+            println!("[{}] {}", record.level(), record.args());
+        }
     }
 
     fn flush(&self) {}
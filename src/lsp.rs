@@ -0,0 +1,586 @@
+use crate::config::Options;
+use lintje::commit::Commit;
+use lintje::git::{self, fetch_and_parse_commits, parse_commit_hook_format};
+use lintje::i18n::translate_issue;
+use lintje::issue::{ContextType, Issue, IssueType, Position};
+use lintje::options::{FetchOptions, RuleOptions};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+// The files Git opens in the editor to write a commit message in
+const COMMIT_MESSAGE_FILES: [&str; 2] = ["COMMIT_EDITMSG", "MERGE_MSG"];
+// The file Git opens in the editor to edit the commits of an interactive rebase
+const REBASE_TODO_FILE: &str = "git-rebase-todo";
+
+// JSON-RPC error code for messages that are not valid JSON
+const PARSE_ERROR: i64 = -32700;
+// JSON-RPC error code for requests the server doesn't support
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Run the Language Server Protocol server, reading requests from STDIN and writing responses to
+/// STDOUT, until the client tells the server to exit.
+pub fn run(rule_options: &RuleOptions, options: &Options) -> i32 {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve(&mut stdin.lock(), &mut stdout.lock(), rule_options, options)
+}
+
+fn serve<R: BufRead, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    rule_options: &RuleOptions,
+    options: &Options,
+) -> i32 {
+    let mut server = Server::new(rule_options, options);
+    loop {
+        let content = match read_message(reader) {
+            Ok(Some(content)) => content,
+            // The client closed STDIN without telling the server to exit
+            Ok(None) => return 1,
+            Err(e) => {
+                error!("Unable to read Language Server Protocol message: {}", e);
+                return 2;
+            }
+        };
+        let responses = match serde_json::from_slice::<Value>(&content) {
+            Ok(message) => {
+                if message["method"] == "exit" {
+                    // Exit with an error if the client didn't request a shutdown first
+                    return if server.shutdown { 0 } else { 1 };
+                }
+                server.handle(&message)
+            }
+            // The ID of the request is unknown if the message can't be parsed, so the error is
+            // sent without an ID. The server keeps reading the next messages.
+            Err(e) => vec![json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": {
+                    "code": PARSE_ERROR,
+                    "message": format!("Parse error: {}", e)
+                }
+            })],
+        };
+        for response in responses {
+            if let Err(e) = write_message(writer, &response) {
+                error!("Unable to write Language Server Protocol message: {}", e);
+                return 2;
+            }
+        }
+    }
+}
+
+struct Server<'a> {
+    rule_options: &'a RuleOptions,
+    options: &'a Options,
+    shutdown: bool,
+    // The commits listed in rebase todo files, by SHA, so they're only fetched once
+    rebase_commits: HashMap<String, Option<Commit>>,
+}
+
+impl<'a> Server<'a> {
+    fn new(rule_options: &'a RuleOptions, options: &'a Options) -> Self {
+        Self {
+            rule_options,
+            options,
+            shutdown: false,
+            rebase_commits: HashMap::new(),
+        }
+    }
+
+    /// Handle the request or notification from the client. Returns the messages to send to the
+    /// client.
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = message.get("id").cloned();
+        let params = &message["params"];
+        match message["method"].as_str().unwrap_or("") {
+            "initialize" => vec![response(
+                id.as_ref(),
+                &json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 1 }
+                    },
+                    "serverInfo": {
+                        "name": "lintje",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }),
+            )],
+            "shutdown" => {
+                self.shutdown = true;
+                vec![response(id.as_ref(), &Value::Null)]
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                self.publish_diagnostics(&document["uri"], document["text"].as_str())
+            }
+            "textDocument/didChange" => {
+                // The server only supports full document sync, so the last change is the entire
+                // document.
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                self.publish_diagnostics(&params["textDocument"]["uri"], text)
+            }
+            "textDocument/didClose" => {
+                let uri = &params["textDocument"]["uri"];
+                self.rebase_commits.clear();
+                vec![diagnostics_notification(uri, &[])]
+            }
+            method => match id {
+                Some(id) => vec![json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("Method not supported: {}", method)
+                    }
+                })],
+                // Other notifications, like `initialized`, need no response
+                None => vec![],
+            },
+        }
+    }
+
+    fn publish_diagnostics(&mut self, uri: &Value, text: Option<&str>) -> Vec<Value> {
+        let (path, text) = match (uri.as_str().and_then(uri_to_path), text) {
+            (Some(path), Some(text)) => (path, text),
+            _ => return vec![],
+        };
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let is_commit_message = COMMIT_MESSAGE_FILES.contains(&file_name.as_str());
+        if !is_commit_message && file_name != REBASE_TODO_FILE {
+            return vec![];
+        }
+        // Git commands work from within the `.git` directory of the repository too, so the
+        // repository's config is used.
+        let directory = path.parent();
+        let diagnostics = if is_commit_message {
+            self.commit_message_diagnostics(directory, text)
+        } else {
+            self.rebase_todo_diagnostics(directory, text)
+        };
+        vec![diagnostics_notification(uri, &diagnostics)]
+    }
+
+    fn commit_message_diagnostics(&self, directory: Option<&Path>, text: &str) -> Vec<Value> {
+        let mut commit = parse_commit_hook_format(
+            text,
+            &git::cleanup_mode(directory),
+            &git::comment_char(directory),
+            true,
+            self.rule_options,
+        );
+        let text_lines = text.lines().collect::<Vec<&str>>();
+        let line_indexes = commit_line_indexes(&text_lines, &commit);
+        let mut diagnostics = vec![];
+        for issue in commit.issues.iter_mut() {
+            if !self.options.hints && issue.r#type == IssueType::Hint
+                || self.options.hint_hidden(issue)
+            {
+                continue;
+            }
+            translate_issue(issue, self.options.language);
+            let (line, column) = match issue.position {
+                Position::Subject { line, column } | Position::MessageLine { line, column } => {
+                    (line, column)
+                }
+                Position::Diff | Position::Branch { .. } => (1, 1),
+            };
+            // Issues about lines that are not in the document, like suggested additions, are
+            // shown on the last line of the commit message.
+            let index = match line_indexes.get(line - 1).or_else(|| line_indexes.last()) {
+                Some(index) => *index,
+                None => 0,
+            };
+            let content = text_lines.get(index).copied().unwrap_or("");
+            let range = issue_range(issue, line, column, content);
+            diagnostics.push(diagnostic(issue, index, range));
+        }
+        diagnostics
+    }
+
+    // Every commit in the rebase todo list is validated with its message in the repository. The
+    // issues are shown on the line of the commit.
+    fn rebase_todo_diagnostics(&mut self, directory: Option<&Path>, text: &str) -> Vec<Value> {
+        let mut diagnostics = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let mut parts = line.splitn(3, ' ');
+            let (command, sha) = match (parts.next(), parts.next()) {
+                (Some(command), Some(sha)) => (command, sha),
+                _ => continue,
+            };
+            // Commits that are dropped, or squashed into the previous commit, won't be part of
+            // the history after the rebase.
+            if !matches!(command, "pick" | "p" | "reword" | "r" | "edit" | "e") {
+                continue;
+            }
+            let rule_options = self.rule_options;
+            let commit = self
+                .rebase_commits
+                .entry(sha.to_string())
+                .or_insert_with(|| {
+                    match fetch_and_parse_commits(
+                        directory,
+                        Some(sha.to_string()),
+                        &FetchOptions::default(),
+                        rule_options,
+                    ) {
+                        Ok(mut commits) if !commits.is_empty() => Some(commits.remove(0)),
+                        Ok(_) => None,
+                        Err(e) => {
+                            debug!("Unable to fetch commit {}: {}", sha, e);
+                            None
+                        }
+                    }
+                });
+            let commit = match commit {
+                Some(commit) => commit,
+                None => continue,
+            };
+            // Underline the subject after the command and SHA
+            let start = command.len() + sha.len() + 2;
+            let range = utf16_len(&line[..start.min(line.len())])..utf16_len(line);
+            for issue in &commit.issues {
+                if !self.options.hints && issue.r#type == IssueType::Hint
+                    || self.options.hint_hidden(issue)
+                {
+                    continue;
+                }
+                // Only the message of the issue is shown, the context is about the commit message,
+                // not the rebase todo list.
+                let message = issue.message.to_string();
+                let mut issue = match issue.r#type {
+                    IssueType::Error => Issue::error(issue.rule, message, Position::Diff, vec![]),
+                    IssueType::Hint => Issue::hint(issue.rule, message, Position::Diff, vec![]),
+                };
+                translate_issue(&mut issue, self.options.language);
+                diagnostics.push(diagnostic(&issue, index, range.clone()));
+            }
+        }
+        diagnostics
+    }
+}
+
+// The indexes of the lines in the document of the subject and every line of the message body.
+// Comment lines and leading empty lines are not part of the commit message, so the line numbers
+// of the parsed commit can differ from those of the document.
+fn commit_line_indexes(text_lines: &[&str], commit: &Commit) -> Vec<usize> {
+    let mut indexes = vec![];
+    let mut lines = text_lines.iter().enumerate();
+    for commit_line in std::iter::once(commit.subject.as_str()).chain(commit.message.lines()) {
+        match lines.find(|(_, line)| line.trim_end() == commit_line) {
+            Some((index, _)) => indexes.push(index),
+            None => break,
+        }
+    }
+    indexes
+}
+
+// The range of the issue on the line, in UTF-16 code units like the Language Server Protocol
+// expects. Uses the range of the error in the issue's context if it's about this line, or the
+// rest of the line from the issue's column otherwise.
+fn issue_range(issue: &Issue, line: usize, column: usize, content: &str) -> std::ops::Range<usize> {
+    let context = issue.context.iter().find(|context| {
+        context.r#type == ContextType::Error
+            && context.line == Some(line)
+            && context.content == content.trim_end()
+    });
+    if let Some(range) = context.and_then(|context| context.range.as_ref()) {
+        if content.is_char_boundary(range.start) && content.is_char_boundary(range.end) {
+            return utf16_len(&content[..range.start])..utf16_len(&content[..range.end]);
+        }
+    }
+    let start = content
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(content.len(), |(index, _)| index);
+    let end = utf16_len(content);
+    let start = utf16_len(&content[..start]);
+    if start < end {
+        start..end
+    } else {
+        0..end
+    }
+}
+
+fn utf16_len(string: &str) -> usize {
+    string.encode_utf16().count()
+}
+
+fn diagnostic(issue: &Issue, line: usize, range: std::ops::Range<usize>) -> Value {
+    // The advice of the issue, like "Remove the punctuation from the end of the subject"
+    let advice = issue
+        .context
+        .iter()
+        .filter(|context| context.r#type != ContextType::Plain)
+        .find_map(|context| context.message.as_deref());
+    let message = match advice {
+        Some(advice) => format!("{}\n{}", issue.message, advice),
+        None => issue.message.to_string(),
+    };
+    let severity = match issue.r#type {
+        IssueType::Error => 1,
+        IssueType::Hint => 4,
+    };
+    json!({
+        "range": {
+            "start": { "line": line, "character": range.start },
+            "end": { "line": line, "character": range.end }
+        },
+        "severity": severity,
+        "code": issue.rule.to_string(),
+        "codeDescription": { "href": issue.rule.doc_url() },
+        "source": "lintje",
+        "message": message
+    })
+}
+
+fn diagnostics_notification(uri: &Value, diagnostics: &[Value]) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics }
+    })
+}
+
+fn response(id: Option<&Value>, result: &Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Convert a `file://` URI to a file path. Percent-encoded characters, like spaces, are decoded.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let mut bytes = vec![];
+    let mut chars = encoded.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    // Windows paths are written like `file:///C:/Users`
+    let path = match path.strip_prefix('/') {
+        Some(windows_path) if windows_path.get(1..2) == Some(":") => windows_path.to_string(),
+        _ => path,
+    };
+    Some(Path::new(&path).to_path_buf())
+}
+
+/// Read a message from the client: headers, followed by an empty line and the JSON content.
+/// Returns the content, which is parsed separately, or `None` when the input is closed.
+fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header")
+    })?;
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(content))
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_message, serve, uri_to_path, write_message, Server};
+    use crate::config::{Options, OutputFormat};
+    use lintje::i18n::Language;
+    use lintje::options::RuleOptions;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    fn options() -> Options {
+        Options {
+            debug: false,
+            color: false,
+            format: OutputFormat::Text,
+            group_by: None,
+            language: Language::En,
+            hints: true,
+            hidden_hint_rules: vec![],
+            quiet: false,
+            rule_stats: false,
+        }
+    }
+
+    fn did_open(text: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///tmp/lintje-lsp/.git/COMMIT_EDITMSG",
+                    "languageId": "git-commit",
+                    "version": 1,
+                    "text": text
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_read_and_write_message() {
+        let mut output = vec![];
+        write_message(&mut output, &json!({ "id": 1 })).unwrap();
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "Content-Length: 8\r\n\r\n{\"id\":1}"
+        );
+        let mut input = output.as_slice();
+        assert_eq!(
+            read_message(&mut input).unwrap(),
+            Some(b"{\"id\":1}".to_vec())
+        );
+        assert_eq!(read_message(&mut input).unwrap(), None);
+        assert!(read_message(&mut "Content-Type: json\r\n\r\n{}".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_serve_with_invalid_json() {
+        let rule_options = RuleOptions::default();
+        let options = options();
+        let mut input = vec![];
+        write_message(
+            &mut input,
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" }),
+        )
+        .unwrap();
+        input.extend_from_slice(b"Content-Length: 9\r\n\r\n{\"id\": 2,");
+        write_message(&mut input, &json!({ "jsonrpc": "2.0", "method": "exit" })).unwrap();
+        let mut output = vec![];
+        let code = serve(&mut input.as_slice(), &mut output, &rule_options, &options);
+        assert_eq!(code, 0);
+
+        let mut output = output.as_slice();
+        let shutdown: Value =
+            serde_json::from_slice(&read_message(&mut output).unwrap().unwrap()).unwrap();
+        assert_eq!(shutdown["id"], 1);
+        let error: Value =
+            serde_json::from_slice(&read_message(&mut output).unwrap().unwrap()).unwrap();
+        assert_eq!(error["id"], Value::Null);
+        assert_eq!(error["error"]["code"], -32700);
+        assert_eq!(read_message(&mut output).unwrap(), None);
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/user/my%20repo/.git/COMMIT_EDITMSG"),
+            Some(PathBuf::from("/home/user/my repo/.git/COMMIT_EDITMSG"))
+        );
+        assert_eq!(
+            uri_to_path("file:///C:/repo/.git/COMMIT_EDITMSG"),
+            Some(PathBuf::from("C:/repo/.git/COMMIT_EDITMSG"))
+        );
+        assert_eq!(uri_to_path("untitled:COMMIT_EDITMSG"), None);
+    }
+
+    #[test]
+    fn test_commit_message_diagnostics() {
+        let rule_options = RuleOptions::default();
+        let options = options();
+        let mut server = Server::new(&rule_options, &options);
+        let messages = server.handle(&did_open(
+            "# Leading comment\n\nFixed the bug.\n\nThe café bug is fixed.\n# Comment",
+        ));
+        assert_eq!(messages.len(), 1);
+        let params = &messages[0]["params"];
+        assert_eq!(params["uri"], "file:///tmp/lintje-lsp/.git/COMMIT_EDITMSG");
+        let diagnostics = params["diagnostics"].as_array().unwrap();
+        let punctuation = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic["code"] == "SubjectPunctuation")
+            .unwrap();
+        assert_eq!(
+            punctuation["range"],
+            json!({
+                "start": { "line": 2, "character": 13 },
+                "end": { "line": 2, "character": 14 }
+            })
+        );
+        assert_eq!(punctuation["severity"], 1);
+        assert_eq!(punctuation["source"], "lintje");
+        assert_eq!(
+            punctuation["message"],
+            "The subject ends with a punctuation character: `.`\n\
+            Remove punctuation from the end of the subject"
+        );
+        let mood = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic["code"] == "SubjectMood")
+            .unwrap();
+        assert_eq!(mood["range"]["start"], json!({ "line": 2, "character": 0 }));
+    }
+
+    #[test]
+    fn test_other_documents() {
+        let rule_options = RuleOptions::default();
+        let options = options();
+        let mut server = Server::new(&rule_options, &options);
+        let message = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": "file:///tmp/README.md", "text": "Fixed." }
+            }
+        });
+        assert!(server.handle(&message).is_empty());
+    }
+
+    #[test]
+    fn test_requests() {
+        let rule_options = RuleOptions::default();
+        let options = options();
+        let mut server = Server::new(&rule_options, &options);
+        let messages = server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" }));
+        assert_eq!(messages[0]["id"], 1);
+        assert_eq!(
+            messages[0]["result"]["capabilities"]["textDocumentSync"]["change"],
+            1
+        );
+        let messages = server.handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "hover" }));
+        assert_eq!(messages[0]["error"]["code"], -32601);
+        assert!(server
+            .handle(&json!({ "jsonrpc": "2.0", "method": "initialized" }))
+            .is_empty());
+        assert!(!server.shutdown);
+        let messages = server.handle(&json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }));
+        assert_eq!(messages[0]["result"], Value::Null);
+        assert!(server.shutdown);
+    }
+}
@@ -11,68 +11,488 @@
 
 #[macro_use]
 extern crate log;
-#[macro_use]
-extern crate lazy_static;
 
-use clap::Parser;
+// Dependencies of the library only
+use lazy_static as _;
+use rayon as _;
+use unicode_segmentation as _;
+use unicode_width as _;
+
 use log::LevelFilter;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process;
 
-mod branch;
-mod command;
-mod commit;
 mod config;
-mod formatter;
-mod git;
-mod issue;
+mod hooks;
 mod logger;
-mod rule;
-mod utils;
-
-use branch::Branch;
-use command::run_command;
-use commit::Commit;
-use config::{Lint, Options};
-use formatter::{formatted_branch_issue, formatted_commit_issue};
-use git::{fetch_and_parse_branch, fetch_and_parse_commits, parse_commit_hook_format};
-use issue::IssueType;
+mod lsp;
+
+use config::{Command, GroupBy, Hook, HookCommand, Lint, Options, OutputFormat, StdinFormat};
+use lintje::bitbucket::Bitbucket;
+use lintje::branch::Branch;
+use lintje::commit::Commit;
+use lintje::formatter::{
+    compact_branch_issue, compact_commit_issue, formatted_branch_header, formatted_branch_issue,
+    formatted_commit_header, formatted_commit_issue, formatted_rule_docs,
+    formatted_rule_explanation, formatted_rule_header, json_branch_issue, json_commit_issue,
+    plain_branch_issue, plain_commit_issue, sarif_branch_result, sarif_commit_result,
+    sarif_document, IndentedWriter,
+};
+use lintje::git::{
+    fetch_and_parse_branch, fetch_and_parse_commits, fetch_and_parse_staged_changes,
+    parse_commit_hook_format, parse_commits_json, parse_remote_commits, CleanupMode, CommitSource,
+    PullRequest,
+};
+use lintje::github::GitHub;
+use lintje::gitlab::GitLab;
+use lintje::i18n::translate_issue;
+use lintje::issue::{Issue, IssueType};
+use lintje::options::{FetchOptions, RuleOptions};
+use lintje::rule::{rule_by_any_name, Rule, RULES};
+use lintje::utils::pluralize;
+use lintje::{formatter, git, stats};
 use logger::Logger;
-use termcolor::{ColorChoice, StandardStream, WriteColor};
-use utils::pluralize;
+use termcolor::{Buffer, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 fn main() {
-    let args = Lint::parse();
-    init_logger(args.debug);
-    let color = args.color();
-    let commit_result = match args.hook_message_file {
-        Some(hook_message_file) => lint_commit_hook(&hook_message_file),
-        None => lint_commit(args.selection),
+    let mut args = Lint::parse_args();
+    init_logger(args.debug, args.lsp);
+    if let Err(e) = args.load_settings() {
+        error!("An error occurred reading the config: {}", e);
+        std::process::exit(2)
+    }
+    if args.debug {
+        stats::enable();
+    }
+    for warning in &args.config_warnings {
+        warn!("{}", warning);
+    }
+    let options = Options {
+        debug: args.debug,
+        color: args.color(),
+        format: args.format,
+        group_by: args.group_by,
+        language: args.lang,
+        hints: args.hints,
+        hidden_hint_rules: args.hidden_hint_rules.clone(),
+        quiet: args.quiet,
+        rule_stats: args.rule_stats,
+    };
+    let exit_code = if let Some(rule) = &args.docs {
+        show_docs(rule.as_deref(), &options)
+    } else if let Some(Command::Hook {
+        command: HookCommand::Print { hook },
+    }) = &args.command
+    {
+        print!("{}", hooks::hook_script(*hook));
+        0
+    } else if args.lsp {
+        lsp::run(&args.rule_options(), &options)
+    } else if let Some(rule_name) = &args.explain {
+        explain_rule(rule_name, &options)
+    } else if options.group_by.is_some()
+        && matches!(options.format, OutputFormat::Json | OutputFormat::Sarif)
+    {
+        error!("The json and sarif formats do not support grouping issues");
+        2
+    } else if args.is_batch() && matches!(options.format, OutputFormat::Json | OutputFormat::Sarif)
+    {
+        error!("The json and sarif formats are not supported when linting multiple repositories");
+        2
+    } else if args.is_batch() {
+        match args.repositories() {
+            Ok(repositories) => lint_repositories(&repositories, &options),
+            Err(error) => {
+                error!("An error occurred reading the repositories: {}", error);
+                2
+            }
+        }
+    } else {
+        lint(&args, &options, &mut LintTotals::default())
+    };
+    if args.debug {
+        stats::log_summary();
+    }
+    if exit_code > 0 {
+        std::process::exit(exit_code)
+    }
+}
+
+/// The number of commits and issues of all linted repositories, for the summary when linting
+/// multiple repositories.
+#[derive(Debug, Default)]
+struct LintTotals {
+    commit_count: usize,
+    error_count: usize,
+    hint_count: usize,
+}
+
+/// Lint the commits and branch of the repository in the current working directory.
+fn lint(args: &Lint, options: &Options, totals: &mut LintTotals) -> i32 {
+    // The rule options are created per repository, because they can depend on the repository,
+    // like the GitHub repository issues are verified with.
+    let rule_options = &args.rule_options();
+    let fetch_options = &args.fetch_options();
+    if args.stdin() {
+        // Commits read from STDIN are not part of a repository, so there's no branch to validate
+        let commit_result = lint_stdin(args.stdin_format, rule_options);
+        return handle_result(print_lint_result(commit_result, None, options, totals));
+    }
+    if let Some(message) = &args.message {
+        let commit_result = Ok(vec![lint_message(message, rule_options)]);
+        return handle_result(print_lint_result(commit_result, None, options, totals));
+    }
+    if let Some(template) = &args.template {
+        let commit_result = lint_template(template.as_deref(), rule_options);
+        return handle_result(print_lint_result(commit_result, None, options, totals));
+    }
+    if let Some(reference) = &args.github_pr {
+        let github = GitHub::new(&args.github_api_url, None, args.github_token.clone());
+        let (commit_result, branch_result) =
+            lint_pull_request(github.fetch_pull_request(reference), args, rule_options);
+        return handle_result(print_lint_result(
+            commit_result,
+            branch_result,
+            options,
+            totals,
+        ));
+    }
+    if let Some(reference) = args.bitbucket_pull_request() {
+        let bitbucket = Bitbucket::new(
+            &args.bitbucket_api_url,
+            args.bitbucket_user.clone(),
+            args.bitbucket_token.clone(),
+        );
+        let pull_request_result =
+            reference.and_then(|reference| bitbucket.fetch_pull_request(&reference));
+        let (commit_result, branch_result) =
+            lint_pull_request(pull_request_result, args, rule_options);
+        return handle_result(print_lint_result(
+            commit_result,
+            branch_result,
+            options,
+            totals,
+        ));
+    }
+    if let Some(reference) = args.gitlab_merge_request() {
+        let gitlab = GitLab::new(&args.gitlab_api_url, args.gitlab_token());
+        let pull_request_result =
+            reference.and_then(|reference| gitlab.fetch_merge_request(&reference));
+        let (commit_result, branch_result) =
+            lint_pull_request(pull_request_result, args, rule_options);
+        return handle_result(print_lint_result(
+            commit_result,
+            branch_result,
+            options,
+            totals,
+        ));
+    }
+    let commit_result = match (&args.hook, &args.hook_message_file) {
+        (Some(Hook::PreCommit), _) => lint_staged_changes(rule_options),
+        (None, Some(hook_message_file)) => lint_commit_hook(
+            hook_message_file,
+            &args.hook_directory,
+            args.hook_commit_source.clone(),
+            rule_options,
+        ),
+        (None, None) => {
+            if args.all {
+                lint_all_commits(fetch_options, rule_options)
+            } else if let Some(number) = args.pull_request {
+                git::pull_request_selection(number)
+                    .and_then(|selection| lint_commit(Some(selection), fetch_options, rule_options))
+            } else if args.branch_range {
+                git::branch_range_selection()
+                    .and_then(|selection| lint_commit(Some(selection), fetch_options, rule_options))
+            } else if args.changed_since_push {
+                git::unpushed_commits_selection()
+                    .and_then(|selection| lint_commit(Some(selection), fetch_options, rule_options))
+            } else {
+                lint_commit(args.selection.clone(), fetch_options, rule_options)
+            }
+        }
     };
     let branch_result = if args.branch_validation {
-        Some(lint_branch())
+        Some(lint_branch(rule_options))
     } else {
         None
     };
-    let options = Options {
-        debug: args.debug,
-        color,
-        hints: args.hints,
+    handle_result(print_lint_result(
+        commit_result,
+        branch_result,
+        options,
+        totals,
+    ))
+}
+
+/// Print the documentation of the rule with the given name.
+fn explain_rule(rule_name: &str, options: &Options) -> i32 {
+    match rule_by_any_name(rule_name) {
+        Some(rule) => handle_result(
+            formatted_rule_explanation(&mut buffer_writer(options.color), rule).map(|()| 0),
+        ),
+        None => {
+            error!("Unknown rule: {}", rule_name);
+            2
+        }
+    }
+}
+
+/// Show the documentation of all rules, or the given rule, in a pager.
+fn show_docs(rule_name: Option<&str>, options: &Options) -> i32 {
+    let rules = match rule_name {
+        Some(rule_name) => match rule_by_any_name(rule_name) {
+            Some(rule) => vec![rule],
+            None => {
+                error!("Unknown rule: {}", rule_name);
+                return 2;
+            }
+        },
+        None => RULES.to_vec(),
+    };
+    let mut buffer = if options.color {
+        Buffer::ansi()
+    } else {
+        Buffer::no_color()
+    };
+    let result = formatted_rule_docs(&mut buffer, &rules)
+        .and_then(|()| page_output(buffer.as_slice()))
+        .map(|()| 0);
+    handle_result(result)
+}
+
+/// Write the output to the user's pager, when writing to a terminal. Uses the pager configured
+/// with the `PAGER` environment variable, or `less`. Falls back on writing to STDOUT when no pager
+/// can be started.
+fn page_output(output: &[u8]) -> io::Result<()> {
+    if atty::is(atty::Stream::Stdout) {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R -F -X".to_string());
+        if !pager.trim().is_empty() {
+            let child = process::Command::new("sh")
+                .args(["-c", &pager])
+                .stdin(process::Stdio::piped())
+                .spawn();
+            match child {
+                Ok(mut child) => {
+                    if let Some(mut stdin) = child.stdin.take() {
+                        // The user can quit the pager before all output is written, which
+                        // closes the pipe. That's not an error.
+                        let _ = stdin.write_all(output);
+                    }
+                    child.wait()?;
+                    return Ok(());
+                }
+                Err(e) => debug!("Unable to start pager `{}`: {}", pager, e),
+            }
+        }
+    }
+    io::stdout().write_all(output)
+}
+
+/// Lint every given repository, one after the other, and print a report per repository.
+/// Returns the highest exit code of all the repositories.
+fn lint_repositories(repositories: &[PathBuf], options: &Options) -> i32 {
+    let mut exit_code = 0;
+    let mut failed_count = 0;
+    let mut totals = LintTotals::default();
+    for repository in repositories {
+        let result = print_repository_header(repository, options).map(|()| 0);
+        if handle_result(result) > 0 {
+            return 2;
+        }
+        let repository_exit_code = match std::env::set_current_dir(repository) {
+            Ok(()) => lint_repository(options, &mut totals),
+            Err(e) => {
+                error!(
+                    "Unable to open repository: {}\n{}",
+                    repository.to_string_lossy(),
+                    e
+                );
+                2
+            }
+        };
+        if repository_exit_code > 0 {
+            failed_count += 1;
+        }
+        exit_code = exit_code.max(repository_exit_code);
+    }
+    let mut out = buffer_writer(options.color);
+    let result =
+        print_repositories_summary(&mut out, repositories.len(), failed_count, &totals, options)
+            .map(|()| 0);
+    exit_code.max(handle_result(result))
+}
+
+// Lint the repository in the current working directory, with the settings of its config files.
+fn lint_repository(options: &Options, totals: &mut LintTotals) -> i32 {
+    match Lint::parse_with_repository_settings() {
+        Ok(args) => {
+            for warning in &args.config_warnings {
+                warn!("{}", warning);
+            }
+            lint(&args, options, totals)
+        }
+        Err(e) => {
+            error!("An error occurred reading the config: {}", e);
+            2
+        }
+    }
+}
+
+fn print_repositories_summary(
+    out: &mut impl WriteColor,
+    repository_count: usize,
+    failed_count: usize,
+    totals: &LintTotals,
+    options: &Options,
+) -> io::Result<()> {
+    let repository_label = if repository_count == 1 {
+        "repository"
+    } else {
+        "repositories"
     };
-    handle_result(print_lint_result(commit_result, branch_result, &options));
+    writeln!(
+        out,
+        "\n{} {} inspected, {} with errors",
+        repository_count, repository_label, failed_count
+    )?;
+    write!(
+        out,
+        "{} {} inspected in total, ",
+        totals.commit_count,
+        pluralize("commit", totals.commit_count)
+    )?;
+    print_issue_counts(out, totals.error_count, totals.hint_count, options.hints)?;
+    writeln!(out)
+}
+
+fn print_repository_header(repository: &Path, options: &Options) -> io::Result<()> {
+    let mut out = buffer_writer(options.color);
+    out.set_color(ColorSpec::new().set_bold(true))?;
+    write!(out, "Repository: {}", repository.to_string_lossy())?;
+    out.reset()?;
+    writeln!(out)
+}
+
+fn lint_branch(options: &RuleOptions) -> Result<Branch, String> {
+    fetch_and_parse_branch(options)
+}
+
+// The result of linting the commits, and of linting the branch if branch validation is enabled.
+type CommitsAndBranchResult = (Result<Vec<Commit>, String>, Option<Result<Branch, String>>);
+
+/// Lint the commits and branch of the pull or merge request, fetched with the API of the Git host
+/// rather than from the local repository.
+fn lint_pull_request(
+    pull_request_result: Result<PullRequest, String>,
+    args: &Lint,
+    options: &RuleOptions,
+) -> CommitsAndBranchResult {
+    match pull_request_result {
+        Ok(pull_request) => {
+            let commits = parse_remote_commits(pull_request.commits, options);
+            let branch_result = if args.branch_validation {
+                let mut branch = Branch::new(pull_request.branch);
+                branch.validate(options);
+                Some(Ok(branch))
+            } else {
+                None
+            };
+            (Ok(commits), branch_result)
+        }
+        Err(error) => (Err(error), None),
+    }
+}
+
+fn lint_commit(
+    selection: Option<String>,
+    fetch_options: &FetchOptions,
+    options: &RuleOptions,
+) -> Result<Vec<Commit>, String> {
+    fetch_and_parse_commits(None, selection, fetch_options, options)
+}
+
+/// Lint the entire history. Prints the number of inspected commits while linting, when STDERR is a
+/// terminal, as this can take a while for large repositories.
+fn lint_all_commits(
+    fetch_options: &FetchOptions,
+    options: &RuleOptions,
+) -> Result<Vec<Commit>, String> {
+    let show_progress = atty::is(atty::Stream::Stderr);
+    let mut printed_progress = false;
+    let result = git::fetch_and_parse_all_commits(fetch_options, options, |count| {
+        if show_progress && count % 100 == 0 {
+            eprint!("\rInspecting commits: {}", count);
+            printed_progress = true;
+        }
+    });
+    if printed_progress {
+        // Clear the progress line
+        eprint!("\r\x1b[2K");
+    }
+    result
+}
+
+/// Lint the commit message read from STDIN, as stored in a commit. Only leading empty lines and
+/// trailing whitespace are cleaned up, lines starting with a comment character are kept. Without
+/// the changes of the commit, the `DiffPresence` rule is not validated.
+///
+/// With the JSON format, all commits in the JSON array read from STDIN are linted.
+fn lint_stdin(format: StdinFormat, options: &RuleOptions) -> Result<Vec<Commit>, String> {
+    let mut contents = String::new();
+    io::stdin()
+        .read_to_string(&mut contents)
+        .map_err(|e| format!("Unable to read commit message from STDIN: {}", e))?;
+    match format {
+        StdinFormat::Message => Ok(vec![lint_message(&contents, options)]),
+        StdinFormat::Json => {
+            parse_commits_json(&contents).map(|commits| parse_remote_commits(commits, options))
+        }
+    }
+}
+
+/// Lint the commit message given as an argument, as stored in a commit. Like messages read from
+/// STDIN, the `DiffPresence` rule is not validated.
+fn lint_message(message: &str, options: &RuleOptions) -> Commit {
+    parse_commit_hook_format(message, &CleanupMode::Whitespace, "#", true, options)
 }
 
-fn lint_branch() -> Result<Branch, String> {
-    fetch_and_parse_branch()
+/// Lint the commit message template file, or the template configured with `commit.template`.
+/// The comment lines are removed, as Git does when committing with the template.
+fn lint_template(file: Option<&Path>, options: &RuleOptions) -> Result<Vec<Commit>, String> {
+    let filename = match file {
+        Some(file) => file.to_path_buf(),
+        None => git::commit_template()?,
+    };
+    let contents = fs::read_to_string(&filename).map_err(|e| {
+        format!(
+            "Unable to read commit message template: {}\n{}",
+            filename.to_string_lossy(),
+            e
+        )
+    })?;
+    Ok(vec![parse_commit_hook_format(
+        &contents,
+        &CleanupMode::Strip,
+        &git::comment_char(None),
+        true,
+        options,
+    )])
 }
 
-fn lint_commit(selection: Option<String>) -> Result<Vec<Commit>, String> {
-    fetch_and_parse_commits(selection)
+fn lint_staged_changes(options: &RuleOptions) -> Result<Vec<Commit>, String> {
+    fetch_and_parse_staged_changes(options)
 }
 
-fn lint_commit_hook(filename: &Path) -> Result<Vec<Commit>, String> {
-    let commits = match File::open(filename) {
+fn lint_commit_hook(
+    filename: &Path,
+    directory: &Path,
+    commit_source: Option<CommitSource>,
+    options: &RuleOptions,
+) -> Result<Vec<Commit>, String> {
+    let commits = match File::open(directory.join(filename)) {
         Ok(mut file) => {
             let mut contents = String::new();
             match file.read_to_string(&mut contents) {
@@ -89,20 +509,33 @@ fn lint_commit_hook(filename: &Path) -> Result<Vec<Commit>, String> {
             // Run the diff command to fetch the current staged changes and determine if the commit is
             // empty or not. The contents of the commit message file is too unreliable as it depends on
             // user config and how the user called the `git commit` command.
-            let mut has_changes = true;
-            match run_command("git", &["diff", "--cached", "--shortstat"]) {
-                Ok(stdout) => {
-                    if stdout.is_empty() {
-                        has_changes = false;
-                    }
+            let mut has_changes = match git::has_staged_changes() {
+                Ok(has_changes) => has_changes,
+                Err(e) => {
+                    error!("Unable to determine commit changes.\nError: {}", e);
+                    true
                 }
-                Err(e) => error!("Unable to determine commit changes.\nError: {}", e.message),
+            };
+            // Amended commits and merge commits can be made without staged changes. The changes
+            // are those of the amended commit or the merged branch, so there's no need to report
+            // them as empty commits.
+            let commit_source = commit_source.or_else(git::detect_commit_source);
+            debug!("Commit source: {:?}", commit_source);
+            if !has_changes
+                && matches!(
+                    commit_source,
+                    Some(CommitSource::Commit) | Some(CommitSource::Merge)
+                )
+            {
+                debug!("No staged changes found for commit source. Skipping DiffPresence rule.");
+                has_changes = true;
             }
             let commit = parse_commit_hook_format(
                 &contents,
-                &git::cleanup_mode(),
-                &git::comment_char(),
+                &git::cleanup_mode(None),
+                &git::comment_char(None),
                 has_changes,
+                options,
             );
             vec![commit]
         }
@@ -117,24 +550,48 @@ fn lint_commit_hook(filename: &Path) -> Result<Vec<Commit>, String> {
     Ok(commits)
 }
 
-fn handle_result(result: io::Result<()>) {
+/// Returns the exit code of the lint result, logging any unexpected errors.
+fn handle_result(result: io::Result<i32>) -> i32 {
     match result {
-        Ok(()) => {}
-        Err(error) => error!("Unexpected error encountered: {}", error),
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            error!("Unexpected error encountered: {}", error);
+            2
+        }
     }
 }
 
+/// Prints the issues found and returns the exit code of the lint result.
 fn print_lint_result(
-    commit_result: Result<Vec<Commit>, String>,
-    branch_result: Option<Result<Branch, String>>,
+    mut commit_result: Result<Vec<Commit>, String>,
+    mut branch_result: Option<Result<Branch, String>>,
     options: &Options,
-) -> io::Result<()> {
+    totals: &mut LintTotals,
+) -> io::Result<i32> {
+    if let Ok(commits) = &mut commit_result {
+        for commit in commits {
+            commit.issues.retain(|issue| !options.hint_hidden(issue));
+            for issue in &mut commit.issues {
+                translate_issue(issue, options.language);
+            }
+        }
+    }
+    if let Some(Ok(branch)) = &mut branch_result {
+        branch.issues.retain(|issue| !options.hint_hidden(issue));
+        for issue in &mut branch.issues {
+            translate_issue(issue, options.language);
+        }
+    }
     let mut out = buffer_writer(options.color);
     let mut error_count = 0;
     let mut hint_count = 0;
     let mut commit_count = 0;
     let mut ignored_commit_count = 0;
     let mut branch_message = "";
+    let mut json_issues = vec![];
+    let mut rule_counts = vec![];
+    // The issues grouped by rule, printed after all issues are collected
+    let mut rule_groups = vec![];
 
     if let Ok(ref commits) = commit_result {
         debug!("Commits: {:?}", commits);
@@ -145,6 +602,13 @@ fn print_lint_result(
             }
             commit_count += 1;
             if !commit.is_valid() {
+                let shown_count = shown_issue_count(&commit.issues, options);
+                let grouped = options.group_by == Some(GroupBy::Commit);
+                if grouped && !options.quiet && shown_count > 0 {
+                    formatted_commit_header(&mut out, commit, shown_count)?;
+                }
+                // Grouped issues are indented beneath the header
+                let mut out = IndentedWriter::new(&mut out, if grouped { 2 } else { 0 });
                 for issue in &commit.issues {
                     let show = match issue.r#type {
                         IssueType::Error => {
@@ -157,7 +621,28 @@ fn print_lint_result(
                         }
                     };
                     if show {
-                        formatted_commit_issue(&mut out, commit, issue)?;
+                        count_rule(&mut rule_counts, issue.rule);
+                    }
+                    if show && !options.quiet && options.group_by == Some(GroupBy::Rule) {
+                        let group = rule_group(&mut rule_groups, issue.rule, options.color);
+                        let mut out = IndentedWriter::new(group, 2);
+                        match options.format {
+                            OutputFormat::Plain => plain_commit_issue(&mut out, commit, issue)?,
+                            OutputFormat::Compact => compact_commit_issue(&mut out, commit, issue)?,
+                            _ => formatted_commit_issue(&mut out, commit, issue)?,
+                        }
+                    } else if show && !options.quiet {
+                        match options.format {
+                            OutputFormat::Text => formatted_commit_issue(&mut out, commit, issue)?,
+                            OutputFormat::Plain => plain_commit_issue(&mut out, commit, issue)?,
+                            OutputFormat::Compact => compact_commit_issue(&mut out, commit, issue)?,
+                            OutputFormat::Json => {
+                                json_issues.push(json_commit_issue(commit, issue));
+                            }
+                            OutputFormat::Sarif => {
+                                json_issues.push(sarif_commit_result(commit, issue));
+                            }
+                        }
                     }
                 }
             }
@@ -170,18 +655,106 @@ fn print_lint_result(
                 debug!("Branch: {:?}", branch);
                 branch_message = " and branch";
                 if !branch.is_valid() {
+                    let shown_count = shown_issue_count(&branch.issues, options);
+                    let grouped = options.group_by == Some(GroupBy::Commit);
+                    if grouped && !options.quiet && shown_count > 0 {
+                        formatted_branch_header(&mut out, branch, shown_count)?;
+                    }
+                    let mut out = IndentedWriter::new(&mut out, if grouped { 2 } else { 0 });
                     for issue in &branch.issues {
-                        match issue.r#type {
-                            IssueType::Error => error_count += 1,
-                            IssueType::Hint => hint_count += 1,
+                        let show = match issue.r#type {
+                            IssueType::Error => {
+                                error_count += 1;
+                                true
+                            }
+                            IssueType::Hint => {
+                                hint_count += 1;
+                                options.hints
+                            }
+                        };
+                        if show {
+                            count_rule(&mut rule_counts, issue.rule);
+                        }
+                        if show && !options.quiet && options.group_by == Some(GroupBy::Rule) {
+                            let group = rule_group(&mut rule_groups, issue.rule, options.color);
+                            let mut out = IndentedWriter::new(group, 2);
+                            match options.format {
+                                OutputFormat::Plain => plain_branch_issue(&mut out, branch, issue)?,
+                                OutputFormat::Compact => compact_branch_issue(&mut out, issue)?,
+                                _ => formatted_branch_issue(&mut out, branch, issue)?,
+                            }
+                        } else if show && !options.quiet {
+                            match options.format {
+                                OutputFormat::Text => {
+                                    formatted_branch_issue(&mut out, branch, issue)?;
+                                }
+                                OutputFormat::Plain => plain_branch_issue(&mut out, branch, issue)?,
+                                OutputFormat::Compact => compact_branch_issue(&mut out, issue)?,
+                                OutputFormat::Json => {
+                                    json_issues.push(json_branch_issue(branch, issue));
+                                }
+                                OutputFormat::Sarif => {
+                                    json_issues.push(sarif_branch_result(branch, issue));
+                                }
+                            }
                         }
-                        formatted_branch_issue(&mut out, branch, issue)?;
                     }
                 }
             }
             Err(error) => branch_error = Some(error),
         }
     }
+    rule_groups.sort_by_key(|(rule, _, _)| RULES.iter().position(|r| r == rule));
+    for (rule, issue_count, buffer) in rule_groups {
+        formatted_rule_header(&mut out, rule, issue_count)?;
+        out.write_all(buffer.as_slice())?;
+    }
+
+    let mut errors = vec![];
+    if let Err(error) = commit_result {
+        errors.push(format!(
+            "An error occurred validating commits: {}",
+            error.trim()
+        ));
+    }
+    if let Some(error) = branch_error {
+        errors.push(format!(
+            "An error occurred validating the branch: {}",
+            error.trim()
+        ));
+    }
+    totals.commit_count += commit_count;
+    totals.error_count += error_count;
+    totals.hint_count += hint_count;
+    let exit_code = if !errors.is_empty() {
+        2
+    } else if error_count > 0 {
+        1
+    } else {
+        0
+    };
+
+    if options.format == OutputFormat::Json {
+        // Errors are part of the JSON document, so the output can always be parsed
+        let document = serde_json::json!({
+            "issues": json_issues,
+            "commit_count": commit_count,
+            "ignored_commit_count": ignored_commit_count,
+            "branch": !branch_message.is_empty(),
+            "error_count": error_count,
+            "hint_count": hint_count,
+            "errors": errors,
+        });
+        writeln!(out, "{:#}", document)?;
+        return Ok(exit_code);
+    }
+    if options.format == OutputFormat::Sarif {
+        writeln!(out, "{:#}", sarif_document(&json_issues, &errors))?;
+        return Ok(exit_code);
+    }
+    if options.quiet && exit_code == 0 {
+        return Ok(exit_code);
+    }
 
     let commit_label = pluralize("commit", commit_count);
     write!(
@@ -199,22 +772,72 @@ fn print_lint_result(
         )?;
     }
     writeln!(out)?;
-    let mut has_error = false;
-    if let Err(error) = commit_result {
-        has_error = true;
-        error!("An error occurred validating commits: {}", error.trim());
-    }
-    if let Some(error) = branch_error {
-        has_error = true;
-        error!("An error occurred validating the branch: {}", error.trim());
+    if options.rule_stats && !rule_counts.is_empty() {
+        print_rule_counts(&mut out, &mut rule_counts)?;
     }
-    if has_error {
-        std::process::exit(2)
+    for error in errors {
+        error!("{}", error);
     }
-    if error_count > 0 {
-        std::process::exit(1)
+    Ok(exit_code)
+}
+
+// Returns the buffer of the rule's grouped issues and counts the issue that is written to it.
+fn rule_group(
+    rule_groups: &mut Vec<(Rule, usize, Buffer)>,
+    rule: Rule,
+    color: bool,
+) -> &mut Buffer {
+    let index = match rule_groups
+        .iter()
+        .position(|(grouped, _, _)| *grouped == rule)
+    {
+        Some(index) => index,
+        None => {
+            let buffer = if color {
+                Buffer::ansi()
+            } else {
+                Buffer::no_color()
+            };
+            rule_groups.push((rule, 0, buffer));
+            rule_groups.len() - 1
+        }
+    };
+    let (_, issue_count, buffer) = &mut rule_groups[index];
+    *issue_count += 1;
+    buffer
+}
+
+fn count_rule(rule_counts: &mut Vec<(Rule, usize)>, rule: Rule) {
+    match rule_counts.iter_mut().find(|(counted, _)| *counted == rule) {
+        Some((_, count)) => *count += 1,
+        None => rule_counts.push((rule, 1)),
     }
-    Ok(())
+}
+
+// Print the number of issues per rule, the most reported rule first.
+fn print_rule_counts(
+    out: &mut impl WriteColor,
+    rule_counts: &mut [(Rule, usize)],
+) -> io::Result<()> {
+    rule_counts.sort_by(|(rule_a, count_a), (rule_b, count_b)| {
+        let index = |rule| RULES.iter().position(|r| r == rule);
+        count_b
+            .cmp(count_a)
+            .then_with(|| index(rule_a).cmp(&index(rule_b)))
+    });
+    let counts = rule_counts
+        .iter()
+        .map(|(rule, count)| format!("{}: {}", rule, count))
+        .collect::<Vec<String>>();
+    writeln!(out, "Issues per rule: {}", counts.join(", "))
+}
+
+// The number of issues that are printed. Hints are not printed with the `--no-hints` option.
+fn shown_issue_count(issues: &[Issue], options: &Options) -> usize {
+    issues
+        .iter()
+        .filter(|issue| options.hints || issue.r#type == IssueType::Error)
+        .count()
 }
 
 fn print_issue_counts(
@@ -245,13 +868,14 @@ fn print_issue_counts(
     Ok(())
 }
 
-fn init_logger(debug: bool) {
+fn init_logger(debug: bool, stderr: bool) {
     let level = if debug {
         LevelFilter::Debug
     } else {
         LevelFilter::Info
     };
-    let result = log::set_boxed_logger(Box::new(Logger::new())).map(|()| log::set_max_level(level));
+    let result =
+        log::set_boxed_logger(Box::new(Logger::new(stderr))).map(|()| log::set_max_level(level));
     match result {
         Ok(_) => (),
         Err(error) => {
@@ -267,9 +891,12 @@ fn init_logger(debug: bool) {
 
 /// Returns a `StandardStream` configured to write with color or not based on the config flag set by
 /// the user.
+///
+/// Whether color is enabled is already detected by the `color` option, so termcolor doesn't need
+/// to check the `TERM` and `NO_COLOR` environment variables again.
 fn buffer_writer(color: bool) -> StandardStream {
     let color_choice = if color {
-        ColorChoice::Auto
+        ColorChoice::Always
     } else {
         ColorChoice::Never
     };
@@ -282,10 +909,12 @@ mod tests {
     use regex::Regex;
     use std::fs;
     use std::fs::File;
-    use std::io::Write;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
     use std::path::{Path, PathBuf};
     use std::process::{Command, Stdio};
     use std::sync::Once;
+    use std::thread;
 
     static COMPILE_ONCE: Once = Once::new();
     const TEST_DIR: &str = "tmp/tests/test_repo";
@@ -412,6 +1041,27 @@ mod tests {
         }
     }
 
+    fn run_git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .output()
+            .unwrap_or_else(|e| panic!("Failed to run git {:?}: {:?}", args, e));
+        if !output.status.success() {
+            panic!(
+                "Failed to run git {:?}!\nExit code: {}\nSDTOUT: {}\nSTDERR: {}",
+                args,
+                output
+                    .status
+                    .code()
+                    .expect("Could not fetch status code of git"),
+                String::from_utf8(output.stdout).unwrap(),
+                String::from_utf8(output.stderr).unwrap()
+            )
+        }
+    }
+
     fn configure_git_cleanup_mode(dir: &Path, mode: &str) {
         let output = Command::new("git")
             .args(&["config", "commit.cleanup", mode])
@@ -528,88 +1178,320 @@ mod tests {
     }
 
     #[test]
-    fn test_single_commit_valid_with_color() {
+    fn test_quiet() {
         compile_bin();
-        let dir = test_dir("single_commit_valid_with_color");
+        let dir = test_dir("quiet");
         create_test_repo(&dir);
         create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
 
+        // Hints don't fail the lint, so nothing is printed
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--color", "--no-hints"])
-            .current_dir(dir)
+            .args(["--no-color", "--quiet"])
+            .current_dir(&dir)
             .assert()
             .success();
-        assert.stdout(
-            "1 commit and branch inspected, \u{1b}[0m\u{1b}[32m0 errors detected\u{1b}[0m\n",
-        );
+        assert.stdout("");
+
+        create_commit_with_file(&dir, "Fixing tests", "", "file2");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--quiet"])
+            .current_dir(&dir)
+            .assert()
+            .failure();
+        assert.stdout("1 commit and branch inspected, 3 errors detected, 1 hint\n");
     }
 
     #[test]
-    fn test_single_commit_with_hint() {
+    fn test_lang() {
         compile_bin();
-        let dir = test_dir("single_commit_valid_hint");
-        create_test_repo(&dir);
-        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
-
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
-        let assert = cmd.args(["--no-color"]).current_dir(dir).assert().success();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--lang=nl",
+                "--message",
+                "Fixing tests\n\nThe tests were broken by a typo.",
+            ])
+            .assert()
+            .failure()
+            .code(1);
         assert
             .stdout(predicate::str::contains(
-                "Hint[MessageTicketNumber]: The message body does not contain a ticket or issue number",
+                "Error[SubjectMood]: Het onderwerp staat niet in de gebiedende wijs",
             ))
             .stdout(predicate::str::contains(
-                "1 commit and branch inspected, 0 errors detected, 1 hint\n",
+                "Gebruik de gebiedende wijs voor het onderwerp",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--format=json",
+                "--message",
+                "Fixing tests\n\nThe tests were broken by a typo.",
+            ])
+            .env("LINTJE_LANG", "nl")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("\"rule\": \"SubjectMood\""))
+            .stdout(predicate::str::contains(
+                "Het onderwerp staat niet in de gebiedende wijs",
             ));
     }
 
     #[test]
-    fn test_single_commit_in_repository() {
+    fn test_group_by_commit() {
         compile_bin();
-        let dir = test_dir("single_commit_in_repository");
+        let dir = test_dir("group_by_commit");
         create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+        create_commit_with_file(&dir, "Fixing tests", "I am a test commit", "file2");
+        create_commit_with_file(&dir, "Fix the login tests", "I am a test commit", "file3");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", "--no-hints"])
-            .current_dir(dir)
+            .args([
+                "--no-color",
+                "--no-hints",
+                "--format=plain",
+                "--group-by=commit",
+                "HEAD~3..HEAD",
+            ])
+            .current_dir(&dir)
             .assert()
-            .success();
-        assert.stdout("1 commit and branch inspected, 0 errors detected\n");
+            .failure();
+        assert
+            .stdout(
+                predicate::str::is_match(
+                    "(?m)^commit [0-9a-f]{7}: Fixing tests — 2 issues\n  Error SubjectCliche: ",
+                )
+                .unwrap(),
+            )
+            .stdout(predicate::str::contains("\n    In commit ").count(2))
+            .stdout(predicate::str::contains("Test commit —").not())
+            .stdout(predicate::str::contains("Fix the login tests —").not());
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--group-by=commit", "--format=json"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout("[ERROR] The json and sarif formats do not support grouping issues\n");
     }
 
     #[test]
-    fn test_single_commit_invalid() {
+    fn test_group_by_rule() {
         compile_bin();
-        let dir = test_dir("single_commit_invalid");
+        let dir = test_dir("group_by_rule");
         create_test_repo(&dir);
-        create_commit_with_file(&dir, "Fixing tests", "", "file");
+        create_commit_with_file(&dir, "Fixed the login", "I am a test commit", "file");
+        create_commit_with_file(&dir, "Fix the login tests", "I am a test commit", "file2");
+        create_commit_with_file(&dir, "Added the signup", "I am a test commit", "file3");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", "--no-hints"])
-            .current_dir(dir)
+            .args([
+                "--no-color",
+                "--no-hints",
+                "--format=plain",
+                "--group-by=rule",
+                "HEAD~3..HEAD",
+            ])
+            .current_dir(&dir)
             .assert()
-            .failure()
-            .code(1);
+            .failure();
+        assert
+            .stdout(
+                predicate::str::is_match(
+                    "(?m)^rule SubjectMood — 2 issues\n  Error SubjectMood: .+\n    \
+                    In commit [0-9a-f]{7} \"Added the signup\"",
+                )
+                .unwrap(),
+            )
+            .stdout(predicate::str::contains("rule SubjectMood").count(1))
+            .stdout(predicate::str::contains("\"Fixed the login\"").count(1))
+            .stdout(predicate::str::contains("Fix the login tests").not());
+    }
 
-        let output = normalize_output(&assert.get_output().stdout);
-        assert_eq!(
-            output,
-            "Error[SubjectCliche]: The subject does not explain the change in much detail\n\
-            \x20\x200000000:1:1: Fixing tests\n\
-            \x20\x20  |\n\
-            \x20\x201 | Fixing tests\n\
-            \x20\x20  | ^^^^^^^^^^^^ Describe the change in more detail\n\
-            \n\
-            Error[SubjectMood]: The subject does not use the imperative grammatical mood\n\
-            \x20\x200000000:1:1: Fixing tests\n\
-            \x20\x20  |\n\
-            \x20\x201 | Fixing tests\n\
-            \x20\x20  | ^^^^^^ Use the imperative mood for the subject\n\
-            \n\
-            Error[MessagePresence]: No message body was found\n\
-            \x20\x200000000:3:1: Fixing tests\n\
+    #[test]
+    fn test_rule_stats() {
+        compile_bin();
+        let dir = test_dir("rule_stats");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing tests", "I am a test commit", "file");
+        create_commit_with_file(&dir, "Fixed tests", "I am a test commit", "file2");
+        create_commit_with_file(&dir, "Fix the login tests", "", "file3");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--quiet", "--rule-stats", "HEAD~3..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .failure();
+        assert.stdout(
+            "3 commits and branch inspected, 5 errors detected, 3 hints\n\
+            Issues per rule: MessageTicketNumber: 3, SubjectMood: 2, SubjectCliche: 2, \
+            MessagePresence: 1\n",
+        );
+
+        // Hints are not counted when they're not shown
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--quiet",
+                "--no-hints",
+                "--rule-stats",
+                "HEAD~3..HEAD",
+            ])
+            .current_dir(&dir)
+            .assert()
+            .failure();
+        assert.stdout(predicate::str::ends_with(
+            "Issues per rule: SubjectMood: 2, SubjectCliche: 2, MessagePresence: 1\n",
+        ));
+    }
+
+    #[test]
+    fn test_single_commit_valid_with_color() {
+        compile_bin();
+        let dir = test_dir("single_commit_valid_with_color");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+
+        // The color option takes precedence over the environment
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--color", "--no-hints"])
+            .env_remove("TERM")
+            .env("NO_COLOR", "1")
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout(
+            "1 commit and branch inspected, \u{1b}[0m\u{1b}[32m0 errors detected\u{1b}[0m\n",
+        );
+    }
+
+    #[test]
+    fn test_single_commit_valid_with_forced_color() {
+        compile_bin();
+        let dir = test_dir("single_commit_valid_with_forced_color");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+
+        // The output is piped, color is only enabled when forced
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-hints"])
+            .env("CLICOLOR_FORCE", "1")
+            .env_remove("NO_COLOR")
+            .env_remove("TERM")
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert.stdout(
+            "1 commit and branch inspected, \u{1b}[0m\u{1b}[32m0 errors detected\u{1b}[0m\n",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-hints"])
+            .env_remove("CLICOLOR_FORCE")
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert.stdout("1 commit and branch inspected, 0 errors detected\n");
+    }
+
+    #[test]
+    fn test_single_commit_with_hint() {
+        compile_bin();
+        let dir = test_dir("single_commit_valid_hint");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.args(["--no-color"]).current_dir(dir).assert().success();
+        assert
+            .stdout(predicate::str::contains(
+                "Hint[MessageTicketNumber]: The message body does not contain a ticket or issue number",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 0 errors detected, 1 hint\n",
+            ));
+    }
+
+    #[test]
+    fn test_single_commit_with_hidden_hint() {
+        compile_bin();
+        let dir = test_dir("single_commit_hidden_hint");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--hide-hint=MessageTicketNumber"])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout("1 commit and branch inspected, 0 errors detected\n");
+    }
+
+    #[test]
+    fn test_single_commit_in_repository() {
+        compile_bin();
+        let dir = test_dir("single_commit_in_repository");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints"])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout("1 commit and branch inspected, 0 errors detected\n");
+    }
+
+    #[test]
+    fn test_single_commit_invalid() {
+        compile_bin();
+        let dir = test_dir("single_commit_invalid");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing tests", "", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints"])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+
+        let output = normalize_output(&assert.get_output().stdout);
+        assert_eq!(
+            output,
+            "Error[SubjectCliche]: The subject does not explain the change in much detail\n\
+            \x20\x200000000:1:1: Fixing tests\n\
+            \x20\x20  |\n\
+            \x20\x201 | Fixing tests\n\
+            \x20\x20  | ^^^^^^^^^^^^ Describe the change in more detail\n\
+            \n\
+            Error[SubjectMood]: The subject does not use the imperative grammatical mood\n\
+            \x20\x200000000:1:1: Fixing tests\n\
+            \x20\x20  |\n\
+            \x20\x201 | Fixing tests\n\
+            \x20\x20  | ^^^^^^ Use the imperative mood for the subject\n\
+            \n\
+            Error[MessagePresence]: No message body was found\n\
+            \x20\x200000000:3:1: Fixing tests\n\
             \x20\x20  |\n\
             \x20\x201 | Fixing tests\n\
             \x20\x202 | \n\
@@ -732,6 +1614,27 @@ mod tests {
         assert.stdout("0 commits and branch inspected, 0 errors detected (1 commit ignored)\n");
     }
 
+    #[test]
+    fn test_single_commit_disable_all() {
+        compile_bin();
+        let dir = test_dir("single_commit_disable_all");
+        create_test_repo(&dir);
+        create_commit_with_file(
+            &dir,
+            "import vendored library.",
+            "Import the library as is.\nlintje:disable-all",
+            "file",
+        );
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints"])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout("0 commits and branch inspected, 0 errors detected (1 commit ignored)\n");
+    }
+
     #[test]
     fn test_single_commit_ignored_with_color() {
         compile_bin();
@@ -804,121 +1707,1586 @@ mod tests {
     }
 
     #[test]
-    fn test_lint_hook() {
+    fn test_changed_since_push() {
         compile_bin();
-        let dir = test_dir("commit_file_option");
+        let dir = test_dir("changed_since_push");
+        let remote_dir = test_dir("changed_since_push_remote");
         create_test_repo(&dir);
-        let filename = "commit_message_file";
-        let commit_file = dir.join(filename);
-        let mut file = File::create(&commit_file).unwrap();
-        file.write_all(b"added some code\n\nThis is a message.")
-            .unwrap();
+        if remote_dir.exists() {
+            fs::remove_dir_all(&remote_dir).expect("Could not remove remote repo dir");
+        }
+        fs::create_dir_all(&remote_dir).expect("Could not create remote repo dir");
+        run_git(&remote_dir, &["init", "--bare"]);
+        let remote_path = fs::canonicalize(&remote_dir).unwrap();
+        run_git(
+            &dir,
+            &["remote", "add", "origin", remote_path.to_str().unwrap()],
+        );
+        create_commit_with_file(&dir, "Pushed commit", "", "file1");
+        run_git(&dir, &["push", "--set-upstream", "origin", "HEAD"]);
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file2");
+        create_commit_with_file(&dir, "Unpushed valid commit", "This is a message.", "file3");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", &format!("--hook-message-file={}", filename)])
-            .current_dir(dir)
+            .args(["--no-color", "--no-hints", "--changed-since-push"])
+            .current_dir(&dir)
             .assert()
             .failure()
             .code(1);
         assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Pushed commit").not())
             .stdout(predicate::str::contains(
-                "Error[SubjectMood]: The subject does not use the imperative grammatical mood",
-            ))
-            .stdout(predicate::str::contains(
-                "Error[SubjectCapitalization]: The subject does not start with a capital letter",
-            ))
-            .stdout(predicate::str::contains(
-                "Error[DiffPresence]: No file changes found",
-            ))
-            .stdout(predicate::str::contains(
-                "1 commit and branch inspected, 3 errors detected",
+                "2 commits and branch inspected, 2 errors detected",
             ));
     }
 
     #[test]
-    fn test_file_option_with_file_changes() {
+    fn test_pull_request() {
         compile_bin();
-        let dir = test_dir("commit_file_option_with_file_changes");
+        let dir = test_dir("pull_request");
+        let remote_dir = test_dir("pull_request_remote");
         create_test_repo(&dir);
-        create_file(&dir.join("file name"));
-        stage_files(&dir);
-        let filename = "commit_message_file";
-        let commit_file = dir.join(filename);
-        let mut file = File::create(&commit_file).unwrap();
-        file.write_all(b"Valid subject\n\nValid message body.")
-            .unwrap();
+        if remote_dir.exists() {
+            fs::remove_dir_all(&remote_dir).expect("Could not remove remote repo dir");
+        }
+        fs::create_dir_all(&remote_dir).expect("Could not create remote repo dir");
+        run_git(&remote_dir, &["init", "--bare"]);
+        let remote_path = fs::canonicalize(&remote_dir).unwrap();
+        run_git(
+            &dir,
+            &["remote", "add", "origin", remote_path.to_str().unwrap()],
+        );
+        create_commit_with_file(&dir, "Fixing bug on main", "This is a message.", "file1");
+        run_git(&dir, &["push", "origin", "HEAD:refs/heads/main"]);
+        run_git(&dir, &["remote", "set-head", "origin", "main"]);
+        run_git(&dir, &["checkout", "-b", "feature"]);
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file2");
+        create_commit_with_file(&dir, "Add valid commit", "This is a message.", "file3");
+        run_git(&dir, &["push", "origin", "HEAD:refs/pull/7/head"]);
+        run_git(&dir, &["checkout", "-"]);
+        run_git(&dir, &["branch", "-D", "feature"]);
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", &format!("--hook-message-file={}", filename)])
-            .current_dir(dir)
+            .args(["--no-color", "--no-hints", "--no-branch", "--pr", "7"])
+            .current_dir(&dir)
             .assert()
-            .success();
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Fixing bug on main").not())
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--pr", "8"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2);
         assert.stdout(predicate::str::contains(
-            "1 commit and branch inspected, 0 errors detected",
+            "Unable to find pull request #8 locally or on the origin remote.",
         ));
     }
 
     #[test]
-    fn test_file_option_with_scissors_cleanup() {
+    fn test_branch_range() {
         compile_bin();
-        let dir = test_dir("commit_file_option_with_scissors_cleanup_default_comment_char");
+        let dir = test_dir("branch_range");
+        let remote_dir = test_dir("branch_range_remote");
         create_test_repo(&dir);
-        configure_git_cleanup_mode(&dir, "scissors");
-        let filename = "commit_message_file";
-        let commit_file = dir.join(filename);
-        let mut file = File::create(&commit_file).unwrap();
-        file.write_all(
-            b"This is a subject\n\n\
-            # ------------------------ >8 ------------------------
-            # This is part of the comment that will be ignored
-            ",
-        )
-        .unwrap();
+        if remote_dir.exists() {
+            fs::remove_dir_all(&remote_dir).expect("Could not remove remote repo dir");
+        }
+        fs::create_dir_all(&remote_dir).expect("Could not create remote repo dir");
+        run_git(&remote_dir, &["init", "--bare"]);
+        let remote_path = fs::canonicalize(&remote_dir).unwrap();
+        run_git(
+            &dir,
+            &["remote", "add", "origin", remote_path.to_str().unwrap()],
+        );
+        create_commit_with_file(&dir, "Fixing bug on main", "This is a message.", "file1");
+        run_git(&dir, &["push", "origin", "HEAD:refs/heads/main"]);
+        run_git(&dir, &["remote", "set-head", "origin", "main"]);
+        run_git(&dir, &["checkout", "-b", "feature"]);
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file2");
+        create_commit_with_file(&dir, "Add valid commit", "This is a message.", "file3");
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", &format!("--hook-message-file={}", filename)])
-            .current_dir(dir)
+            .args(["--no-color", "--no-hints", "--no-branch", "--branch-range"])
+            .current_dir(&dir)
             .assert()
             .failure()
             .code(1);
-        assert.stdout(predicate::str::contains("Error[MessagePresence]: "));
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Fixing bug on main").not())
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 2 errors detected",
+            ));
     }
 
     #[test]
-    fn test_file_option_with_scissors_cleanup_custom_comment_char() {
+    fn test_author() {
         compile_bin();
-        let dir = test_dir("commit_file_option_with_scissors_cleanup_custom_comment_char");
+        let dir = test_dir("author");
         create_test_repo(&dir);
-        configure_git_cleanup_mode(&dir, "scissors");
-        configure_git_comment_char(&dir, "-");
-        let filename = "commit_message_file";
-        let commit_file = dir.join(filename);
-        let mut file = File::create(&commit_file).unwrap();
-        file.write_all(
-            b"This is a subject\n\n\
-            - ------------------------ >8 ------------------------
-            - This is part of the comment that will be ignored
-            ",
-        )
-        .unwrap();
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file1");
+        create_file(&dir.join("file2"));
+        stage_files(&dir);
+        run_git(
+            &dir,
+            &[
+                "commit",
+                "--no-gpg-sign",
+                "--author=Jane <jane@example.com>",
+                "-mFixing bug",
+                "-mThis is a message.",
+            ],
+        );
 
         let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
         let assert = cmd
-            .args(["--no-color", &format!("--hook-message-file={}", filename)])
-            .current_dir(dir)
+            .args([
+                "--no-color",
+                "--no-hints",
+                "--no-branch",
+                "--author",
+                "jane@example.com",
+                "HEAD~2..HEAD",
+            ])
+            .current_dir(&dir)
             .assert()
             .failure()
             .code(1);
-        assert.stdout(predicate::str::contains("Error[MessagePresence]: "));
+        assert
+            .stdout(predicate::str::contains("Fixing bug"))
+            .stdout(predicate::str::contains("Fixing tests").not())
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 2 errors detected",
+            ));
     }
 
     #[test]
-    fn test_file_option_without_file() {
+    fn test_since() {
         compile_bin();
-        let dir = test_dir("commit_file_option_without_file");
+        let dir = test_dir("since");
+        create_test_repo(&dir);
+        create_file(&dir.join("file1"));
+        stage_files(&dir);
+        let output = Command::new("git")
+            .args([
+                "commit",
+                "--no-gpg-sign",
+                "-mFixing bug",
+                "-mThis is a message.",
+            ])
+            .env("GIT_AUTHOR_DATE", "2001-01-01T12:00:00")
+            .env("GIT_COMMITTER_DATE", "2001-01-01T12:00:00")
+            .current_dir(&dir)
+            .output()
+            .expect("Could not make old commit");
+        assert!(output.status.success());
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file2");
+        create_commit_with_file(&dir, "Add valid commit", "This is a message.", "file3");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--no-hints",
+                "--no-branch",
+                "--since",
+                "2002-01-01",
+            ])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Fixing bug").not())
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 2 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_multiple_repositories() {
+        compile_bin();
+        let valid_dir = test_dir("multiple_repositories_valid");
+        let invalid_dir = test_dir("multiple_repositories_invalid");
+        create_test_repo(&valid_dir);
+        create_test_repo(&invalid_dir);
+        create_commit_with_file(&valid_dir, "Valid commit subject", "Message body", "file");
+        create_commit_with_file(&invalid_dir, "Fixing tests", "Message body", "file");
+        let repos_file = test_dir("multiple_repositories.txt");
+        let mut file = File::create(&repos_file).unwrap();
+        writeln!(file, "# Repositories\nmultiple_repositories_invalid").unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--no-branch",
+                "--repo",
+                valid_dir.to_str().unwrap(),
+                "--repos-file",
+                repos_file.to_str().unwrap(),
+            ])
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(format!(
+                "Repository: {}",
+                fs::canonicalize(&valid_dir).unwrap().to_str().unwrap()
+            )))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 0 errors detected",
+            ))
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 2 errors detected",
+            ))
+            .stdout(predicate::str::contains(
+                "2 repositories inspected, 1 with errors\n\
+                2 commits inspected in total, 2 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_allow_fixup_without_target() {
+        compile_bin();
+        let dir = test_dir("allow_fixup_without_target");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Add the signup form", "Message body", "file");
+        create_commit_with_file(&dir, "Add the login form", "Message body", "file2");
+        create_commit_with_file(&dir, "fixup! Add the signup form", "", "file3");
+
+        // The target is outside the range, the fixup commit is reported once, as a hint
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-branch", "--allow-fixup", "HEAD~2..HEAD"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains(
+                "Hint[NeedsRebase]: The target commit of the fixup commit was not found",
+            ))
+            .stdout(predicate::str::contains("A fixup commit was found").not())
+            .stdout(predicate::str::contains(
+                "2 commits inspected, 0 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_multiple_repositories_config() {
+        compile_bin();
+        let configured_dir = test_dir("multiple_repositories_configured");
+        let default_dir = test_dir("multiple_repositories_default");
+        create_test_repo(&configured_dir);
+        create_test_repo(&default_dir);
+        create_commit_with_file(
+            &configured_dir,
+            "Add the login form",
+            "Message body",
+            "file",
+        );
+        create_commit_with_file(&default_dir, "Add the login form", "Message body", "file");
+        fs::write(
+            configured_dir.join(".lintje.toml"),
+            "max-subject-length = 10\n",
+        )
+        .unwrap();
+
+        // Every repository is linted with its own config file
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-branch", "--repo"])
+            .arg(&configured_dir)
+            .arg("--repo")
+            .arg(&default_dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectLength]"))
+            .stdout(predicate::str::contains(
+                "2 repositories inspected, 1 with errors\n\
+                2 commits inspected in total, 1 error detected",
+            ));
+    }
+
+    #[test]
+    fn test_multiple_repositories_wildcard() {
+        compile_bin();
+        let dir = test_dir("wildcard_repositories");
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+        let valid_dir = dir.join("service-a");
+        let invalid_dir = dir.join("service-b");
+        create_test_repo(&valid_dir);
+        create_test_repo(&invalid_dir);
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        create_commit_with_file(&valid_dir, "Fixing tests", "Message body", "file");
+        create_commit_with_file(&valid_dir, "Valid commit subject", "Message body", "file2");
+        create_commit_with_file(&invalid_dir, "Valid commit subject", "Message body", "file");
+        create_commit_with_file(&invalid_dir, "Fixing tests", "Message body", "file2");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-branch", "--repo"])
+            .arg(dir.join("*"))
+            .arg("HEAD~1..HEAD")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("service-a"))
+            .stdout(predicate::str::contains("service-b"))
+            .stdout(predicate::str::contains("docs").not())
+            .stdout(predicate::str::contains(
+                "2 repositories inspected, 1 with errors\n\
+                2 commits inspected in total, 2 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_git_dir_bare_repository() {
+        compile_bin();
+        let dir = test_dir("git_dir_repository");
+        let bare_dir = test_dir("git_dir_bare_repository.git");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing tests", "Message body", "file");
+        if bare_dir.exists() {
+            fs::remove_dir_all(&bare_dir).unwrap();
+        }
+        let output = Command::new("git")
+            .args(["clone", "--bare", "--quiet"])
+            .args([&dir, &bare_dir])
+            .stdin(Stdio::null())
+            .output()
+            .expect("Could not clone bare test repo");
+        assert!(output.status.success(), "{:?}", output);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--git-dir"])
+            .arg(&bare_dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--git-dir", "tmp/tests/unknown.git"])
+            .assert()
+            .failure()
+            .code(2)
+            .stdout(predicate::str::contains("The Git directory does not exist"));
+    }
+
+    #[test]
+    fn test_directory() {
+        compile_bin();
+        let dir = test_dir("directory_repository");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing tests", "Message body", "file");
+        run_git(&dir, &["config", "lintje.hints", "false"]);
+
+        // The Git config of the repository in the directory is used
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "-C"])
+            .arg(&dir)
+            .arg("HEAD")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 2 errors detected\n",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "-C", "tmp/tests/unknown"])
+            .assert()
+            .failure()
+            .code(2)
+            .stdout(predicate::str::contains(
+                "Unable to change the working directory to tmp/tests/unknown",
+            ));
+    }
+
+    // Serve the response bodies for the request paths on a local HTTP server, in the background.
+    // The `{url}` placeholder in the bodies is replaced with the URL of the server, which is
+    // returned.
+    fn serve_http(responses: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server_url = url.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                BufReader::new(&stream)
+                    .read_line(&mut request_line)
+                    .unwrap();
+                let path = request_line.split(' ').nth(1).unwrap_or_default();
+                let (status, body) = match responses.iter().find(|(p, _)| *p == path) {
+                    Some((_, body)) => ("200 OK", body.replace("{url}", &server_url)),
+                    None => ("404 Not Found", "{}".to_string()),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_github_pull_request() {
+        compile_bin();
+        let url = serve_http(vec![
+            (
+                "/repos/tombruijn/lintje/pulls/12",
+                r#"{"number":12,"head":{"ref":"login-form"}}"#,
+            ),
+            (
+                "/repos/tombruijn/lintje/pulls/12/commits?per_page=100&page=1",
+                r#"[
+                    {"sha":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","commit":{
+                        "author":{"name":"Tom","email":"tom@example.com"},
+                        "message":"Add the login form\n\nUsers can now log in."}},
+                    {"sha":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb","commit":{
+                        "author":{"name":"Tom","email":"tom@example.com"},
+                        "message":"Fixing tests\n\nThe tests failed."}}
+                ]"#,
+            ),
+        ]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--no-hints",
+                "--github-pr",
+                "tombruijn/lintje#12",
+            ])
+            .arg(format!("--github-api-url={}", url))
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("bbbbbbb"))
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Add the login form").not())
+            .stdout(predicate::str::contains(
+                "2 commits and branch inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--github-pr", "tombruijn/lintje#13"])
+            .arg(format!("--github-api-url={}", url))
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Pull request not found on GitHub: tombruijn/lintje#13",
+        ));
+    }
+
+    #[test]
+    fn test_gitlab_merge_request() {
+        compile_bin();
+        let url = serve_http(vec![
+            (
+                "/projects/42/merge_requests/12",
+                r#"{"iid":12,"source_branch":"login-form"}"#,
+            ),
+            (
+                "/projects/42/merge_requests/12/commits?per_page=100&page=1",
+                r#"[
+                    {"id":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                        "author_name":"Tom","author_email":"tom@example.com",
+                        "message":"Fixing tests\n\nThe tests failed."},
+                    {"id":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                        "author_name":"Tom","author_email":"tom@example.com",
+                        "message":"Add the login form\n\nUsers can now log in."}
+                ]"#,
+            ),
+        ]);
+
+        // The merge request of the GitLab CI pipeline
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--gitlab-mr"])
+            .env("CI_API_V4_URL", &url)
+            .env("CI_MERGE_REQUEST_PROJECT_ID", "42")
+            .env("CI_MERGE_REQUEST_IID", "12")
+            .env("CI_JOB_TOKEN", "secret")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("bbbbbbb"))
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Add the login form").not())
+            .stdout(predicate::str::contains(
+                "2 commits and branch inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--gitlab-mr=group/project!13"])
+            .env("CI_API_V4_URL", &url)
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Merge request not found on GitLab: group/project!13",
+        ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--gitlab-mr"])
+            .env_remove("CI_MERGE_REQUEST_IID")
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Unable to determine the GitLab merge request",
+        ));
+    }
+
+    #[test]
+    fn test_bitbucket_pull_request() {
+        compile_bin();
+        let url = serve_http(vec![
+            (
+                "/repositories/tombruijn/lintje/pullrequests/12",
+                r#"{"id":12,"source":{"branch":{"name":"login-form"}}}"#,
+            ),
+            (
+                "/repositories/tombruijn/lintje/pullrequests/12/commits?pagelen=100&page=1",
+                r#"{"next":"{url}/repositories/tombruijn/lintje/pullrequests/12/commits?pagelen=100&page=2",
+                    "values":[{"hash":"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                        "author":{"raw":"Tom <tom@example.com>"},
+                        "message":"Fixing tests\n\nThe tests failed.\n"}]}"#,
+            ),
+            (
+                "/repositories/tombruijn/lintje/pullrequests/12/commits?pagelen=100&page=2",
+                r#"{"values":[{"hash":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                    "author":{"raw":"Tom <tom@example.com>"},
+                    "message":"Add the login form\n\nUsers can now log in.\n"}]}"#,
+            ),
+        ]);
+
+        // The pull request of the Bitbucket Pipelines build
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--bitbucket-pr"])
+            .arg(format!("--bitbucket-api-url={}", url))
+            .env("BITBUCKET_REPO_FULL_NAME", "tombruijn/lintje")
+            .env("BITBUCKET_PR_ID", "12")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("bbbbbbb"))
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("Add the login form").not())
+            .stdout(predicate::str::contains(
+                "2 commits and branch inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--bitbucket-pr=tombruijn/lintje#13"])
+            .arg(format!("--bitbucket-api-url={}", url))
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Pull request not found on Bitbucket: tombruijn/lintje#13",
+        ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--bitbucket-pr"])
+            .env_remove("BITBUCKET_PR_ID")
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Unable to determine the Bitbucket pull request",
+        ));
+    }
+
+    #[test]
+    fn test_plain_format() {
+        compile_bin();
+        let dir = test_dir("plain_format");
+        create_test_repo(&dir);
+        create_commit(&dir, "Fixing tests", "This is a message body.");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-branch", "--format=plain"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let sha = Regex::new(r"In commit ([a-z0-9]{7})")
+            .unwrap()
+            .captures(&output)
+            .expect("No commit SHA found")[1]
+            .to_string();
+        assert!(output.contains(&format!(
+            "Error SubjectMood: The subject does not use the imperative grammatical mood.\n  \
+            In commit {} \"Fixing tests\", on line 1, column 1.\n  \
+            Problem on line 1, columns 1 to 6 (\"Fixing\"): \
+            Use the imperative mood for the subject.\n",
+            sha
+        )));
+        assert!(!output.contains('|'));
+        assert!(!output.contains('^'));
+    }
+
+    #[test]
+    fn test_compact_format() {
+        compile_bin();
+        let dir = test_dir("compact_format");
+        create_test_repo(&dir);
+        create_commit(&dir, "Fixing tests", "This is a message body.");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-branch", "--format=compact"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1)
+            .stdout(
+                predicate::str::is_match(
+                    "(?m)^[0-9a-f]{7}:1:1 Error SubjectMood The subject does not use the imperative \
+                    grammatical mood\n",
+                )
+                .unwrap(),
+            )
+            .stdout(predicate::str::contains('|').not());
+    }
+
+    #[test]
+    fn test_json_format() {
+        compile_bin();
+        let dir = test_dir("json_format");
+        create_test_repo(&dir);
+        create_commit(&dir, "Fixing tests", "This is a message body.");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-branch", "--format=json"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(document["commit_count"], 1);
+        assert_eq!(document["error_count"], 3);
+        assert_eq!(document["branch"], false);
+        assert_eq!(document["errors"], serde_json::json!([]));
+        let issue = document["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["rule"] == "SubjectMood")
+            .expect("No SubjectMood issue found");
+        assert_eq!(issue["type"], "error");
+        assert_eq!(issue["commit"]["subject"], "Fixing tests");
+        assert_eq!(issue["commit"]["sha"].as_str().unwrap().len(), 40);
+        assert_eq!(
+            issue["position"],
+            serde_json::json!({ "type": "subject", "line": 1, "column": 1 })
+        );
+        assert_eq!(
+            issue["context"][0]["columns"],
+            serde_json::json!({ "start": 1, "end": 6 })
+        );
+    }
+
+    #[test]
+    fn test_sarif_format() {
+        compile_bin();
+        let dir = test_dir("sarif_format");
+        create_test_repo(&dir);
+        create_commit(&dir, "Fixing tests", "This is a message body.");
+        checkout_branch(&dir, "fix-123");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--format=sarif"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let run = &document["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "Lintje");
+        assert_eq!(run["invocations"][0]["executionSuccessful"], true);
+        let results = run["results"].as_array().unwrap();
+        let result = results
+            .iter()
+            .find(|result| result["ruleId"] == "SubjectMood")
+            .expect("No SubjectMood result found");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            serde_json::json!({
+                "artifactLocation": { "uri": ".git/COMMIT_EDITMSG" },
+                "region": { "startLine": 1, "startColumn": 1 },
+            })
+        );
+        let location = &result["locations"][0]["logicalLocations"][0];
+        assert_eq!(location["kind"], "commit");
+        assert_eq!(location["fullyQualifiedName"].as_str().unwrap().len(), 40);
+        assert_eq!(
+            result["properties"]["position"],
+            serde_json::json!({ "type": "subject", "line": 1, "column": 1 })
+        );
+        assert!(results
+            .iter()
+            .any(|result| result["ruleId"] == "BranchNameTicketNumber"));
+        assert_sarif_upload_requirements(&document);
+    }
+
+    // The SARIF 2.1.0 schema and GitHub code scanning's upload requirements: a tool name and
+    // rules, and every result with a rule, a message and a file location with a region whose
+    // line and column start at 1.
+    fn assert_sarif_upload_requirements(document: &serde_json::Value) {
+        assert_eq!(
+            document["$schema"],
+            "https://json.schemastore.org/sarif-2.1.0.json"
+        );
+        assert_eq!(document["version"], "2.1.0");
+        let runs = document["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+        let driver = &runs[0]["tool"]["driver"];
+        assert!(driver["name"].is_string());
+        let rules = driver["rules"].as_array().unwrap();
+        let results = runs[0]["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        for result in results {
+            let index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[index]["id"], result["ruleId"]);
+            assert!(["error", "warning", "note"].contains(&result["level"].as_str().unwrap()));
+            assert!(!result["message"]["text"].as_str().unwrap().is_empty());
+            let location = &result["locations"][0]["physicalLocation"];
+            let uri = location["artifactLocation"]["uri"].as_str().unwrap();
+            assert!(!uri.is_empty() && !uri.starts_with('/'));
+            let region = &location["region"];
+            assert!(region["startLine"].as_u64().unwrap() >= 1);
+            if let Some(column) = region.get("startColumn") {
+                assert!(column.as_u64().unwrap() >= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        compile_bin();
+        let dir = test_dir("message");
+        // Not a repository, the message doesn't need Git
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--message",
+                "Fixing tests\n\n#123 is fixed by this.",
+            ])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectMood]"))
+            .stdout(predicate::str::contains("0000000:1:1: Fixing tests"))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--message",
+                "Fix the login tests\n\nThe tests were broken by a typo.",
+            ])
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_template() {
+        compile_bin();
+        let dir = test_dir("template");
+        create_test_repo(&dir);
+        fs::write(
+            dir.join(".gitmessage"),
+            "Fixing tests\n\n# Describe why this change was made\n",
+        )
+        .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--template=.gitmessage"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectMood]"))
+            .stdout(predicate::str::contains("Describe why").not())
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 3 errors detected",
+            ));
+
+        fs::write(
+            dir.join(".gitmessage"),
+            "Fix the login tests\n\n# Describe why this change was made\n\
+             The tests were broken by a typo.\n",
+        )
+        .unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--template"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "No commit message template configured with commit.template",
+        ));
+
+        run_git(&dir, &["config", "commit.template", ".gitmessage"]);
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--template"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_stdin() {
+        compile_bin();
+        let dir = test_dir("stdin");
+        // Not a repository, STDIN doesn't need Git
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "-"])
+            .current_dir(&dir)
+            .write_stdin("Fixing tests\n\n#123 is fixed by this.\n")
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectMood]"))
+            .stdout(predicate::str::contains("0000000:1:1: Fixing tests"))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--stdin"])
+            .current_dir(&dir)
+            .write_stdin("Fix the login tests\n\nThe tests were broken by a typo.\n")
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_stdin_json() {
+        compile_bin();
+        let dir = test_dir("stdin_json");
+        // Not a repository, STDIN doesn't need Git
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--stdin", "--stdin-format=json"])
+            .current_dir(&dir)
+            .write_stdin(
+                r#"[
+                    {
+                        "sha": "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                        "subject": "Fixing tests",
+                        "message": "The tests failed.",
+                        "author": {"name": "Jane Doe", "email": "jane@example.com"}
+                    },
+                    {"subject": "Add the login form", "message": "Users can now log in."},
+                    {"message": "Update the login form\n\nUsers can log in again."}
+                ]"#,
+            )
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("bbbbbbb:1:1: Fixing tests"))
+            .stdout(predicate::str::contains("login form").not())
+            .stdout(predicate::str::contains(
+                "3 commits inspected, 2 errors detected",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--stdin-format=json", "-"])
+            .current_dir(&dir)
+            .write_stdin(r#"{"subject": "Add the login form"}"#)
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "The commits JSON is not an array of commits",
+        ));
+    }
+
+    #[test]
+    fn test_docs() {
+        compile_bin();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.args(["--docs"]).assert().success();
+        assert
+            .stdout(predicate::str::contains("Commit subject rules\n"))
+            .stdout(predicate::str::contains(
+                "SubjectMood: Checks if the subject uses the imperative grammatical mood.",
+            ))
+            .stdout(predicate::str::contains("Branch rules\n"))
+            .stdout(predicate::str::contains(
+                "BranchNameLength: Checks if the branch name is at least 4 characters long.",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.args(["--docs", "BranchNameLength"]).assert().success();
+        assert
+            .stdout(predicate::str::contains("BranchNameLength: "))
+            .stdout(predicate::str::contains("Branch rules").not())
+            .stdout(predicate::str::contains("SubjectMood").not());
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--docs", "Unknown"]).assert().failure().code(2);
+    }
+
+    #[test]
+    fn test_hook_print() {
+        compile_bin();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.args(["hook", "print", "commit-msg"]).assert().success();
+        assert
+            .stdout(predicate::str::starts_with("#!/bin/sh\n"))
+            .stdout(predicate::str::contains(
+                "lintje --hook-message-file=\"$1\"",
+            ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["hook", "print", "unknown"])
+            .assert()
+            .failure()
+            .code(2);
+    }
+
+    #[test]
+    fn test_hook_print_pre_push() {
+        compile_bin();
+        let dir = test_dir("hook_print_pre_push");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing tests", "This is a message.", "file");
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let output = cmd.args(["hook", "print", "pre-push"]).output().unwrap();
+        let script = String::from_utf8(output.stdout).unwrap();
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD~1", "HEAD"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let shas = String::from_utf8(output.stdout).unwrap();
+        let shas = shas.lines().collect::<Vec<&str>>();
+        // Make sure the hook runs the Lintje executable that was just built
+        let bin_dir = assert_cmd::cargo::cargo_bin("lintje")
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let path = format!(
+            "{}:{}",
+            bin_dir.to_str().unwrap(),
+            std::env::var("PATH").unwrap()
+        );
+
+        let mut cmd = assert_cmd::Command::new("sh");
+        let assert = cmd
+            .args(["-c", &script])
+            .env("PATH", path)
+            .current_dir(&dir)
+            .write_stdin(format!(
+                "refs/heads/main {} refs/heads/main {}\n",
+                shas[1], shas[0]
+            ))
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Fixing tests"))
+            .stdout(predicate::str::contains("1 commit and branch inspected"));
+    }
+
+    #[test]
+    fn test_explain_rule() {
+        compile_bin();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--explain", "SubjectMood"])
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains(
+                "SubjectMood: Checks if the subject uses the imperative grammatical mood.",
+            ))
+            .stdout(predicate::str::contains("- Fixed bug"))
+            .stdout(predicate::str::contains(
+                "https://lintje.dev/docs/rules/commit-subject/#subjectmood",
+            ));
+    }
+
+    #[test]
+    fn test_explain_unknown_rule() {
+        compile_bin();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--explain", "Unknown"])
+            .assert()
+            .failure()
+            .code(2);
+    }
+
+    #[test]
+    fn test_changed_since_push_without_upstream() {
+        compile_bin();
+        let dir = test_dir("changed_since_push_without_upstream");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--changed-since-push"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "The current branch has no remote tracking branch.",
+        ));
+    }
+
+    #[test]
+    fn test_lint_hook() {
+        compile_bin();
+        let dir = test_dir("commit_file_option");
+        create_test_repo(&dir);
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(b"added some code\n\nThis is a message.")
+            .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(
+                "Error[SubjectMood]: The subject does not use the imperative grammatical mood",
+            ))
+            .stdout(predicate::str::contains(
+                "Error[SubjectCapitalization]: The subject does not start with a capital letter",
+            ))
+            .stdout(predicate::str::contains(
+                "Error[DiffPresence]: No file changes found",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 3 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_lint_hook_amend_commit_source() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_amend_commit_source");
+        create_test_repo(&dir);
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(b"Valid subject\n\nValid message body.")
+            .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                &format!("--hook-message-file={}", filename),
+                "--hook-commit-source=commit",
+            ])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit and branch inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_lint_hook_merge_commit_source_detection() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_merge_commit_source");
+        create_test_repo(&dir);
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(&dir)
+            .output()
+            .expect("Failed to fetch commit SHA.");
+        // Simulate a merge in progress
+        let mut merge_head = File::create(dir.join(".git/MERGE_HEAD")).unwrap();
+        merge_head.write_all(&output.stdout).unwrap();
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(b"Merge the login feature\n\nMerge the login feature branch.")
+            .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit and branch inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_lint_hook_from_other_directory() {
+        compile_bin();
+        let name = "commit_file_option_other_directory";
+        let dir = test_dir(name);
+        create_test_repo(&dir);
+        configure_git_comment_char(&dir, ";");
+        create_file(&dir.join("file"));
+        stage_files(&dir);
+        let mut file = File::create(dir.join(".git/COMMIT_EDITMSG")).unwrap();
+        file.write_all(
+            b"Add the login feature\n\nAdd a login form.\n\
+            ; Lines starting with the comment character are stripped from the commit message.",
+        )
+        .unwrap();
+
+        // Shared hooks can be run from another directory, with GIT_DIR relative to the directory
+        // Git was run from.
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--debug",
+                &format!("--hook-message-file={}/.git/COMMIT_EDITMSG", name),
+            ])
+            .env("GIT_DIR", format!("{}/.git", name))
+            .current_dir(dir.parent().unwrap())
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains(format!(
+                "Repository root: {}\n",
+                dir.canonicalize().unwrap().to_string_lossy()
+            )))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 0 errors detected",
+            ));
+    }
+
+    #[test]
+    fn test_lint_hook_from_other_directory_with_config() {
+        compile_bin();
+        let name = "commit_file_option_other_directory_config";
+        let dir = test_dir(name);
+        create_test_repo(&dir);
+        create_file(&dir.join("file"));
+        stage_files(&dir);
+        fs::write(dir.join(".lintje.toml"), "max-subject-length = 10\n").unwrap();
+        fs::write(
+            dir.join(".git/COMMIT_EDITMSG"),
+            "Add the login feature\n\nAdd a login form.\n",
+        )
+        .unwrap();
+
+        // The config file of the repository is used, not those of the directory Git was run from
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                &format!("--hook-message-file={}/.git/COMMIT_EDITMSG", name),
+            ])
+            .env("GIT_DIR", format!("{}/.git", name))
+            .current_dir(dir.parent().unwrap())
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectLength]"))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 1 error detected",
+            ));
+    }
+
+    #[test]
+    fn test_pre_commit_hook() {
+        compile_bin();
+        let dir = test_dir("pre_commit_hook");
+        create_test_repo(&dir);
+        create_file(&dir.join("file"));
+        stage_files(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--hook=pre-commit"])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout("1 commit and branch inspected, 0 errors detected\n");
+    }
+
+    #[test]
+    fn test_pre_commit_hook_diff_rules() {
+        compile_bin();
+        let dir = test_dir("pre_commit_hook_diff_rules");
+        create_test_repo(&dir);
+        fs::create_dir_all(dir.join("app")).unwrap();
+        fs::create_dir_all(dir.join("config")).unwrap();
+        create_file(&dir.join("Cargo.lock"));
+        create_file(&dir.join(".env"));
+        create_file(&dir.join("app").join("file"));
+        create_file(&dir.join("config").join("file"));
+        stage_files(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args([
+                "--no-color",
+                "--hook=pre-commit",
+                "--max-file-size=10",
+                "--forbidden-path=.env",
+                "--max-files-changed=3",
+                "--max-directories=1",
+            ])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[DiffLargeFile]"))
+            .stdout(predicate::str::contains("Hint[DiffLockfile]"))
+            .stdout(predicate::str::contains("Error[DiffForbiddenPath]"))
+            .stdout(predicate::str::contains("Hint[DiffFileCount]"))
+            .stdout(predicate::str::contains("Hint[DiffUnrelatedChanges]"));
+    }
+
+    #[test]
+    fn test_large_file() {
+        compile_bin();
+        let dir = test_dir("large_file");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Add the test file", "\nFor the tests.", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--max-file-size=10"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(
+                "Error[DiffLargeFile]: The added file `file` is 17 bytes, larger than the maximum of 10 bytes",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 1 error detected",
+            ));
+
+        create_file(&dir.join("other file"));
+        stage_files(&dir);
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--max-file-size=10", "--hook=pre-commit"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert.stdout(predicate::str::contains(
+            "Error[DiffLargeFile]: The added file `other file` is 17 bytes",
+        ));
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn test_many_added_files() {
+        compile_bin();
+        let dir = test_dir("many_added_files");
+        create_test_repo(&dir);
+        // Enough files that the sizes of the added files don't fit in the pipe buffers of the
+        // Git command at once
+        for i in 0..4000 {
+            fs::write(dir.join(format!("file_{}", i)), i.to_string()).unwrap();
+        }
+        stage_files(&dir);
+        create_commit(&dir, "Add the test files", "For the tests.");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--max-files-changed=5000", "HEAD"])
+            .current_dir(&dir)
+            .timeout(std::time::Duration::from_secs(60))
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit and branch inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_lsp() {
+        compile_bin();
+        let dir = test_dir("lsp");
+        create_test_repo(&dir);
+        let uri = format!("file://{}/.git/COMMIT_EDITMSG", dir.to_string_lossy());
+        let input = [
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            serde_json::json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": { "uri": uri, "text": "Fix bug.\n\n# Comment" }
+                }
+            }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            serde_json::json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]
+        .iter()
+        .map(|message| {
+            let content = message.to_string();
+            format!("Content-Length: {}\r\n\r\n{}", content.len(), content)
+        })
+        .collect::<String>();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.arg("--lsp").write_stdin(input).assert().success();
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        assert!(output.starts_with("Content-Length: "));
+        assert!(output.contains("\"method\":\"textDocument/publishDiagnostics\""));
+        assert!(output.contains("\"code\":\"SubjectPunctuation\""));
+        assert!(output.contains("\"code\":\"MessagePresence\""));
+        assert!(output.ends_with("{\"id\":2,\"jsonrpc\":\"2.0\",\"result\":null}"));
+
+        // Exits with an error without a shutdown request
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.arg("--lsp").write_stdin("").assert().failure().code(1);
+    }
+
+    #[test]
+    fn test_lsp_rebase_todo() {
+        compile_bin();
+        let dir = test_dir("lsp_rebase_todo");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Fixing bug", "", "file");
+        let output = Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let sha = String::from_utf8(output.stdout).unwrap().trim().to_string();
+        let rebase_dir = dir.join(".git").join("rebase-merge");
+        fs::create_dir_all(&rebase_dir).unwrap();
+        let uri = format!("file://{}/git-rebase-todo", rebase_dir.to_string_lossy());
+        let open = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": uri,
+                    "text": format!("pick {} Fixing bug\n\n# Rebase instructions", sha)
+                }
+            }
+        })
+        .to_string();
+        let input = format!("Content-Length: {}\r\n\r\n{}", open.len(), open);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.arg("--lsp").write_stdin(input).assert().failure();
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        assert!(output.contains("\"code\":\"SubjectMood\""));
+        assert!(output.contains(&format!(
+            "\"start\":{{\"character\":{},\"line\":0}}",
+            sha.len() + 6
+        )));
+    }
+
+    #[test]
+    fn test_pre_commit_hook_without_file_changes() {
+        compile_bin();
+        let dir = test_dir("pre_commit_hook_without_file_changes");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--hook=pre-commit"])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(
+                "Error[DiffPresence]: No file changes found",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 1 error detected",
+            ));
+    }
+
+    #[test]
+    fn test_file_option_with_file_changes() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_with_file_changes");
+        create_test_repo(&dir);
+        create_file(&dir.join("file name"));
+        stage_files(&dir);
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(b"Valid subject\n\nValid message body.")
+            .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit and branch inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_file_option_with_scissors_cleanup() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_with_scissors_cleanup_default_comment_char");
+        create_test_repo(&dir);
+        configure_git_cleanup_mode(&dir, "scissors");
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(
+            b"This is a subject\n\n\
+            # ------------------------ >8 ------------------------
+            # This is part of the comment that will be ignored
+            ",
+        )
+        .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert.stdout(predicate::str::contains("Error[MessagePresence]: "));
+    }
+
+    #[test]
+    fn test_file_option_with_scissors_cleanup_custom_comment_char() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_with_scissors_cleanup_custom_comment_char");
+        create_test_repo(&dir);
+        configure_git_cleanup_mode(&dir, "scissors");
+        configure_git_comment_char(&dir, "-");
+        let filename = "commit_message_file";
+        let commit_file = dir.join(filename);
+        let mut file = File::create(&commit_file).unwrap();
+        file.write_all(
+            b"This is a subject\n\n\
+            - ------------------------ >8 ------------------------
+            - This is part of the comment that will be ignored
+            ",
+        )
+        .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert.stdout(predicate::str::contains("Error[MessagePresence]: "));
+    }
+
+    #[test]
+    fn test_file_option_without_file() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_without_file");
         create_test_repo(&dir);
         let filename = "commit_message_file";
 
@@ -1002,4 +3370,262 @@ mod tests {
             "1 commit inspected, 0 errors detected",
         ));
     }
+
+    #[test]
+    fn test_git_config_options() {
+        compile_bin();
+        let dir = test_dir("git_config_options");
+        create_test_repo(&dir);
+        let subject = "Add a longer subject that is allowed by the config";
+        create_commit_with_file(
+            &dir,
+            &format!("{} now", subject),
+            "I am a test commit.",
+            "file",
+        );
+        run_git(&dir, &["config", "lintje.maxSubjectLength", "60"]);
+        run_git(&dir, &["config", "lintje.branch", "false"]);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.arg("--no-color").current_dir(&dir).assert().success();
+        assert.stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+
+        // Command line options take precedence over the Git config
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--max-subject-length=50"])
+            .current_dir(&dir)
+            .assert()
+            .failure();
+        assert.stdout(predicate::str::contains("SubjectLength"));
+
+        run_git(&dir, &["config", "lintje.hints", "maybe"]);
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd.current_dir(&dir).assert().code(2);
+        assert.stdout(predicate::str::contains(
+            "Invalid boolean value for lintje.hints: maybe",
+        ));
+    }
+
+    #[test]
+    fn test_file_option_with_comment_string() {
+        compile_bin();
+        let dir = test_dir("commit_file_option_with_comment_string");
+        create_test_repo(&dir);
+        create_file(&dir.join("file"));
+        stage_files(&dir);
+        configure_git_comment_char(&dir, ";");
+        run_git(&dir, &["config", "core.commentString", "//"]);
+        let filename = "commit_message_file";
+        let mut file = File::create(dir.join(filename)).unwrap();
+        file.write_all(
+            b"This is a subject\n\n\
+            This is the message body.\n\
+            // Please enter the commit message for your changes. Lines starting with '//' will be ignored.
+            ",
+        )
+        .unwrap();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", &format!("--hook-message-file={}", filename)])
+            .current_dir(dir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit and branch inspected, 0 errors detected",
+        ));
+    }
+
+    #[test]
+    fn test_config_files() {
+        compile_bin();
+        let dir = test_dir("config_files");
+        create_test_repo(&dir);
+        let subject = "Add a longer subject that is allowed by the config";
+        create_commit_with_file(
+            &dir,
+            &format!("{} now", subject),
+            "I am a test commit.",
+            "file",
+        );
+        checkout_branch(&dir, "fix-123");
+        let config_home = dir.canonicalize().unwrap().join("config_home");
+        std::fs::create_dir_all(config_home.join("lintje")).unwrap();
+        std::fs::write(
+            config_home.join("lintje/config.toml"),
+            "branch = false\nmax-subject-length = 40\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".lintje.toml"), "max-subject-length = 60\n").unwrap();
+        let subdir = dir.join("src");
+        std::fs::create_dir_all(&subdir).unwrap();
+
+        // The repository config file is found from a subdirectory and overrides the user config
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .arg("--no-color")
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&subdir)
+            .assert()
+            .success();
+        assert.stdout(predicate::str::contains(
+            "1 commit inspected, 0 errors detected",
+        ));
+
+        // Config files in subdirectories override the repository config file
+        std::fs::write(subdir.join(".lintje.toml"), "max-subject-length = 50\n").unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .arg("--no-color")
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&subdir)
+            .assert()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains("Error[SubjectLength]"))
+            .stdout(predicate::str::contains("BranchNameTicketNumber").not());
+        std::fs::remove_file(subdir.join(".lintje.toml")).unwrap();
+
+        // Command line options take precedence over the config files
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--max-subject-length", "50"])
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&subdir)
+            .assert()
+            .code(1);
+        assert.stdout(predicate::str::contains("Error[SubjectLength]"));
+
+        std::fs::write(dir.join(".lintje.toml"), "max-subject-lenght = 60\n").unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&dir)
+            .assert()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Unknown option `max-subject-lenght` in ",
+        ));
+
+        std::fs::write(dir.join(".lintje.toml"), "max-subject-length = [\n").unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&dir)
+            .assert()
+            .code(2);
+        assert.stdout(predicate::str::contains("Invalid config file: "));
+
+        // Config files that can't be fetched are skipped with a warning, so Lintje also runs
+        // offline
+        std::fs::write(
+            dir.join(".lintje.toml"),
+            "extends = \"https://127.0.0.1:9/lintje.toml\"\nmax-subject-length = 60\n",
+        )
+        .unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .arg("--no-color")
+            .env("XDG_CONFIG_HOME", &config_home)
+            .env("XDG_CACHE_HOME", dir.join("cache_home"))
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains(
+                "[WARN] Unable to fetch config file, skipping it: https://127.0.0.1:9/lintje.toml",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit inspected, 0 errors detected",
+            ));
+
+        std::fs::write(
+            dir.join(".lintje.toml"),
+            "extends = \"http://127.0.0.1:9/lintje.toml\"\n",
+        )
+        .unwrap();
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .env("XDG_CONFIG_HOME", &config_home)
+            .current_dir(&dir)
+            .assert()
+            .code(2);
+        assert.stdout(predicate::str::contains(
+            "Config files can only be fetched over HTTPS",
+        ));
+    }
+
+    #[test]
+    fn test_all_commits() {
+        compile_bin();
+        let dir = test_dir("all_commits");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "added some code", "This is a message.", "file");
+        create_commit_with_file(&dir, "Test commit", "I am a test commit.", "file2");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-branch", "--all"])
+            .current_dir(&dir)
+            .assert()
+            .failure()
+            .code(1);
+        assert
+            .stdout(predicate::str::contains(
+                "Error[SubjectMood]: The subject does not use the imperative grammatical mood",
+            ))
+            // Includes the first commit of the repository
+            .stdout(predicate::str::contains("3 commits inspected, "));
+    }
+
+    #[test]
+    fn test_debug_stats() {
+        compile_bin();
+        let dir = test_dir("debug_stats");
+        create_test_repo(&dir);
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--debug"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+        assert
+            .stdout(predicate::str::contains("[DEBUG] Subprocesses: "))
+            .stdout(predicate::str::contains(": git log --pretty="))
+            .stdout(predicate::str::contains("[DEBUG] Parsing commits: "))
+            .stdout(predicate::str::contains("[DEBUG] Commit subject rules: "))
+            .stdout(predicate::str::contains("[DEBUG] Branch rules: "));
+    }
+
+    #[test]
+    fn test_require_signature() {
+        compile_bin();
+        let dir = test_dir("require_signature");
+        create_test_repo(&dir);
+        create_commit_with_file(&dir, "Test commit", "I am a test commit", "file");
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        cmd.args(["--no-color", "--no-hints"])
+            .current_dir(&dir)
+            .assert()
+            .success();
+
+        let mut cmd = assert_cmd::Command::cargo_bin("lintje").unwrap();
+        let assert = cmd
+            .args(["--no-color", "--no-hints", "--require-signature"])
+            .current_dir(dir)
+            .assert()
+            .failure();
+        assert
+            .stdout(predicate::str::contains(
+                "Error[CommitSignature]: The commit is not signed",
+            ))
+            .stdout(predicate::str::contains(
+                "1 commit and branch inspected, 1 error detected",
+            ));
+    }
 }
@@ -0,0 +1,286 @@
+use crate::rule::Rule;
+use crate::tickets::TicketTracker;
+use core::ops::Range;
+use regex::{Regex, RegexBuilder};
+
+/// Options that change how rules validate commits and branches.
+#[derive(Debug, Default)]
+pub struct RuleOptions {
+    /// Maximum width of the subject. Defaults to 50 characters.
+    pub max_subject_length: Option<usize>,
+    /// Additional subject phrases to consider clichés, on top of the built-in phrases.
+    pub cliche_phrases: Vec<String>,
+    /// Additional words that make a subject a cliché when followed by at most one other word.
+    pub cliche_words: Vec<String>,
+    /// Only use the configured cliché words, not the built-in words.
+    pub replace_cliche_words: bool,
+    /// Additional words that are not in the imperative mood, for subjects in other languages.
+    pub mood_words: Vec<String>,
+    /// Only use the configured mood words, not the built-in English words.
+    pub replace_mood_words: bool,
+    /// Allow subjects to start with an emoji or emoji code, for projects that use gitmoji.
+    pub allow_gitmoji: bool,
+    /// Words that are not allowed in branch names.
+    pub forbidden_branch_words: Vec<String>,
+    /// Minimum number of `/` separated segments in branch names.
+    pub branch_min_depth: Option<usize>,
+    /// Maximum number of `/` separated segments in branch names.
+    pub branch_max_depth: Option<usize>,
+    /// Personal prefixes of branch names, like `tom/`. Only the part of the branch name after the
+    /// prefix is checked for ticket numbers and clichés.
+    pub branch_prefixes: Vec<String>,
+    /// Require a ticket number in branch names, instead of disallowing branch names of only a
+    /// ticket number.
+    pub require_branch_ticket: bool,
+    /// Patterns of ticket numbers branch names must contain. Replaces the built-in Jira ticket
+    /// pattern.
+    pub branch_ticket_patterns: Vec<Regex>,
+    /// Do not allow uppercase characters in branch names.
+    pub check_branch_case: bool,
+    /// Only allow ASCII letters, numbers, `-`, `/` and `_` in branch names.
+    pub check_branch_characters: bool,
+    /// Characters other than the default characters that are allowed in branch names.
+    pub branch_characters: String,
+    /// Allow branch names generated by GitHub, like `patch-1`.
+    pub allow_generated_branch_names: bool,
+    /// Report fixup, squash and work in progress commits as hints rather than errors.
+    pub allow_fixup: bool,
+    /// Branches commits can't be made on directly.
+    pub protected_branches: Vec<String>,
+    /// Issue trackers to verify the tickets referenced in commits with.
+    pub ticket_trackers: Vec<Box<dyn TicketTracker>>,
+    /// Report referenced tickets that are closed.
+    pub require_open_tickets: bool,
+    /// Words and phrases that are not allowed in the subject and message body.
+    pub forbidden_phrases: Vec<ForbiddenPhrase>,
+    /// Trailers the message body must end with, like `Signed-off-by`.
+    pub required_trailers: Vec<String>,
+    /// Require a `Signed-off-by` trailer from the commit author, for the Developer Certificate of
+    /// Origin.
+    pub require_dco: bool,
+    /// Require commits to be signed with a GPG or SSH signature.
+    pub require_signature: bool,
+    /// Check the subject for misspelled words.
+    pub check_spelling: bool,
+    /// Words the spell checker allows, and additional misspellings with their correction.
+    pub spelling_words: Vec<SpellingWord>,
+    /// Check the subject for characters that are not ASCII.
+    pub check_subject_ascii: bool,
+    /// Characters other than ASCII characters that are allowed in the subject.
+    pub subject_characters: String,
+    /// Minimum number of words in the subject. Not checked when not set.
+    pub subject_min_words: Option<usize>,
+    /// Minimum number of paragraphs in the message body. Not checked when not set.
+    pub message_min_paragraphs: Option<usize>,
+    /// Patterns of lines in the message body that are allowed to be longer than 72 characters.
+    pub line_length_exemptions: Vec<Regex>,
+    /// Patterns of ticket numbers the subject may not contain. Replaces the built-in Jira ticket
+    /// pattern.
+    pub ticket_patterns: Vec<Regex>,
+    /// Text that matches a ticket pattern, but is not a ticket number, like `UTF-8`.
+    pub ticket_exemptions: Vec<String>,
+    /// Require a ticket number in the subject, instead of disallowing it.
+    pub require_subject_ticket: bool,
+    /// Patterns of ticket references in the message body, on top of the built-in `Fixes #123`
+    /// and `Part of #123` references.
+    pub ticket_reference_patterns: Vec<Regex>,
+    /// Lint squash merge commits made by GitHub, like `Fix bug (#123)`, instead of ignoring them.
+    pub lint_squash_merges: bool,
+    /// Maximum size in bytes of files added in commits. Defaults to 1 MB.
+    pub max_file_size: Option<usize>,
+    /// Maximum number of files changed in a commit. Defaults to 50.
+    pub max_files_changed: Option<usize>,
+    /// Maximum number of top-level directories changed in a commit. Defaults to 5.
+    pub max_directories: Option<usize>,
+    /// Lockfiles and the manifests they belong to, like `Cargo.lock` and `Cargo.toml`. Replaces
+    /// the built-in lockfiles.
+    pub lockfiles: Vec<Lockfile>,
+    /// Paths commits are not allowed to change, like `.env` or `secrets/`.
+    pub forbidden_paths: Vec<PathPattern>,
+    /// Rules that are not validated at all.
+    pub disabled_rules: Vec<Rule>,
+}
+
+/// Options that select which commits are fetched from Git, rather than how they're validated.
+#[derive(Debug, Default)]
+pub struct FetchOptions {
+    /// Only fetch commits by authors matching this pattern.
+    pub author: Option<String>,
+    /// Only fetch commits made since this date.
+    pub since: Option<String>,
+}
+
+/// A word or phrase that is not allowed in commits, with advice about what to use instead.
+#[derive(Debug, Clone)]
+pub struct ForbiddenPhrase {
+    pub phrase: String,
+    pub advice: Option<String>,
+    regex: Option<Regex>,
+}
+
+impl ForbiddenPhrase {
+    /// Parse the phrase from the `phrase=advice` format. The advice is optional.
+    pub fn parse(value: &str) -> Self {
+        let (phrase, advice) = match value.split_once('=') {
+            Some((phrase, advice)) if !advice.trim().is_empty() => {
+                (phrase.trim(), Some(advice.trim().to_string()))
+            }
+            Some((phrase, _)) => (phrase.trim(), None),
+            None => (value.trim(), None),
+        };
+        Self {
+            phrase: phrase.to_string(),
+            advice,
+            regex: Self::build_regex(phrase),
+        }
+    }
+
+    // Only match whole words. A word boundary is only required on the sides of the phrase that
+    // start or end with a word character, so phrases like `#wip` match too. The boundaries don't
+    // consume the characters around the phrase, so phrases right after each other all match.
+    fn build_regex(phrase: &str) -> Option<Regex> {
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        let first = phrase.chars().next()?;
+        let last = phrase.chars().last()?;
+        let source = format!(
+            "{}{}{}",
+            if is_word_char(first) { r"\b" } else { "" },
+            regex::escape(phrase),
+            if is_word_char(last) { r"\b" } else { "" }
+        );
+        match RegexBuilder::new(&source).case_insensitive(true).build() {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                error!("ForbiddenPhrase: Invalid forbidden phrase: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Returns the byte ranges of the occurrences of the phrase in the line.
+    pub fn find_all(&self, line: &str) -> Vec<Range<usize>> {
+        match &self.regex {
+            Some(regex) => regex.find_iter(line).map(|found| found.range()).collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl PartialEq for ForbiddenPhrase {
+    fn eq(&self, other: &Self) -> bool {
+        self.phrase == other.phrase && self.advice == other.advice
+    }
+}
+
+/// A word for the spell checker: either a word that is spelled correctly, or a misspelling with
+/// its correction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpellingWord {
+    Allowed(String),
+    Misspelling(String, String),
+}
+
+impl SpellingWord {
+    /// Parse the word from the `misspelling=correction` format. Words without a correction are
+    /// allowed words.
+    pub fn parse(value: &str) -> Self {
+        match value.split_once('=') {
+            Some((misspelling, correction)) => Self::Misspelling(
+                misspelling.trim().to_lowercase(),
+                correction.trim().to_string(),
+            ),
+            None => Self::Allowed(value.trim().to_lowercase()),
+        }
+    }
+}
+
+/// A lockfile and the manifest that lists the dependencies it locks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lockfile {
+    pub lockfile: String,
+    pub manifest: String,
+}
+
+impl Lockfile {
+    /// Parse the lockfile from the `lockfile=manifest` format.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.split_once('=') {
+            Some((lockfile, manifest))
+                if !lockfile.trim().is_empty() && !manifest.trim().is_empty() =>
+            {
+                Ok(Self {
+                    lockfile: lockfile.trim().to_string(),
+                    manifest: manifest.trim().to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Invalid lockfile, expected the `lockfile=manifest` format: {}",
+                value
+            )),
+        }
+    }
+}
+
+/// A pattern of file paths in the `.gitignore` format, like `.env`, `*.pem` or `secrets/`.
+///
+/// Patterns without a `/` match files and directories with that name in any directory. Patterns
+/// ending with a `/` only match directories. A `*` matches anything but a `/`, and `**` matches
+/// anything.
+#[derive(Debug, Clone)]
+pub struct PathPattern {
+    pub pattern: String,
+    regex: Regex,
+}
+
+impl PathPattern {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let pattern = value.trim();
+        let (name, directory) = match pattern.strip_suffix('/') {
+            Some(name) => (name, true),
+            None => (pattern, false),
+        };
+        let anchored = name.contains('/');
+        let name = name.trim_start_matches('/');
+        if name.is_empty() {
+            return Err(format!("Invalid path pattern: {}", value));
+        }
+        let mut source = String::from(if anchored { "^" } else { "(?:^|/)" });
+        let mut chars = name.chars().peekable();
+        while let Some(character) = chars.next() {
+            match character {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    // `**/` also matches no directories at all
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        source.push_str("(?:.*/)?");
+                    } else {
+                        source.push_str(".*");
+                    }
+                }
+                '*' => source.push_str("[^/]*"),
+                '?' => source.push_str("[^/]"),
+                _ => source.push_str(&regex::escape(&character.to_string())),
+            }
+        }
+        source.push_str(if directory { "/" } else { "(?:/|$)" });
+        match Regex::new(&source) {
+            Ok(regex) => Ok(Self {
+                pattern: pattern.to_string(),
+                regex,
+            }),
+            Err(e) => Err(format!("Invalid path pattern: {}: {}", value, e)),
+        }
+    }
+
+    /// Returns true if the file path, relative to the repository root, matches the pattern.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+impl RuleOptions {
+    pub fn rule_disabled(&self, rule: Rule) -> bool {
+        self.disabled_rules.contains(&rule)
+    }
+}
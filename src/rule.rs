@@ -1,27 +1,61 @@
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Rule {
     MergeCommit,
     NeedsRebase,
+    RevertCommit,
+    CommitSignature,
     SubjectLength,
     SubjectMood,
     SubjectWhitespace,
     SubjectCapitalization,
     SubjectPunctuation,
     SubjectTicketNumber,
+    SubjectTicketPresence,
     SubjectPrefix,
     SubjectBuildTag,
     SubjectCliche,
+    SubjectDuplicate,
+    SubjectConfusable,
+    SubjectInvisibleCharacter,
+    SubjectSpelling,
+    SubjectRepeatedWord,
+    SubjectAscii,
+    SubjectWordCount,
+    SubjectTruncated,
     MessageEmptyFirstLine,
     MessagePresence,
+    MessageStructure,
     MessageLineLength,
     MessageTicketNumber,
+    MessageTrailer,
+    MessageSignOff,
+    MessageCoAuthor,
+    MessageInvisibleCharacter,
+    MessageDiff,
+    ForbiddenPhrase,
+    SecretLeak,
+    TicketReference,
     DiffPresence,
+    DiffLargeFile,
+    DiffLockfile,
+    DiffForbiddenPath,
+    DiffFileCount,
+    DiffUnrelatedChanges,
     BranchNameTicketNumber,
+    BranchNameTicketPresence,
     BranchNameLength,
     BranchNamePunctuation,
+    BranchNameCase,
+    BranchNameCharacters,
     BranchNameCliche,
+    BranchNameForbiddenWord,
+    BranchNameDepth,
+    BranchNameGenerated,
+    BranchNameProtected,
+    BranchNameConfusable,
+    BranchNameInvisibleCharacter,
 }
 
 impl fmt::Display for Rule {
@@ -29,47 +63,948 @@ impl fmt::Display for Rule {
         let label = match self {
             Rule::MergeCommit => "MergeCommit",
             Rule::NeedsRebase => "NeedsRebase",
+            Rule::RevertCommit => "RevertCommit",
+            Rule::CommitSignature => "CommitSignature",
             Rule::SubjectLength => "SubjectLength",
             Rule::SubjectMood => "SubjectMood",
             Rule::SubjectWhitespace => "SubjectWhitespace",
             Rule::SubjectCapitalization => "SubjectCapitalization",
             Rule::SubjectPunctuation => "SubjectPunctuation",
             Rule::SubjectTicketNumber => "SubjectTicketNumber",
+            Rule::SubjectTicketPresence => "SubjectTicketPresence",
             Rule::SubjectPrefix => "SubjectPrefix",
             Rule::SubjectBuildTag => "SubjectBuildTag",
             Rule::SubjectCliche => "SubjectCliche",
+            Rule::SubjectDuplicate => "SubjectDuplicate",
+            Rule::SubjectConfusable => "SubjectConfusable",
+            Rule::SubjectInvisibleCharacter => "SubjectInvisibleCharacter",
+            Rule::SubjectSpelling => "SubjectSpelling",
+            Rule::SubjectRepeatedWord => "SubjectRepeatedWord",
+            Rule::SubjectAscii => "SubjectAscii",
+            Rule::SubjectWordCount => "SubjectWordCount",
+            Rule::SubjectTruncated => "SubjectTruncated",
             Rule::MessageEmptyFirstLine => "MessageEmptyFirstLine",
             Rule::MessagePresence => "MessagePresence",
+            Rule::MessageStructure => "MessageStructure",
             Rule::MessageLineLength => "MessageLineLength",
             Rule::MessageTicketNumber => "MessageTicketNumber",
+            Rule::MessageTrailer => "MessageTrailer",
+            Rule::MessageSignOff => "MessageSignOff",
+            Rule::MessageCoAuthor => "MessageCoAuthor",
+            Rule::MessageInvisibleCharacter => "MessageInvisibleCharacter",
+            Rule::MessageDiff => "MessageDiff",
+            Rule::ForbiddenPhrase => "ForbiddenPhrase",
+            Rule::SecretLeak => "SecretLeak",
+            Rule::TicketReference => "TicketReference",
             Rule::DiffPresence => "DiffPresence",
+            Rule::DiffLargeFile => "DiffLargeFile",
+            Rule::DiffLockfile => "DiffLockfile",
+            Rule::DiffForbiddenPath => "DiffForbiddenPath",
+            Rule::DiffFileCount => "DiffFileCount",
+            Rule::DiffUnrelatedChanges => "DiffUnrelatedChanges",
             Rule::BranchNameTicketNumber => "BranchNameTicketNumber",
+            Rule::BranchNameTicketPresence => "BranchNameTicketPresence",
             Rule::BranchNameLength => "BranchNameLength",
             Rule::BranchNamePunctuation => "BranchNamePunctuation",
+            Rule::BranchNameCase => "BranchNameCase",
+            Rule::BranchNameCharacters => "BranchNameCharacters",
             Rule::BranchNameCliche => "BranchNameCliche",
+            Rule::BranchNameForbiddenWord => "BranchNameForbiddenWord",
+            Rule::BranchNameDepth => "BranchNameDepth",
+            Rule::BranchNameGenerated => "BranchNameGenerated",
+            Rule::BranchNameProtected => "BranchNameProtected",
+            Rule::BranchNameConfusable => "BranchNameConfusable",
+            Rule::BranchNameInvisibleCharacter => "BranchNameInvisibleCharacter",
         };
         write!(f, "{}", label)
     }
 }
 
+/// All rules Lintje validates, in the order they are documented.
+pub const RULES: [Rule; 54] = [
+    Rule::MergeCommit,
+    Rule::NeedsRebase,
+    Rule::RevertCommit,
+    Rule::CommitSignature,
+    Rule::SubjectLength,
+    Rule::SubjectMood,
+    Rule::SubjectWhitespace,
+    Rule::SubjectCapitalization,
+    Rule::SubjectPunctuation,
+    Rule::SubjectTicketNumber,
+    Rule::SubjectTicketPresence,
+    Rule::SubjectPrefix,
+    Rule::SubjectBuildTag,
+    Rule::SubjectCliche,
+    Rule::SubjectDuplicate,
+    Rule::SubjectConfusable,
+    Rule::SubjectInvisibleCharacter,
+    Rule::SubjectSpelling,
+    Rule::SubjectRepeatedWord,
+    Rule::SubjectAscii,
+    Rule::SubjectWordCount,
+    Rule::SubjectTruncated,
+    Rule::MessageEmptyFirstLine,
+    Rule::MessagePresence,
+    Rule::MessageStructure,
+    Rule::MessageLineLength,
+    Rule::MessageTicketNumber,
+    Rule::MessageTrailer,
+    Rule::MessageSignOff,
+    Rule::MessageCoAuthor,
+    Rule::MessageInvisibleCharacter,
+    Rule::MessageDiff,
+    Rule::ForbiddenPhrase,
+    Rule::SecretLeak,
+    Rule::TicketReference,
+    Rule::DiffPresence,
+    Rule::DiffLargeFile,
+    Rule::DiffLockfile,
+    Rule::DiffForbiddenPath,
+    Rule::DiffFileCount,
+    Rule::DiffUnrelatedChanges,
+    Rule::BranchNameTicketNumber,
+    Rule::BranchNameTicketPresence,
+    Rule::BranchNameLength,
+    Rule::BranchNamePunctuation,
+    Rule::BranchNameCase,
+    Rule::BranchNameCharacters,
+    Rule::BranchNameCliche,
+    Rule::BranchNameForbiddenWord,
+    Rule::BranchNameDepth,
+    Rule::BranchNameGenerated,
+    Rule::BranchNameProtected,
+    Rule::BranchNameConfusable,
+    Rule::BranchNameInvisibleCharacter,
+];
+
+const DOCS_URL: &str = "https://lintje.dev/docs/rules";
+
+// Commits with issues for these rules need to be rebased into other commits, so the format of
+// the commit doesn't matter.
+const REBASE_RULES: &[Rule] = &[Rule::MergeCommit, Rule::NeedsRebase];
+// Revert commits use the subject of the reverted commit, so the rules about the subject format
+// don't apply. The `RevertCommit` rule checks the message body instead of `MessagePresence`.
+const REVERT_RULES: &[Rule] = &[Rule::MergeCommit, Rule::NeedsRebase, Rule::RevertCommit];
+
+/// The group of rules a rule belongs to. Every group has its own documentation page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RuleCategory {
+    CommitType,
+    CommitSubject,
+    CommitMessage,
+    CommitChanges,
+    Branch,
+}
+
+impl RuleCategory {
+    pub fn title(self) -> &'static str {
+        match self {
+            RuleCategory::CommitType => "Commit type",
+            RuleCategory::CommitSubject => "Commit subject",
+            RuleCategory::CommitMessage => "Commit message",
+            RuleCategory::CommitChanges => "Commit changes",
+            RuleCategory::Branch => "Branch",
+        }
+    }
+
+    fn docs_page(self) -> &'static str {
+        match self {
+            RuleCategory::CommitType => "commit-type",
+            RuleCategory::CommitSubject => "commit-subject",
+            RuleCategory::CommitMessage => "commit-message",
+            RuleCategory::CommitChanges => "commit-changes",
+            RuleCategory::Branch => "branch",
+        }
+    }
+}
+
+/// Documentation about a rule, used to explain a rule to the user.
+#[derive(Debug)]
+pub struct RuleMetadata {
+    pub category: RuleCategory,
+    /// A description of what the rule checks.
+    pub description: &'static str,
+    /// Why the rule is validated.
+    pub rationale: &'static str,
+    /// Examples of values that are valid for this rule.
+    pub valid_examples: &'static [&'static str],
+    /// Examples of values that are invalid for this rule.
+    pub invalid_examples: &'static [&'static str],
+    /// If Lintje can fix issues of this rule automatically.
+    pub auto_fixable: bool,
+}
+
+impl Rule {
+    /// Returns the rules that cause this rule to be skipped when they report an issue. Either
+    /// because this rule would report the same problem, or because the problem doesn't matter
+    /// until the other issue is fixed. The `RevertCommit` rule causes rules to be skipped for all
+    /// revert commits, whether it reports an issue or not.
+    ///
+    /// Rules are validated after the rules they are skipped by.
+    pub fn skipped_by(self) -> &'static [Rule] {
+        match self {
+            Rule::SubjectLength => &[
+                Rule::MergeCommit,
+                Rule::NeedsRebase,
+                Rule::RevertCommit,
+                Rule::SubjectCliche,
+            ],
+            Rule::SubjectCapitalization => {
+                &[Rule::MergeCommit, Rule::NeedsRebase, Rule::SubjectPrefix]
+            }
+            Rule::MessageSignOff => &[Rule::MergeCommit, Rule::NeedsRebase, Rule::MessageTrailer],
+            Rule::MessageStructure => &[
+                Rule::MergeCommit,
+                Rule::NeedsRebase,
+                Rule::RevertCommit,
+                Rule::MessagePresence,
+            ],
+            Rule::SubjectPunctuation
+            | Rule::SubjectTicketNumber
+            | Rule::SubjectTicketPresence
+            | Rule::SubjectBuildTag
+            | Rule::SubjectSpelling
+            | Rule::SubjectRepeatedWord
+            | Rule::SubjectAscii
+            | Rule::MessagePresence => REVERT_RULES,
+            Rule::RevertCommit
+            | Rule::SubjectMood
+            | Rule::SubjectWhitespace
+            | Rule::SubjectPrefix
+            | Rule::SubjectCliche
+            | Rule::SubjectConfusable
+            | Rule::SubjectWordCount
+            | Rule::SubjectTruncated
+            | Rule::MessageEmptyFirstLine
+            | Rule::MessageLineLength
+            | Rule::MessageTicketNumber
+            | Rule::MessageTrailer
+            | Rule::MessageCoAuthor
+            | Rule::MessageDiff
+            | Rule::ForbiddenPhrase
+            | Rule::SecretLeak
+            | Rule::TicketReference => REBASE_RULES,
+            // Invisible characters can hide changes in any commit, including merge commits and
+            // commits that need to be rebased
+            Rule::SubjectInvisibleCharacter
+            | Rule::MessageInvisibleCharacter
+            | Rule::MergeCommit
+            | Rule::NeedsRebase
+            | Rule::CommitSignature
+            | Rule::SubjectDuplicate
+            | Rule::DiffPresence
+            | Rule::DiffLargeFile
+            | Rule::DiffLockfile
+            | Rule::DiffForbiddenPath
+            | Rule::DiffFileCount
+            | Rule::DiffUnrelatedChanges
+            | Rule::BranchNameTicketNumber
+            | Rule::BranchNameTicketPresence
+            | Rule::BranchNameLength
+            | Rule::BranchNamePunctuation
+            | Rule::BranchNameCase
+            | Rule::BranchNameCharacters
+            | Rule::BranchNameCliche
+            | Rule::BranchNameForbiddenWord
+            | Rule::BranchNameDepth
+            | Rule::BranchNameGenerated
+            | Rule::BranchNameProtected
+            | Rule::BranchNameConfusable
+            | Rule::BranchNameInvisibleCharacter => &[],
+        }
+    }
+
+    /// Returns the documentation URL of this rule on the Lintje website.
+    pub fn doc_url(self) -> String {
+        format!(
+            "{}/{}/#{}",
+            DOCS_URL,
+            self.metadata().category.docs_page(),
+            self.to_string().to_lowercase()
+        )
+    }
+
+    pub fn metadata(self) -> RuleMetadata {
+        match self {
+            Rule::MergeCommit => RuleMetadata {
+                category: RuleCategory::CommitType,
+                description: "Checks if the commit merges a remote branch into its local copy, \
+                    like a `git pull` without rebasing does.",
+                rationale: "Merging the remote branch into the local branch of the same name adds \
+                    merge commits that don't describe a change to the history. Rebase the local \
+                    branch on the remote branch instead.",
+                valid_examples: &[
+                    "Merge pull request #123 from repo/branch",
+                    "Merge branch 'main' into feature-branch",
+                ],
+                invalid_examples: &["Merge branch 'main' of github.com:org/repo into main"],
+                auto_fixable: false,
+            },
+            Rule::NeedsRebase => RuleMetadata {
+                category: RuleCategory::CommitType,
+                description: "Checks if the commit is a fixup or squash commit. When a range \
+                    of commits is linted, it also checks if the commit the fixup or squash \
+                    commit targets is an older commit in the range.",
+                rationale: "Fixup and squash commits are meant to be squashed into other \
+                    commits with an interactive rebase before the branch is merged.",
+                valid_examples: &["Fix bug in user signup"],
+                invalid_examples: &["fixup! Fix bug in user signup", "squash! Fix bug"],
+                auto_fixable: false,
+            },
+            Rule::RevertCommit => RuleMetadata {
+                category: RuleCategory::CommitType,
+                description: "Checks if revert commits explain why the change was reverted. \
+                    Revert commits are not checked by the rules about the subject format and \
+                    message body presence.",
+                rationale: "The message body Git generates for revert commits only \
+                    references the reverted commit. Without the reason for the revert it's \
+                    unclear when the change can be applied again.",
+                valid_examples: &[
+                    "Revert \"Add login form\"\n\nThis reverts commit abc123.\n\n\
+                    The form broke the signup page.",
+                ],
+                invalid_examples: &["Revert \"Add login form\"\n\nThis reverts commit abc123."],
+                auto_fixable: false,
+            },
+            Rule::CommitSignature => RuleMetadata {
+                category: RuleCategory::CommitType,
+                description: "Checks if the commit is signed with a valid GPG or SSH signature. \
+                    Signatures made with a revoked key are reported as errors, expired \
+                    signatures and keys as hints. Only checked when signatures are required.",
+                rationale: "Signed commits prove the commit was made by the author, for \
+                    repositories with a signing policy.",
+                valid_examples: &["A commit made with `git commit --gpg-sign`"],
+                invalid_examples: &["A commit made without a signature"],
+                auto_fixable: false,
+            },
+            Rule::SubjectLength => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description:
+                    "Checks if the subject is between 5 and 50 characters long. The maximum \
+                    length is configurable with the `--max-subject-length` option.",
+                rationale: "Short subjects do not describe the change in enough detail. Long \
+                    subjects are truncated by Git tools and hard to scan in the log.",
+                valid_examples: &["Fix bug in user signup"],
+                invalid_examples: &[
+                    "Fix",
+                    "Fix the bug in user signup that occurred when the email address was empty",
+                ],
+                auto_fixable: false,
+            },
+            Rule::SubjectMood => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject uses the imperative grammatical mood.",
+                rationale: "The subject should complete the sentence \"If applied, this commit \
+                    will ...\", matching the messages Git itself generates.",
+                valid_examples: &["Fix bug", "Add feature"],
+                invalid_examples: &["Fixed bug", "Fixing bug", "Fixes bug"],
+                auto_fixable: false,
+            },
+            Rule::SubjectWhitespace => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject starts with whitespace.",
+                rationale: "Leading whitespace misaligns the subject in the Git log.",
+                valid_examples: &["Fix bug"],
+                invalid_examples: &[" Fix bug"],
+                auto_fixable: false,
+            },
+            Rule::SubjectCapitalization => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject starts with a capital letter, and is not \
+                    written in all caps.",
+                rationale: "The subject is a title of the change, and titles start with a \
+                    capital letter. Subjects in all caps are harder to read and stand out in \
+                    the log.",
+                valid_examples: &["Fix bug"],
+                invalid_examples: &["fix bug", "FIX LOGIN BUG"],
+                auto_fixable: false,
+            },
+            Rule::SubjectPunctuation => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject starts or ends with punctuation.",
+                rationale: "The subject is a title of the change, and titles do not end with \
+                    punctuation. Punctuation at the start usually indicates a prefix or emoji.",
+                valid_examples: &["Fix bug"],
+                invalid_examples: &["Fix bug.", "!Fix bug", "Fix bug?"],
+                auto_fixable: false,
+            },
+            Rule::SubjectTicketNumber => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains a ticket number.",
+                rationale: "Ticket numbers take up space in the subject without describing the \
+                    change. Move them to the message body instead.",
+                valid_examples: &["Fix bug in user signup"],
+                invalid_examples: &["JIRA-123 Fix bug", "Fix bug (#123)", "Fix #123"],
+                auto_fixable: false,
+            },
+            Rule::SubjectTicketPresence => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains a ticket number. Only checked when \
+                    enabled with the `--require-subject-ticket` option, which disables the \
+                    SubjectTicketNumber rule.",
+                rationale: "Some projects require every commit to reference a ticket in the \
+                    subject, so the reason for the change can be looked up from the Git log.",
+                valid_examples: &["JIRA-123 Fix bug in user signup"],
+                invalid_examples: &["Fix bug in user signup"],
+                auto_fixable: false,
+            },
+            Rule::SubjectPrefix => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject starts with a prefix.",
+                rationale: "Prefixes take up space in the subject without describing the \
+                    change. Describe the type of change in the subject itself.",
+                valid_examples: &["Fix bug in user signup"],
+                invalid_examples: &["fix: Bug in user signup", "[chore] Update dependencies"],
+                auto_fixable: false,
+            },
+            Rule::SubjectBuildTag => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains a build tag, like `[ci skip]`.",
+                rationale: "Build tags are instructions for CI systems and do not describe the \
+                    change. Move them to the message body instead.",
+                valid_examples: &["Update README"],
+                invalid_examples: &["Update README [ci skip]", "Update README [skip ci]"],
+                auto_fixable: false,
+            },
+            Rule::SubjectCliche => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject is a cliché, like \"Fix bug\" or \"WIP\".",
+                rationale: "Cliché subjects do not describe the change in enough detail to \
+                    understand it from the Git log.",
+                valid_examples: &["Fix crash on signup with empty email address"],
+                invalid_examples: &["Fix bug", "WIP", "Update file"],
+                auto_fixable: false,
+            },
+            Rule::SubjectDuplicate => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject is similar to the subject of another commit \
+                    in the selected range.",
+                rationale: "Commits with similar subjects are usually follow-up changes that \
+                    can be squashed into the original commit.",
+                valid_examples: &["Add user signup form", "Validate email on signup"],
+                invalid_examples: &["Add user signup form", "Add user signup forms"],
+                auto_fixable: false,
+            },
+            Rule::SubjectConfusable => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains characters that look like ASCII \
+                    characters, like a Cyrillic `о` or typographic quotes.",
+                rationale: "Lookalike characters make commits hard to find with search tools \
+                    and can be used to spoof text. They are often inserted by word processors.",
+                valid_examples: &["Fix \"Save\" button"],
+                invalid_examples: &[
+                    "Fix \u{201C}Save\u{201D} button",
+                    "Fix l\u{043E}gin form (with a Cyrillic `о`)",
+                ],
+                auto_fixable: false,
+            },
+            Rule::SubjectInvisibleCharacter => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains zero-width or bidirectional \
+                    control characters.",
+                rationale: "Invisible characters make commits hard to find with search tools. \
+                    Bidirectional control characters change how text is displayed, which can \
+                    be used to make the subject look different from what it is.",
+                valid_examples: &["Fix user signup"],
+                invalid_examples: &["Fix user\u{200B}signup (with a zero width space)"],
+                auto_fixable: false,
+            },
+            Rule::SubjectSpelling => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains a word from Lintje's list of \
+                    common misspellings, or the misspellings added with the `--spelling-word` \
+                    option. It's not a dictionary based spell check, other typos are not \
+                    reported. Only checked when spell checking is enabled.",
+                rationale: "Typos in the subject make commits harder to find in the Git log \
+                    and are easy to fix before the commit is pushed.",
+                valid_examples: &["Fix the length of the username field"],
+                invalid_examples: &["Fix teh lenght of the username field"],
+                auto_fixable: false,
+            },
+            Rule::SubjectRepeatedWord => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains the same word twice in a row.",
+                rationale: "A repeated word is a common typo that is easy to miss when reading \
+                    the subject.",
+                valid_examples: &["Fix the bug"],
+                invalid_examples: &["Fix the the bug"],
+                auto_fixable: false,
+            },
+            Rule::SubjectAscii => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject only contains ASCII characters, and the \
+                    configured additional characters. Only checked when enabled with the \
+                    `--check-subject-ascii` option.",
+                rationale: "Some tools that process commit subjects, like changelog generators \
+                    and ticket systems, do not handle other characters well.",
+                valid_examples: &["Fix the cafe menu"],
+                invalid_examples: &["Fix the café menu", "Add login form ✨"],
+                auto_fixable: false,
+            },
+            Rule::SubjectWordCount => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject contains a minimum number of words. Only \
+                    checked when enabled with the `--subject-min-words` option.",
+                rationale: "Subjects of a single word, like \"Refactor\" or \"Cleanup\", are \
+                    short enough for the other subject rules, but do not describe the change.",
+                valid_examples: &["Refactor the user signup"],
+                invalid_examples: &["Refactor", "Cleanup"],
+                auto_fixable: false,
+            },
+            Rule::SubjectTruncated => RuleMetadata {
+                category: RuleCategory::CommitSubject,
+                description: "Checks if the subject was cut off, ending with an ellipsis or \
+                    continuing in the message body.",
+                rationale: "Some Git clients truncate long subjects and move the rest of the \
+                    sentence to the message body. The subject no longer describes the change.",
+                valid_examples: &["Fix crash on signup with empty email address"],
+                invalid_examples: &[
+                    "Fix crash on signup with empty…\n\n…email address",
+                    "Fix crash on signup...",
+                ],
+                auto_fixable: false,
+            },
+            Rule::MessageEmptyFirstLine => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the line after the subject is empty.",
+                rationale: "Git uses the empty line to separate the subject from the message \
+                    body. Without it, the message body becomes part of the subject.",
+                valid_examples: &["Fix bug\n\nMessage body"],
+                invalid_examples: &["Fix bug\nMessage body"],
+                auto_fixable: false,
+            },
+            Rule::MessagePresence => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the commit has a message body of a meaningful length.",
+                rationale: "The message body explains why the change was made, which the \
+                    subject and diff cannot.",
+                valid_examples: &["Fix bug\n\nThe signup crashed when the email was empty."],
+                invalid_examples: &["Fix bug", "Fix bug\n\nWIP"],
+                auto_fixable: false,
+            },
+            Rule::MessageStructure => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body contains a minimum number of \
+                    paragraphs, not counting the trailers. Only checked when enabled with the \
+                    `--message-min-paragraphs` option.",
+                rationale: "A message body of a single sentence rarely explains both what \
+                    changed and why it changed.",
+                valid_examples: &[
+                    "Fix bug\n\nThe signup crashed when the email was empty.\n\n\
+                    Validate the email before creating the user.",
+                ],
+                invalid_examples: &["Fix bug\n\nThe signup crashed."],
+                auto_fixable: false,
+            },
+            Rule::MessageLineLength => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the lines in the message body are at most 72 \
+                    characters long. Code blocks, Markdown tables and block quotes, lines with \
+                    URLs and lines matching the `--line-length-exemption` option are not \
+                    checked.",
+                rationale: "Git does not wrap lines in the message body. Long lines are hard to \
+                    read in terminals and other Git tools.",
+                valid_examples: &["Fix bug\n\nA message body line that is short enough."],
+                invalid_examples: &[
+                    "Fix bug\n\nA message body line that is much too long to read comfortably \
+                    in a terminal.",
+                ],
+                auto_fixable: false,
+            },
+            Rule::MessageTicketNumber => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body contains a ticket number or link. \
+                    Other reference formats, like Jira ticket numbers or issue URLs, are \
+                    accepted when configured with the `--ticket-reference-pattern` option.",
+                rationale: "A reference to the issue or ticket gives more context about why the \
+                    change was made.",
+                valid_examples: &["Fix bug\n\nFixes #123", "Fix bug\n\nCloses JIRA-123"],
+                invalid_examples: &["Fix bug\n\nNo reference to the issue."],
+                auto_fixable: false,
+            },
+            Rule::MessageTrailer => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body ends with the required trailers, like \
+                    `Signed-off-by:`. Only checked when trailers are required.",
+                rationale: "Projects can require trailers, like a sign-off to certify the \
+                    contributor has the right to submit the change.",
+                valid_examples: &["Fix bug\n\nSigned-off-by: Jane Doe <jane@example.com>"],
+                invalid_examples: &["Fix bug\n\nThe signup crashed when the email was empty."],
+                auto_fixable: false,
+            },
+            Rule::MessageSignOff => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body contains a `Signed-off-by:` trailer \
+                    with the name and email address of the commit author. Only checked when the \
+                    Developer Certificate of Origin is required.",
+                rationale: "Projects that use the Developer Certificate of Origin require \
+                    authors to certify they have the right to submit the change by signing off \
+                    their commits.",
+                valid_examples: &["Fix bug\n\nSigned-off-by: Jane Doe <jane@example.com>"],
+                invalid_examples: &[
+                    "Fix bug\n\nThe signup crashed when the email was empty.",
+                    "Fix bug\n\nSigned-off-by: John Doe <john@example.com> (authored by Jane Doe)",
+                ],
+                auto_fixable: false,
+            },
+            Rule::MessageCoAuthor => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the `Co-authored-by:` trailers use the `Name <email>` \
+                    format.",
+                rationale: "GitHub only credits co-authors for trailers in the `Name <email>` \
+                    format. Other trailers are ignored without warning.",
+                valid_examples: &["Fix bug\n\nCo-authored-by: Jane Doe <jane@example.com>"],
+                invalid_examples: &[
+                    "Fix bug\n\nCo-authored-by: Jane Doe",
+                    "Fix bug\n\nCo-authored-by: jane@example.com",
+                ],
+                auto_fixable: false,
+            },
+            Rule::MessageInvisibleCharacter => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body contains zero-width or bidirectional \
+                    control characters.",
+                rationale: "Invisible characters make commits hard to find with search tools. \
+                    Bidirectional control characters change how text is displayed, which can \
+                    be used to make the message look different from what it is.",
+                valid_examples: &["Fix bug\n\nThe signup crashed when the email was empty."],
+                invalid_examples: &[
+                    "Fix bug\n\nThe signup crashed when the \u{202E}email\u{202C} was empty.",
+                ],
+                auto_fixable: false,
+            },
+            Rule::MessageDiff => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the message body contains the output of `git diff`, \
+                    like `diff --git` lines and hunk headers. Diffs in fenced code blocks are \
+                    not reported.",
+                rationale: "The diff is already part of the commit. Diffs end up in the message \
+                    body when the message is written with the `verbatim` cleanup mode of the \
+                    `git commit --verbose` editor, or when terminal output is pasted in it.",
+                valid_examples: &["Fix bug\n\nThe signup crashed when the email was empty."],
+                invalid_examples: &[
+                    "Fix bug\n\ndiff --git a/signup.rb b/signup.rb\n--- a/signup.rb\n+++ b/signup.rb",
+                ],
+                auto_fixable: false,
+            },
+            Rule::ForbiddenPhrase => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the subject or message body contains a configured \
+                    forbidden word or phrase.",
+                rationale: "Teams can forbid words and phrases in commits, like names of \
+                    internal projects in public repositories, or reminders like \
+                    \"DO NOT MERGE\" that should not end up in the main branch.",
+                valid_examples: &["Add the login form"],
+                invalid_examples: &[
+                    "Add the login form DO NOT MERGE (with `DO NOT MERGE` as forbidden phrase)",
+                ],
+                auto_fixable: false,
+            },
+            Rule::SecretLeak => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the subject or message body contains something that \
+                    looks like a secret, like an AWS access key, an API token with a known \
+                    prefix or a password assignment.",
+                rationale: "Secrets in commit messages are easy to miss in review. Once the \
+                    commit is pushed the secret can't be removed from the history without \
+                    rewriting it, and needs to be revoked.",
+                valid_examples: &["Fix login with passwords containing spaces"],
+                invalid_examples: &["Fix login for the test account\n\npassword=hunter2"],
+                auto_fixable: false,
+            },
+            Rule::TicketReference => RuleMetadata {
+                category: RuleCategory::CommitMessage,
+                description: "Checks if the tickets referenced in the commit exist in the \
+                    configured issue tracker, and optionally if they are open. Only checked \
+                    when an issue tracker is configured.",
+                rationale: "References to tickets that do not exist, because of a typo or a \
+                    wrong project, do not give any context about the change.",
+                valid_examples: &["Fix bug\n\nFixes JIRA-123 (an existing ticket)"],
+                invalid_examples: &["Fix bug\n\nFixes JIRA-999999 (a ticket that doesn't exist)"],
+                auto_fixable: false,
+            },
+            Rule::DiffPresence => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if the commit contains any changes.",
+                rationale: "Empty commits add noise to the history without changing anything.",
+                valid_examples: &["A commit with changes to one or more files"],
+                invalid_examples: &["A commit made with `git commit --allow-empty`"],
+                auto_fixable: false,
+            },
+            Rule::DiffLargeFile => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if the commit adds files larger than the maximum file \
+                    size. Defaults to 1 MB.",
+                rationale: "Files added to the repository stay in its history, even after \
+                    they are removed again. Large files make every clone of the repository \
+                    slower.",
+                valid_examples: &["A commit that adds a source file"],
+                invalid_examples: &["A commit that adds a 50 MB video file"],
+                auto_fixable: false,
+            },
+            Rule::DiffLockfile => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if lockfiles, like `Cargo.lock` and `package-lock.json`, \
+                    only change together with their manifest, like `Cargo.toml` and \
+                    `package.json`.",
+                rationale: "Lockfile changes without manifest changes are often committed by \
+                    accident, after installing the dependencies with another version of the \
+                    package manager.",
+                valid_examples: &["A commit that changes `Cargo.toml` and `Cargo.lock`"],
+                invalid_examples: &["A commit that only changes `Cargo.lock`"],
+                auto_fixable: false,
+            },
+            Rule::DiffForbiddenPath => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if the commit changes files matching a configured \
+                    forbidden path, like `.env`, `*.pem` or `secrets/`.",
+                rationale: "Files with secrets, like environment files and private keys, \
+                    should not be committed. Once pushed, they stay in the history of the \
+                    repository.",
+                valid_examples: &["A commit that changes `.env.example`"],
+                invalid_examples: &["A commit that adds `.env` (with `.env` as forbidden path)"],
+                auto_fixable: false,
+            },
+            Rule::DiffFileCount => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if the commit changes more files than the maximum number \
+                    of files. Defaults to 50 files.",
+                rationale: "Commits that change many files are hard to review, and hard to \
+                    revert when only part of the change needs to be undone.",
+                valid_examples: &["A commit that changes 3 files"],
+                invalid_examples: &["A commit that changes 120 files"],
+                auto_fixable: false,
+            },
+            Rule::DiffUnrelatedChanges => RuleMetadata {
+                category: RuleCategory::CommitChanges,
+                description: "Checks if the commit changes files in more top-level directories \
+                    than the maximum number of directories. Defaults to 5 directories. Files \
+                    in the root directory are not counted.",
+                rationale: "Changes to many parts of the repository in one commit are often \
+                    unrelated changes. Atomic commits are easier to review and revert.",
+                valid_examples: &["A commit that changes files in `src/` and `tests/`"],
+                invalid_examples: &[
+                    "A commit that changes files in `api/`, `bin/`, `docs/`, `lib/`, `src/` and \
+                    `tests/`",
+                ],
+                auto_fixable: false,
+            },
+            Rule::BranchNameTicketNumber => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name only consists of a ticket number.",
+                rationale: "A ticket number alone does not describe the change the branch \
+                    contains.",
+                valid_examples: &["fix-user-signup", "123-fix-user-signup"],
+                invalid_examples: &["123", "JIRA-123", "fix-123"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameTicketPresence => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name contains a ticket number. Only checked \
+                    when enabled with the `--require-branch-ticket` option, which disables the \
+                    BranchNameTicketNumber rule.",
+                rationale: "Some teams link branches to tickets automatically, based on the \
+                    ticket number in the branch name.",
+                valid_examples: &["JIRA-123-fix-user-signup", "feature/JIRA-123"],
+                invalid_examples: &["fix-user-signup"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameLength => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name is at least 4 characters long.",
+                rationale: "Short branch names do not describe the change the branch contains.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["fix", "abc"],
+                auto_fixable: false,
+            },
+            Rule::BranchNamePunctuation => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name starts or ends with punctuation.",
+                rationale: "Punctuation at the start or end of the branch name does not add \
+                    meaning and is easy to mistype.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["fix-user-signup!", "_fix-user-signup"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameCase => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name contains uppercase characters. Only \
+                    checked when enabled with the `--check-branch-case` option.",
+                rationale: "Branch names that only differ in case conflict on case-insensitive \
+                    file systems, and mixed case branch names are easy to mistype.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["Fix-User-Signup", "JIRA-123-fix-user-signup"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameCharacters => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name only contains ASCII letters, numbers, \
+                    `-`, `/` and `_`, and the configured additional characters. Only checked \
+                    when enabled with the `--check-branch-characters` option.",
+                rationale: "Spaces, characters that are not ASCII and characters with a special \
+                    meaning in shells, like `$` and `&`, make branch names hard to type and \
+                    need to be quoted in scripts.",
+                valid_examples: &["fix-user-signup", "feature/fix_user_signup"],
+                invalid_examples: &["fix-user-signup$", "fix-café-menu", "fix&deploy"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameCliche => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name is a cliché, like `fix-bug` or `wip`.",
+                rationale: "Cliché branch names do not describe the change the branch contains.",
+                valid_examples: &["fix-user-signup-crash"],
+                invalid_examples: &["fix-bug", "wip", "feature"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameForbiddenWord => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name contains a configured forbidden word.",
+                rationale: "Teams can forbid words in branch names, like names of internal \
+                    projects in public repositories.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["tmp-fix-user-signup (with `tmp` as forbidden word)"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameDepth => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name has the configured minimum and maximum \
+                    number of `/` separated segments.",
+                rationale: "Teams can require a consistent branch name structure, like \
+                    `type/description`.",
+                valid_examples: &["feature/user-signup (with a minimum of 2 segments)"],
+                invalid_examples: &["user-signup (with a minimum of 2 segments)"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameGenerated => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name looks generated, like `patch-1`.",
+                rationale: "Generated branch names do not describe the change the branch \
+                    contains.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["patch-1", "tombruijn-patch-2", "revert-123-fix-signup"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameProtected => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch is a protected branch, like `main`, `master` \
+                    or `develop`. Only checked when protected branches are configured.",
+                rationale: "Changes to protected branches are made with pull requests. \
+                    Commits made directly on a protected branch can't be pushed.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["main", "master", "develop"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameConfusable => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name contains characters that look like ASCII \
+                    characters, like a Cyrillic `о` or an en dash.",
+                rationale: "Branch names with lookalike characters are hard to type and check \
+                    out, and can be used to spoof other branch names.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["fix\u{2013}user-signup", "fix-l\u{043E}gin-form"],
+                auto_fixable: false,
+            },
+            Rule::BranchNameInvisibleCharacter => RuleMetadata {
+                category: RuleCategory::Branch,
+                description: "Checks if the branch name contains zero-width or bidirectional \
+                    control characters.",
+                rationale: "Branch names with invisible characters are hard to type and check \
+                    out, and can be used to spoof other branch names.",
+                valid_examples: &["fix-user-signup"],
+                invalid_examples: &["fix-user\u{200B}-signup (with a zero width space)"],
+                auto_fixable: false,
+            },
+        }
+    }
+}
+
+/// Find a commit or branch rule by its name.
+pub fn rule_by_any_name(name: &str) -> Option<Rule> {
+    RULES.iter().find(|rule| rule.to_string() == name).copied()
+}
+
 pub fn rule_by_name(name: &str) -> Option<Rule> {
     match name {
         "MergeCommit" => Some(Rule::MergeCommit),
         "NeedsRebase" => Some(Rule::NeedsRebase),
+        "RevertCommit" => Some(Rule::RevertCommit),
+        "CommitSignature" => Some(Rule::CommitSignature),
         "SubjectLength" => Some(Rule::SubjectLength),
         "SubjectMood" => Some(Rule::SubjectMood),
         "SubjectWhitespace" => Some(Rule::SubjectWhitespace),
         "SubjectCapitalization" => Some(Rule::SubjectCapitalization),
         "SubjectPunctuation" => Some(Rule::SubjectPunctuation),
         "SubjectTicketNumber" => Some(Rule::SubjectTicketNumber),
+        "SubjectTicketPresence" => Some(Rule::SubjectTicketPresence),
         "SubjectBuildTag" => Some(Rule::SubjectBuildTag),
         "SubjectPrefix" => Some(Rule::SubjectPrefix),
         "SubjectCliche" => Some(Rule::SubjectCliche),
+        "SubjectDuplicate" => Some(Rule::SubjectDuplicate),
+        "SubjectConfusable" => Some(Rule::SubjectConfusable),
+        "SubjectInvisibleCharacter" => Some(Rule::SubjectInvisibleCharacter),
+        "SubjectSpelling" => Some(Rule::SubjectSpelling),
+        "SubjectRepeatedWord" => Some(Rule::SubjectRepeatedWord),
+        "SubjectAscii" => Some(Rule::SubjectAscii),
+        "SubjectWordCount" => Some(Rule::SubjectWordCount),
+        "SubjectTruncated" => Some(Rule::SubjectTruncated),
         "MessageEmptyFirstLine" => Some(Rule::MessageEmptyFirstLine),
         "MessagePresence" => Some(Rule::MessagePresence),
+        "MessageStructure" => Some(Rule::MessageStructure),
         "MessageLineLength" => Some(Rule::MessageLineLength),
         "MessageTicketNumber" => Some(Rule::MessageTicketNumber),
+        "MessageTrailer" => Some(Rule::MessageTrailer),
+        "MessageSignOff" => Some(Rule::MessageSignOff),
+        "MessageCoAuthor" => Some(Rule::MessageCoAuthor),
+        "MessageInvisibleCharacter" => Some(Rule::MessageInvisibleCharacter),
+        "MessageDiff" => Some(Rule::MessageDiff),
+        "ForbiddenPhrase" => Some(Rule::ForbiddenPhrase),
+        "SecretLeak" => Some(Rule::SecretLeak),
+        "TicketReference" => Some(Rule::TicketReference),
         "DiffPresence" => Some(Rule::DiffPresence),
+        "DiffLargeFile" => Some(Rule::DiffLargeFile),
+        "DiffLockfile" => Some(Rule::DiffLockfile),
+        "DiffForbiddenPath" => Some(Rule::DiffForbiddenPath),
+        "DiffFileCount" => Some(Rule::DiffFileCount),
+        "DiffUnrelatedChanges" => Some(Rule::DiffUnrelatedChanges),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{rule_by_name, Rule, RULES};
+
+    #[test]
+    fn test_rule_metadata() {
+        for rule in RULES {
+            let metadata = rule.metadata();
+            assert!(!metadata.description.is_empty(), "{}", rule);
+            assert!(!metadata.rationale.is_empty(), "{}", rule);
+            assert!(!metadata.valid_examples.is_empty(), "{}", rule);
+            assert!(!metadata.invalid_examples.is_empty(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn test_rule_doc_url() {
+        assert_eq!(
+            Rule::SubjectLength.doc_url(),
+            "https://lintje.dev/docs/rules/commit-subject/#subjectlength"
+        );
+        assert_eq!(
+            Rule::BranchNameLength.doc_url(),
+            "https://lintje.dev/docs/rules/branch/#branchnamelength"
+        );
+    }
+
+    #[test]
+    fn test_rule_by_name() {
+        for rule in RULES {
+            if let Some(found_rule) = rule_by_name(&rule.to_string()) {
+                assert_eq!(found_rule, rule);
+            }
+        }
+        assert_eq!(rule_by_name("Unknown"), None);
+    }
+}
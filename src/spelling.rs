@@ -0,0 +1,323 @@
+use crate::options::SpellingWord;
+use core::ops::Range;
+use regex::Regex;
+
+lazy_static! {
+    static ref WORD: Regex = Regex::new(r"[\p{L}']+").unwrap();
+}
+
+// Common misspellings of English words, and of words often used in commits, with their
+// correction. This is not a dictionary: words that are not listed are never reported. Only
+// misspellings that are not words themselves are listed, not spelling variants like
+// "cancelation" or other words like "calender", so that only obvious typos are reported. The
+// list is sorted by misspelling, so it can be searched.
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accidentaly", "accidentally"),
+    ("accomodate", "accommodate"),
+    ("acess", "access"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("algoritm", "algorithm"),
+    ("allways", "always"),
+    ("alot", "a lot"),
+    ("alredy", "already"),
+    ("ammount", "amount"),
+    ("apparantly", "apparently"),
+    ("appearence", "appearance"),
+    ("applicaton", "application"),
+    ("arguement", "argument"),
+    ("arguemnt", "argument"),
+    ("assosiate", "associate"),
+    ("asynchonous", "asynchronous"),
+    ("atribute", "attribute"),
+    ("attribue", "attribute"),
+    ("authenication", "authentication"),
+    ("authentification", "authentication"),
+    ("availabe", "available"),
+    ("availible", "available"),
+    ("becasue", "because"),
+    ("becuase", "because"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("boundry", "boundary"),
+    ("buisness", "business"),
+    ("catagory", "category"),
+    ("charater", "character"),
+    ("choosen", "chosen"),
+    ("collaps", "collapse"),
+    ("comand", "command"),
+    ("comit", "commit"),
+    ("commited", "committed"),
+    ("commiting", "committing"),
+    ("comparision", "comparison"),
+    ("compatability", "compatibility"),
+    ("compatable", "compatible"),
+    ("compatibilty", "compatibility"),
+    ("compiliation", "compilation"),
+    ("completly", "completely"),
+    ("componet", "component"),
+    ("concurent", "concurrent"),
+    ("configuraton", "configuration"),
+    ("conjuction", "conjunction"),
+    ("connecton", "connection"),
+    ("consistant", "consistent"),
+    ("containg", "containing"),
+    ("continous", "continuous"),
+    ("convertion", "conversion"),
+    ("correclty", "correctly"),
+    ("corrent", "correct"),
+    ("curent", "current"),
+    ("currenly", "currently"),
+    ("definately", "definitely"),
+    ("defualt", "default"),
+    ("defult", "default"),
+    ("dependancy", "dependency"),
+    ("dependecy", "dependency"),
+    ("dependencys", "dependencies"),
+    ("deprectated", "deprecated"),
+    ("descripton", "description"),
+    ("destory", "destroy"),
+    ("determin", "determine"),
+    ("developement", "development"),
+    ("diffrent", "different"),
+    ("directoy", "directory"),
+    ("dissapear", "disappear"),
+    ("documenation", "documentation"),
+    ("doesnt", "doesn't"),
+    ("duplicatd", "duplicated"),
+    ("efficent", "efficient"),
+    ("embarass", "embarrass"),
+    ("enviorment", "environment"),
+    ("enviroment", "environment"),
+    ("environent", "environment"),
+    ("equivalant", "equivalent"),
+    ("exection", "execution"),
+    ("existance", "existence"),
+    ("existant", "existent"),
+    ("experiance", "experience"),
+    ("explicitely", "explicitly"),
+    ("extention", "extension"),
+    ("familar", "familiar"),
+    ("fucntion", "function"),
+    ("funciton", "function"),
+    ("funtion", "function"),
+    ("futher", "further"),
+    ("gaurd", "guard"),
+    ("genrate", "generate"),
+    ("goverment", "government"),
+    ("grammer", "grammar"),
+    ("guarentee", "guarantee"),
+    ("hanlder", "handler"),
+    ("happend", "happened"),
+    ("heigth", "height"),
+    ("hierachy", "hierarchy"),
+    ("idenitfy", "identify"),
+    ("immediatly", "immediately"),
+    ("implmentation", "implementation"),
+    ("incompatable", "incompatible"),
+    ("incorect", "incorrect"),
+    ("independant", "independent"),
+    ("infomation", "information"),
+    ("informaton", "information"),
+    ("initalize", "initialize"),
+    ("initialze", "initialize"),
+    ("instace", "instance"),
+    ("instanciate", "instantiate"),
+    ("interupt", "interrupt"),
+    ("intial", "initial"),
+    ("lenght", "length"),
+    ("libary", "library"),
+    ("lisence", "license"),
+    ("maintainance", "maintenance"),
+    ("maintenence", "maintenance"),
+    ("managment", "management"),
+    ("mesage", "message"),
+    ("messsage", "message"),
+    ("middlware", "middleware"),
+    ("mispell", "misspell"),
+    ("mispelled", "misspelled"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("nessecary", "necessary"),
+    ("noticable", "noticeable"),
+    ("occassion", "occasion"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("occuring", "occurring"),
+    ("ommit", "omit"),
+    ("optinal", "optional"),
+    ("optionnal", "optional"),
+    ("orignal", "original"),
+    ("paramater", "parameter"),
+    ("paramter", "parameter"),
+    ("peformance", "performance"),
+    ("performace", "performance"),
+    ("permision", "permission"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("preceeding", "preceding"),
+    ("prefered", "preferred"),
+    ("presense", "presence"),
+    ("previos", "previous"),
+    ("priviledge", "privilege"),
+    ("privilige", "privilege"),
+    ("probaly", "probably"),
+    ("proccess", "process"),
+    ("programatically", "programmatically"),
+    ("propery", "property"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("recomend", "recommend"),
+    ("recursivly", "recursively"),
+    ("refactorred", "refactored"),
+    ("refered", "referred"),
+    ("refernce", "reference"),
+    ("refrence", "reference"),
+    ("relevent", "relevant"),
+    ("remvoe", "remove"),
+    ("reponse", "response"),
+    ("repostiory", "repository"),
+    ("repsonse", "response"),
+    ("requirment", "requirement"),
+    ("resouce", "resource"),
+    ("responce", "response"),
+    ("respository", "repository"),
+    ("retreive", "retrieve"),
+    ("retrun", "return"),
+    ("reuslt", "result"),
+    ("seperate", "separate"),
+    ("seperator", "separator"),
+    ("sequnce", "sequence"),
+    ("similiar", "similar"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("sucess", "success"),
+    ("sufficent", "sufficient"),
+    ("suport", "support"),
+    ("supress", "suppress"),
+    ("synchonize", "synchronize"),
+    ("syncronous", "synchronous"),
+    ("teh", "the"),
+    ("temparary", "temporary"),
+    ("temprary", "temporary"),
+    ("threshhold", "threshold"),
+    ("throught", "through"),
+    ("tommorow", "tomorrow"),
+    ("transfered", "transferred"),
+    ("truely", "truly"),
+    ("udpate", "update"),
+    ("unecessary", "unnecessary"),
+    ("unneccessary", "unnecessary"),
+    ("untill", "until"),
+    ("upate", "update"),
+    ("updat", "update"),
+    ("usefull", "useful"),
+    ("validaton", "validation"),
+    ("varaible", "variable"),
+    ("variabel", "variable"),
+    ("verison", "version"),
+    ("visable", "visible"),
+    ("whitch", "which"),
+    ("widht", "width"),
+    ("wierd", "weird"),
+    ("writting", "writing"),
+];
+
+/// Find the misspelled words in the text, from the bundled list of common misspellings and the
+/// user's words. Returns the byte range of every misspelled word and its correction, with the
+/// same capitalization as the misspelled word.
+pub fn find_misspellings(text: &str, user_words: &[SpellingWord]) -> Vec<(Range<usize>, String)> {
+    let mut misspellings = vec![];
+    for word in WORD.find_iter(text) {
+        let lowercase = word.as_str().to_lowercase();
+        if let Some(correction) = correction(&lowercase, user_words) {
+            misspellings.push((
+                word.range(),
+                match_capitalization(word.as_str(), correction),
+            ));
+        }
+    }
+    misspellings
+}
+
+fn correction<'a>(word: &str, user_words: &'a [SpellingWord]) -> Option<&'a str> {
+    for user_word in user_words {
+        match user_word {
+            SpellingWord::Allowed(allowed) if allowed == word => return None,
+            SpellingWord::Misspelling(misspelling, correction) if misspelling == word => {
+                return Some(correction);
+            }
+            _ => {}
+        }
+    }
+    MISSPELLINGS
+        .binary_search_by(|(misspelling, _)| (*misspelling).cmp(word))
+        .ok()
+        .map(|index| MISSPELLINGS[index].1)
+}
+
+// Capitalize the correction if the misspelled word is capitalized, like at the start of the
+// subject.
+fn match_capitalization(word: &str, correction: &str) -> String {
+    if matches!(word.chars().next(), Some(first) if first.is_uppercase()) {
+        let mut chars = correction.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else {
+        correction.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_misspellings, MISSPELLINGS};
+    use crate::options::SpellingWord;
+
+    #[test]
+    fn test_misspellings_sorted() {
+        for pair in MISSPELLINGS.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{} is not sorted", pair[1].0);
+        }
+        for (misspelling, _) in MISSPELLINGS {
+            assert_eq!(*misspelling, misspelling.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_find_misspellings() {
+        assert!(find_misspellings("Fix the login form", &[]).is_empty());
+        assert_eq!(
+            find_misspellings("Fix teh lenght of the form", &[]),
+            vec![(4..7, "the".to_string()), (8..14, "length".to_string())]
+        );
+        // Capitalized corrections
+        assert_eq!(
+            find_misspellings("Seperate the forms", &[]),
+            vec![(0..8, "Separate".to_string())]
+        );
+        // Whole words only
+        assert!(find_misspellings("Add tehran to the cities", &[]).is_empty());
+        // Words and spelling variants are not misspellings
+        assert!(find_misspellings("Fix the calender cancelation", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_misspellings_with_user_words() {
+        let words = vec![
+            SpellingWord::parse("Teh"),
+            SpellingWord::parse("wrok = work"),
+        ];
+        assert_eq!(words[0], SpellingWord::Allowed("teh".to_string()));
+        assert_eq!(
+            words[1],
+            SpellingWord::Misspelling("wrok".to_string(), "work".to_string())
+        );
+        assert_eq!(
+            find_misspellings("Fix teh wrok", &words),
+            vec![(8..12, "work".to_string())]
+        );
+    }
+}
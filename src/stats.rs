@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+lazy_static! {
+    static ref STATS: Mutex<Stats> = Mutex::new(Stats::default());
+}
+// Statistics are only recorded when enabled, so the commits validated in parallel don't wait on
+// the lock of the statistics when they're not printed.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Timing statistics of a Lintje run, printed with the `--debug` option to diagnose performance
+/// issues.
+#[derive(Debug, Default)]
+struct Stats {
+    commands: Vec<(String, Duration)>,
+    timings: Vec<(&'static str, Duration)>,
+    // The durations of the measurements nested in the currently running measurements, per thread.
+    // These are subtracted from the duration of the outer measurement.
+    nested: HashMap<ThreadId, Vec<Duration>>,
+}
+
+/// Start recording statistics, to print them with `log_summary` at the end of the run.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Record a subprocess that was run and how long it took.
+pub fn record_command(command: String, duration: Duration) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Ok(mut stats) = STATS.lock() {
+        stats.commands.push((command, duration));
+    }
+}
+
+/// Measure the duration of the block and add it to the total duration of the given name. The
+/// durations of measurements nested in the block are not included.
+pub fn measure<T, F: FnOnce() -> T>(name: &'static str, block: F) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return block();
+    }
+    let thread_id = thread::current().id();
+    if let Ok(mut stats) = STATS.lock() {
+        stats
+            .nested
+            .entry(thread_id)
+            .or_default()
+            .push(Duration::default());
+    }
+    let start = Instant::now();
+    let result = block();
+    let elapsed = start.elapsed();
+    if let Ok(mut stats) = STATS.lock() {
+        let nested = stats.nested.entry(thread_id).or_default();
+        let nested_duration = nested.pop().unwrap_or_default();
+        if let Some(parent) = nested.last_mut() {
+            *parent += elapsed;
+        }
+        let duration = elapsed.saturating_sub(nested_duration);
+        match stats.timings.iter_mut().find(|(timing, _)| *timing == name) {
+            Some((_, total)) => *total += duration,
+            None => stats.timings.push((name, duration)),
+        }
+    }
+    result
+}
+
+/// Log the subprocesses that were run and the total duration of every measurement.
+pub fn log_summary() {
+    let stats = match STATS.lock() {
+        Ok(stats) => stats,
+        Err(_) => return,
+    };
+    let total = stats
+        .commands
+        .iter()
+        .map(|(_, duration)| *duration)
+        .sum::<Duration>();
+    debug!(
+        "Subprocesses: {} run in {:.1?}",
+        stats.commands.len(),
+        total
+    );
+    for (command, duration) in &stats.commands {
+        debug!("  {:.1?}: {}", duration, command);
+    }
+    for (name, duration) in &stats.timings {
+        debug!("{}: {:.1?}", name, duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enable, measure, STATS};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    fn timing(name: &str) -> Duration {
+        STATS
+            .lock()
+            .unwrap()
+            .timings
+            .iter()
+            .find(|(timing, _)| *timing == name)
+            .map(|(_, duration)| *duration)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_measure() {
+        enable();
+        let result = measure("Test outer", || {
+            sleep(Duration::from_millis(20));
+            measure("Test inner", || sleep(Duration::from_millis(30)));
+            measure("Test inner", || sleep(Duration::from_millis(30)));
+            "result"
+        });
+        assert_eq!(result, "result");
+        assert!(timing("Test inner") >= Duration::from_millis(60));
+        assert!(timing("Test outer") >= Duration::from_millis(20));
+    }
+}
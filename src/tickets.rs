@@ -0,0 +1,49 @@
+use core::ops::Range;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TicketStatus {
+    Open,
+    Closed,
+    Missing,
+}
+
+/// An issue tracker that can look up the tickets referenced in commits. Commits are validated in
+/// parallel, so trackers are shared between threads.
+pub trait TicketTracker: std::fmt::Debug + Send + Sync {
+    /// Name of the issue tracker, used in issue messages.
+    fn name(&self) -> &str;
+
+    /// Returns the bytes ranges of all ticket references in the text.
+    fn ticket_references(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Fetch the status of the ticket from the issue tracker.
+    fn fetch_ticket_status(&self, reference: &str) -> Result<TicketStatus, String>;
+
+    /// Cache of ticket statuses already fetched, so that tickets referenced in multiple commits
+    /// are only fetched once.
+    fn cache(&self) -> &Mutex<HashMap<String, TicketStatus>>;
+
+    /// Returns the status of the ticket, fetching it from the issue tracker if it's not cached.
+    fn ticket_status(&self, reference: &str) -> Result<TicketStatus, String> {
+        if let Some(status) = self.cached_ticket_status(reference) {
+            return Ok(status);
+        }
+        // The cache isn't locked while fetching, so tickets of other commits can be fetched at
+        // the same time
+        let status = self.fetch_ticket_status(reference)?;
+        debug!("{} ticket {}: {:?}", self.name(), reference, status);
+        if let Ok(mut cache) = self.cache().lock() {
+            cache.insert(reference.to_string(), status);
+        }
+        Ok(status)
+    }
+
+    fn cached_ticket_status(&self, reference: &str) -> Option<TicketStatus> {
+        self.cache()
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(reference).copied())
+    }
+}
@@ -108,11 +108,10 @@ pub fn line_length_stats(line: &str, max_width: usize) -> (usize, MarkerStats) {
 /// # Examples
 ///
 /// ```
+/// # use lintje::utils::character_count_for_bytes_index;
 /// let s = "Hello world";
-///          ^ zero bytes index
-///               ^ starting bytes index mapped to character count
-///                ^ this character is the target
-/// assert_eq!(s.len(), 12);
+/// // The "w" at bytes index 6 is the 7th character
+/// assert_eq!(s.len(), 11);
 /// assert_eq!(character_count_for_bytes_index(s, 6), 7);
 /// ```
 pub fn character_count_for_bytes_index(string: &str, bytes_index: usize) -> usize {
@@ -141,13 +140,73 @@ pub fn pluralize(label: &str, count: usize) -> String {
     format!("{}{}", label, plural)
 }
 
+/// Format the size in bytes in the largest unit it's at least one of, like `1.5 MB`.
+pub fn format_file_size(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} {}", bytes, pluralize("byte", bytes));
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Calculate the Levenshtein edit distance between two strings: the number of characters that need
+/// to be inserted, removed or replaced to turn one string into the other.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars = b.chars().collect::<Vec<char>>();
+    let mut previous_row = (0..=b_chars.len()).collect::<Vec<usize>>();
+    for (a_index, a_char) in a.chars().enumerate() {
+        let mut row = vec![a_index + 1];
+        for (b_index, b_char) in b_chars.iter().enumerate() {
+            let cost = if a_char == *b_char { 0 } else { 1 };
+            let value = (previous_row[b_index] + cost)
+                .min(previous_row[b_index + 1] + 1)
+                .min(row[b_index] + 1);
+            row.push(value);
+        }
+        previous_row = row;
+    }
+    previous_row[b_chars.len()]
+}
+
+/// Join a list of values into a human readable sentence.
+///
+/// # Examples
+///
+/// ```
+/// # use lintje::utils::to_sentence;
+/// assert_eq!(to_sentence(&[1]), "1");
+/// assert_eq!(to_sentence(&[1, 2]), "1 and 2");
+/// assert_eq!(to_sentence(&[1, 2, 3]), "1, 2 and 3");
+/// ```
+pub fn to_sentence<T: ToString>(values: &[T]) -> String {
+    let values = values.iter().map(ToString::to_string).collect::<Vec<_>>();
+    match values.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.to_string(),
+        None => "".to_string(),
+    }
+}
+
 #[cfg(test)]
 pub mod test {
-    use super::{character_count_for_bytes_index, display_width, line_length_stats, MarkerStats};
+    use super::{
+        character_count_for_bytes_index, display_width, edit_distance, format_file_size,
+        line_length_stats, to_sentence, MarkerStats,
+    };
+    #[cfg(feature = "color")]
     use crate::formatter::formatted_context as formatted_context_real;
+    #[cfg(feature = "color")]
     use crate::issue::Issue;
+    #[cfg(feature = "color")]
     use termcolor::{BufferWriter, ColorChoice};
 
+    #[cfg(feature = "color")]
     pub fn formatted_context(issue: &Issue) -> String {
         let bufwtr = BufferWriter::stdout(ColorChoice::Never);
         let mut out = bufwtr.buffer();
@@ -409,4 +468,34 @@ pub mod test {
             }
         );
     }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("Fix test", "Fix test"), 0);
+        assert_eq!(edit_distance("Fix test", "Fix tests"), 1);
+        assert_eq!(edit_distance("Fix tests", "Fix test"), 1);
+        assert_eq!(edit_distance("Fix test", "Fix text"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("héllo", "hello"), 1);
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(1), "1 byte");
+        assert_eq!(format_file_size(1023), "1023 bytes");
+        assert_eq!(format_file_size(1024), "1.0 KB");
+        assert_eq!(format_file_size(1536 * 1024), "1.5 MB");
+        assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+        assert_eq!(format_file_size(2048 * 1024 * 1024 * 1024), "2048.0 GB");
+    }
+
+    #[test]
+    fn test_to_sentence() {
+        assert_eq!(to_sentence::<usize>(&[]), "");
+        assert_eq!(to_sentence(&[1]), "1");
+        assert_eq!(to_sentence(&[1, 2]), "1 and 2");
+        assert_eq!(to_sentence(&[1, 2, 3]), "1, 2 and 3");
+    }
 }